@@ -1,5 +1,6 @@
 use super::LocalFile;
 use crate::models::list::{ItemData, ItemType, PanelItem};
+use crate::operations::archive::ArchiveEntry;
 use crate::operations::s3::S3Object;
 
 pub fn modes_to_items() -> Vec<PanelItem> {
@@ -168,3 +169,51 @@ pub fn local_files_to_items(files: Vec<LocalFile>, has_parent: bool) -> Vec<Pane
 
     items
 }
+
+/// Inhalt des Archiv-Ordners `prefix`; Ordner ohne eigenen Eintrag im Archiv werden aus
+/// den Pfaden abgeleitet
+pub fn archive_entries_to_items(entries: &[ArchiveEntry], prefix: &str) -> Vec<PanelItem> {
+    let mut items = vec![PanelItem {
+        name: "..".to_string(),
+        item_type: ItemType::ParentDir,
+        size: None,
+        modified: None,
+        data: ItemData::Profile("..".to_string()),
+    }];
+
+    let mut dirs = std::collections::BTreeSet::new();
+    for entry in entries {
+        let Some(rest) = entry.path.strip_prefix(prefix).filter(|r| !r.is_empty()) else {
+            continue;
+        };
+        match rest.split_once('/') {
+            Some((dir, _)) => {
+                dirs.insert(dir.to_string());
+            }
+            None if entry.is_dir => {
+                dirs.insert(rest.to_string());
+            }
+            None => items.push(PanelItem {
+                name: rest.to_string(),
+                item_type: ItemType::File,
+                size: Some(entry.size),
+                modified: entry.modified,
+                data: ItemData::ArchiveEntry {
+                    path: entry.path.clone(),
+                },
+            }),
+        }
+    }
+
+    items.extend(dirs.into_iter().map(|dir| PanelItem {
+        data: ItemData::ArchiveEntry {
+            path: format!("{prefix}{dir}"),
+        },
+        name: dir,
+        item_type: ItemType::Directory,
+        size: None,
+        modified: None,
+    }));
+
+    items
+}
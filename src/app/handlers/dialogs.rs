@@ -42,6 +42,74 @@ pub fn show_filter_prompt(app: &mut App) {
     app.screen = Screen::Input;
 }
 
+pub fn show_open_url_prompt(app: &mut App) {
+    app.input.mode = InputMode::OpenUrl;
+    app.input.buffer = "s3://".to_string();
+    app.input.cursor_position = app.input.buffer.chars().count();
+    app.input.prompt = "Open public bucket anonymously (s3://bucket/prefix):".to_string();
+    app.prev_screen = Some(app.screen.clone());
+    app.screen = Screen::Input;
+}
+
+pub fn show_preview_search_prompt(app: &mut App, backward: bool) {
+    if super::is_hex_preview(app) {
+        app.show_info("Search works in the text view - press F4 to switch");
+        return;
+    }
+    app.input.mode = InputMode::PreviewSearch { backward };
+    // Letztes Suchmuster vorbelegen
+    app.input.buffer = app
+        .file_content_preview
+        .as_ref()
+        .and_then(|p| p.search.as_ref())
+        .map(|s| s.pattern.clone())
+        .unwrap_or_default();
+    app.input.cursor_position = app.input.buffer.chars().count();
+    app.input.prompt = if backward {
+        "Search backward (regex):".to_string()
+    } else {
+        "Search forward (regex):".to_string()
+    };
+    app.prev_screen = Some(app.screen.clone());
+    app.screen = Screen::Input;
+}
+
+pub fn show_goto_offset_prompt(app: &mut App) {
+    if super::is_compressed_preview(app) {
+        app.show_info("Go to offset is not available for compressed files");
+        return;
+    }
+    if super::is_record_preview(app) {
+        app.show_info("Go to offset is not available for record previews");
+        return;
+    }
+    app.input.mode = InputMode::PreviewGotoOffset;
+    app.input.buffer = "0x".to_string();
+    app.input.cursor_position = app.input.buffer.chars().count();
+    app.input.prompt = "Go to offset (decimal or 0x hex):".to_string();
+    app.prev_screen = Some(app.screen.clone());
+    app.screen = Screen::Input;
+}
+
+pub fn show_bandwidth_prompt(app: &mut App) {
+    app.input.mode = InputMode::BandwidthLimit;
+    app.input.buffer = app
+        .bandwidth
+        .global()
+        .limit()
+        .map(|limit| match limit {
+            l if l % (1024 * 1024) == 0 => format!("{}M", l / (1024 * 1024)),
+            l if l % 1024 == 0 => format!("{}k", l / 1024),
+            l => l.to_string(),
+        })
+        .unwrap_or_default();
+    app.input.cursor_position = app.input.buffer.chars().count();
+    app.input.prompt =
+        "Bandwidth limit for all transfers (e.g. 500k, 10M; empty for unlimited):".to_string();
+    app.prev_screen = Some(app.screen.clone());
+    app.screen = Screen::Input;
+}
+
 pub fn apply_filter(app: &mut App, pattern: String) {
     let panel = app.get_active_panel();
     if pattern.trim().is_empty() {
@@ -0,0 +1,229 @@
+use crate::app::{App, ExternalFile, ExternalS3Origin, PanelType, Screen};
+use crate::models::list::{ItemData, ItemType};
+use crate::operations::external::{self, FileFingerprint};
+use std::process::ExitStatus;
+
+/// Opens the selected file in the editor (o) or pager (v). S3 objects are downloaded
+/// to a temp copy first; the event loop starts the program.
+pub async fn open_in_external_program(app: &mut App, edit: bool) {
+    let panel = app.get_active_panel();
+    let panel_type = panel.panel_type.clone();
+    let s3_manager = panel.s3_manager.clone();
+    let Some(item) = panel.list_model.get_item(panel.selected_index).cloned() else {
+        return;
+    };
+    if item.item_type != ItemType::File {
+        app.show_info("Select a file to open it in an external program");
+        return;
+    }
+
+    let mut file = match (&panel_type, &item.data) {
+        (PanelType::LocalFilesystem { .. }, ItemData::LocalFile { path, .. }) => ExternalFile {
+            name: item.name.clone(),
+            path: path.clone(),
+            edit,
+            s3: None,
+            fingerprint: None,
+        },
+        (PanelType::S3Browser { bucket, .. }, ItemData::S3Object(s3_obj)) => {
+            let Some(manager) = s3_manager else {
+                app.show_error("No S3 connection available");
+                return;
+            };
+            let path = match external::temp_copy_path(&item.name) {
+                Ok(path) => path,
+                Err(e) => {
+                    app.show_error(&format!("Cannot open '{}': {e}", item.name));
+                    return;
+                }
+            };
+            match manager.download_file_with_etag(&s3_obj.key, &path).await {
+                Ok(etag) => ExternalFile {
+                    name: item.name.clone(),
+                    path,
+                    edit,
+                    s3: Some(ExternalS3Origin {
+                        manager,
+                        bucket: bucket.clone(),
+                        key: s3_obj.key.clone(),
+                        etag,
+                    }),
+                    fingerprint: None,
+                },
+                Err(e) => {
+                    external::remove_temp_copy(&path);
+                    app.show_error(&format!("Cannot download '{}': {e}", item.name));
+                    return;
+                }
+            }
+        }
+        _ => {
+            app.show_info("External programs are only available for local and S3 files");
+            return;
+        }
+    };
+
+    if edit {
+        match FileFingerprint::read(&file.path) {
+            Ok(fingerprint) => file.fingerprint = Some(fingerprint),
+            Err(e) => {
+                discard_temp_copy(&file);
+                app.show_error(&format!("Cannot read '{}': {e}", file.name));
+                return;
+            }
+        }
+    }
+    app.external_edit.pending = Some(file);
+}
+
+/// Runs after the program exited: a changed S3 copy opens the upload dialog
+pub async fn finish_external_program(
+    app: &mut App,
+    file: ExternalFile,
+    status: std::io::Result<ExitStatus>,
+) {
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            discard_temp_copy(&file);
+            app.show_error(&format!("External program exited with {status}"));
+            return;
+        }
+        Err(e) => {
+            discard_temp_copy(&file);
+            app.show_error(&format!("Cannot start external program: {e}"));
+            return;
+        }
+    }
+    let Some(fingerprint) = &file.fingerprint else {
+        discard_temp_copy(&file);
+        return;
+    };
+
+    let changed = match fingerprint.changed(&file.path) {
+        Ok(changed) => changed,
+        Err(e) => {
+            discard_temp_copy(&file);
+            app.show_error(&format!("Cannot check '{}' for changes: {e}", file.name));
+            return;
+        }
+    };
+    if !changed {
+        discard_temp_copy(&file);
+        app.show_info(&format!("No changes to '{}'", file.name));
+        return;
+    }
+
+    let Some(origin) = &file.s3 else {
+        reload_local_panel(app);
+        app.show_success(&format!("Saved '{}'", file.name));
+        return;
+    };
+
+    // Check before asking whether the object was changed in the meantime
+    let modified_remotely = match origin.manager.object_etag(&origin.key).await {
+        Ok(etag) => etag != origin.etag,
+        Err(_) => false, // The upload checks the ETag again (If-Match)
+    };
+    app.external_edit.upload = Some(file);
+    app.external_edit.modified_remotely = modified_remotely;
+    app.external_edit.button = 0;
+    app.prev_screen = Some(app.screen.clone());
+    app.screen = Screen::ExternalEditUpload;
+}
+
+/// Uploads or discards the changed copy. Returns true if the dialog can be closed.
+pub async fn confirm_external_upload(app: &mut App) -> bool {
+    let Some(file) = app.external_edit.upload.clone() else {
+        return true;
+    };
+    let Some(origin) = &file.s3 else {
+        return true;
+    };
+    if app.external_edit.button == 1 {
+        discard_temp_copy(&file);
+        app.external_edit.upload = None;
+        app.show_info(&format!("Changes to '{}' discarded", file.name));
+        return true;
+    }
+
+    // "Overwrite" after the warning uploads without If-Match
+    let if_match = if app.external_edit.modified_remotely {
+        None
+    } else {
+        origin.etag.as_deref()
+    };
+    match origin
+        .manager
+        .put_file_if_match(&file.path, &origin.key, if_match)
+        .await
+    {
+        Ok(true) => {
+            discard_temp_copy(&file);
+            app.external_edit.upload = None;
+            reload_s3_panel(app, &origin.bucket);
+            app.show_success(&format!(
+                "Uploaded '{}' to s3://{}/{}",
+                file.name, origin.bucket, origin.key
+            ));
+            true
+        }
+        Ok(false) => {
+            // Changed between the check and the upload: show the warning, ask again
+            app.external_edit.modified_remotely = true;
+            false
+        }
+        Err(e) => {
+            app.external_edit.upload = None;
+            app.show_error(&format!(
+                "Upload failed: {e} - the edited copy is kept at {}",
+                file.path.display()
+            ));
+            true
+        }
+    }
+}
+
+/// Esc in the upload dialog: don't upload, but keep the edited copy
+pub fn cancel_external_upload(app: &mut App) {
+    if let Some(file) = app.external_edit.upload.take() {
+        app.show_info(&format!(
+            "Not uploaded - the edited copy is kept at {}",
+            file.path.display()
+        ));
+    }
+}
+
+fn discard_temp_copy(file: &ExternalFile) {
+    if file.s3.is_some() {
+        external::remove_temp_copy(&file.path);
+    }
+}
+
+/// Shows the new size and modification time of the saved file
+fn reload_local_panel(app: &mut App) {
+    let PanelType::LocalFilesystem { path } = app.get_active_panel().panel_type.clone() else {
+        return;
+    };
+    let has_parent = path.parent().is_some();
+    if let Ok(files) = crate::app::navigation::read_local_directory(&path) {
+        app.get_active_panel()
+            .list_model
+            .set_items(crate::app::converters::local_files_to_items(
+                files, has_parent,
+            ));
+    }
+}
+
+fn reload_s3_panel(app: &mut App, uploaded_bucket: &str) {
+    if let PanelType::S3Browser {
+        profile,
+        bucket,
+        prefix,
+    } = app.get_active_panel().panel_type.clone()
+    {
+        if bucket == uploaded_bucket {
+            crate::app::navigation::start_background_list_objects(app, profile, bucket, prefix);
+        }
+    }
+}
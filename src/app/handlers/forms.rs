@@ -1,5 +1,6 @@
-use crate::app::{App, PanelType, Screen};
+use crate::app::{App, ConfigFormState, PanelType, Screen};
 use crate::message::Message;
+use crate::models::config::BucketConfig;
 use crate::models::list::{ItemData, ItemType, PanelItem};
 use anyhow::Result;
 
@@ -7,8 +8,13 @@ pub fn show_config_form(app: &mut App) {
     if let PanelType::BucketList { profile } = &app.get_active_panel().panel_type {
         app.config_form.profile = profile.clone();
         app.config_form.bucket = String::new();
+        app.config_form.base_prefix = String::new();
         app.config_form.description = String::new();
         app.config_form.region = "eu-west-1".to_string();
+        app.config_form.endpoint_url = String::new();
+        app.config_form.path_style = false;
+        app.config_form.anonymous = false;
+        app.config_form.bandwidth_limit = None;
         app.config_form.roles = vec![String::new()];
         app.config_form.field = 0;
         app.config_form.cursor = 0;
@@ -52,23 +58,19 @@ pub fn handle_config_form_message(app: &mut App, msg: Message) -> Result<()> {
     use Message::*;
 
     match msg {
-        ConfigFormUp => {
-            if app.config_form.field > 0 {
-                app.config_form.field -= 1;
-                app.config_form.cursor = get_config_form_field_len(app, app.config_form.field);
-            }
+        ConfigFormUp if app.config_form.field > 0 => {
+            app.config_form.field -= 1;
+            app.config_form.cursor = get_config_form_field_len(app, app.config_form.field);
         }
         ConfigFormDown => {
-            let max_field = app.config_form.roles.len() + 7; // Changed from 5 to 7 (added 2 fields)
+            let max_field = app.config_form.button_field() + 2; // Save/Test/Cancel buttons
             if app.config_form.field < max_field {
                 app.config_form.field += 1;
                 app.config_form.cursor = get_config_form_field_len(app, app.config_form.field);
             }
         }
-        ConfigFormLeft => {
-            if app.config_form.cursor > 0 {
-                app.config_form.cursor -= 1;
-            }
+        ConfigFormLeft if app.config_form.cursor > 0 => {
+            app.config_form.cursor -= 1;
         }
         ConfigFormRight => {
             let max_cursor = get_config_form_field_len(app, app.config_form.field);
@@ -101,10 +103,7 @@ pub fn handle_config_form_message(app: &mut App, msg: Message) -> Result<()> {
                 && app.config_form.cursor < app.config_form.endpoint_url.len()
             {
                 app.config_form.endpoint_url.remove(app.config_form.cursor);
-            } else if app.config_form.field > 5
-                && app.config_form.field <= app.config_form.roles.len() + 5
-            {
-                let role_idx = app.config_form.field - 6;
+            } else if let Some(role_idx) = app.config_form.role_index() {
                 if let Some(role) = app.config_form.roles.get_mut(role_idx) {
                     if app.config_form.cursor < role.len() {
                         role.remove(app.config_form.cursor);
@@ -137,53 +136,47 @@ pub fn handle_config_form_message(app: &mut App, msg: Message) -> Result<()> {
             } else if app.config_form.field == 5 && c == ' ' {
                 // Toggle path_style checkbox with space
                 app.config_form.path_style = !app.config_form.path_style;
-            } else if app.config_form.field > 5
-                && app.config_form.field <= app.config_form.roles.len() + 5
-            {
-                let role_idx = app.config_form.field - 6;
+            } else if app.config_form.field == 6 && c == ' ' {
+                // Toggle anonymous checkbox with space
+                app.config_form.anonymous = !app.config_form.anonymous;
+            } else if let Some(role_idx) = app.config_form.role_index() {
                 if let Some(role) = app.config_form.roles.get_mut(role_idx) {
                     role.insert(app.config_form.cursor, c);
                     app.config_form.cursor += 1;
                 }
             }
         }
-        ConfigFormBackspace => {
-            if app.config_form.cursor > 0 {
-                if app.config_form.field == 0 {
-                    app.config_form.cursor -= 1;
-                    app.config_form.bucket.remove(app.config_form.cursor);
-                } else if app.config_form.field == 1 {
-                    app.config_form.cursor -= 1;
-                    app.config_form.base_prefix.remove(app.config_form.cursor);
-                } else if app.config_form.field == 2 {
-                    app.config_form.cursor -= 1;
-                    app.config_form.description.remove(app.config_form.cursor);
-                } else if app.config_form.field == 3 {
-                    app.config_form.cursor -= 1;
-                    app.config_form.region.remove(app.config_form.cursor);
-                } else if app.config_form.field == 4 {
+        ConfigFormBackspace if app.config_form.cursor > 0 => {
+            if app.config_form.field == 0 {
+                app.config_form.cursor -= 1;
+                app.config_form.bucket.remove(app.config_form.cursor);
+            } else if app.config_form.field == 1 {
+                app.config_form.cursor -= 1;
+                app.config_form.base_prefix.remove(app.config_form.cursor);
+            } else if app.config_form.field == 2 {
+                app.config_form.cursor -= 1;
+                app.config_form.description.remove(app.config_form.cursor);
+            } else if app.config_form.field == 3 {
+                app.config_form.cursor -= 1;
+                app.config_form.region.remove(app.config_form.cursor);
+            } else if app.config_form.field == 4 {
+                app.config_form.cursor -= 1;
+                app.config_form.endpoint_url.remove(app.config_form.cursor);
+            } else if let Some(role_idx) = app.config_form.role_index() {
+                if let Some(role) = app.config_form.roles.get_mut(role_idx) {
                     app.config_form.cursor -= 1;
-                    app.config_form.endpoint_url.remove(app.config_form.cursor);
-                } else if app.config_form.field > 5
-                    && app.config_form.field <= app.config_form.roles.len() + 5
-                {
-                    let role_idx = app.config_form.field - 6;
-                    if let Some(role) = app.config_form.roles.get_mut(role_idx) {
-                        app.config_form.cursor -= 1;
-                        role.remove(app.config_form.cursor);
-                    }
+                    role.remove(app.config_form.cursor);
                 }
             }
         }
         ConfigFormAddRole => {
             app.config_form.roles.push(String::new());
         }
-        ConfigFormRemoveRole => {
-            if app.config_form.roles.len() > 1 {
-                app.config_form.roles.pop();
-                if app.config_form.field >= 5 + app.config_form.roles.len() {
-                    app.config_form.field = 5 + app.config_form.roles.len() - 1;
-                }
+        ConfigFormRemoveRole if app.config_form.roles.len() > 1 => {
+            app.config_form.roles.pop();
+            // Keep focus on the same button / move off the removed role
+            if app.config_form.field >= app.config_form.button_field() {
+                app.config_form.field -= 1;
             }
         }
         _ => {}
@@ -198,63 +191,62 @@ fn get_config_form_field_len(app: &App, field: usize) -> usize {
         2 => app.config_form.description.len(),
         3 => app.config_form.region.len(),
         4 => app.config_form.endpoint_url.len(),
-        5 => 0, // path_style checkbox has no cursor
-        _ if field <= app.config_form.roles.len() + 5 => {
-            let role_idx = field - 6;
-            app.config_form
-                .roles
-                .get(role_idx)
-                .map(|r| r.len())
-                .unwrap_or(0)
-        }
+        5 | 6 => 0, // path_style/anonymous checkboxes have no cursor
+        _ if field < app.config_form.button_field() => app
+            .config_form
+            .roles
+            .get(field - ConfigFormState::FIRST_ROLE_FIELD)
+            .map(|r| r.len())
+            .unwrap_or(0),
         _ => 0,
     }
 }
 
-pub fn save_config_form(app: &mut App) -> Result<()> {
-    if !app.config_form.bucket.trim().is_empty() {
-        let roles: Vec<String> = app
+/// Builds a bucket config from the current form values (empty optional fields become None)
+fn bucket_config_from_form(app: &App) -> BucketConfig {
+    let optional = |value: &String| {
+        if value.trim().is_empty() {
+            None
+        } else {
+            Some(value.clone())
+        }
+    };
+
+    BucketConfig {
+        name: app.config_form.bucket.clone(),
+        role_chain: app
             .config_form
             .roles
             .iter()
             .filter(|r| !r.trim().is_empty())
             .cloned()
-            .collect();
-
-        let description = if app.config_form.description.trim().is_empty() {
-            None
-        } else {
-            Some(app.config_form.description.clone())
-        };
-
-        let base_prefix = if app.config_form.base_prefix.trim().is_empty() {
-            None
+            .collect(),
+        region: app.config_form.region.clone(),
+        description: optional(&app.config_form.description),
+        base_prefix: optional(&app.config_form.base_prefix),
+        endpoint_url: optional(&app.config_form.endpoint_url),
+        path_style: if app.config_form.path_style {
+            Some(true)
         } else {
-            Some(app.config_form.base_prefix.clone())
-        };
-
-        let endpoint_url = if app.config_form.endpoint_url.trim().is_empty() {
             None
-        } else {
-            Some(app.config_form.endpoint_url.clone())
-        };
-
-        let path_style = if app.config_form.path_style {
+        },
+        anonymous: if app.config_form.anonymous {
             Some(true)
         } else {
             None
-        };
-
-        app.config_manager.add_bucket_to_profile(
-            &app.config_form.profile,
-            app.config_form.bucket.clone(),
-            roles,
-            app.config_form.region.clone(),
-            description,
-            base_prefix,
-            endpoint_url,
-            path_style,
-        )?;
+        },
+        bandwidth_limit: app.config_form.bandwidth_limit,
+        source: None,
+    }
+}
+
+pub fn save_config_form(app: &mut App) -> Result<()> {
+    if !app.config_form.bucket.trim().is_empty() {
+        let bucket_config = bucket_config_from_form(app);
+        let profile = app.config_form.profile.clone();
+
+        app.config_manager
+            .save_bucket_config(&profile, bucket_config)?;
 
         // Refresh bucket list if we're on BucketList screen
         let profile = app.config_form.profile.clone();
@@ -271,6 +263,73 @@ pub fn save_config_form(app: &mut App) -> Result<()> {
     Ok(())
 }
 
+/// Runs the connection test for the (unsaved) form values
+pub fn start_connection_test(app: &mut App) {
+    if app.config_form.bucket.trim().is_empty() {
+        app.show_error("Enter a bucket name first");
+        return;
+    }
+
+    if let Some(handle) = app.connection_test.task_handle.take() {
+        handle.abort();
+    }
+
+    let bucket_config = bucket_config_from_form(app);
+    let (receiver, handle) = crate::operations::diagnostics::start_connection_test(
+        app.config_form.profile.clone(),
+        bucket_config.clone(),
+    );
+
+    app.connection_test.title = format!(
+        "Connection test: {}/{} ({})",
+        app.config_form.profile, bucket_config.name, bucket_config.region
+    );
+    app.connection_test.steps.clear();
+    app.connection_test.receiver = Some(receiver);
+    app.connection_test.task_handle = Some(handle);
+    // prev_screen stays untouched: closing the test returns to the form
+    app.screen = Screen::ConnectionTest;
+}
+
+pub fn close_connection_test(app: &mut App) {
+    if let Some(handle) = app.connection_test.task_handle.take() {
+        handle.abort();
+    }
+    app.connection_test.receiver = None;
+    app.screen = Screen::ConfigForm;
+}
+
+/// Collects step updates from the running connection test (called from the event loop)
+pub fn check_connection_test_progress(app: &mut App) -> bool {
+    let Some(receiver) = app.connection_test.receiver.as_mut() else {
+        return false;
+    };
+
+    let mut changed = false;
+    loop {
+        match receiver.try_recv() {
+            Ok(update) => {
+                let steps = &mut app.connection_test.steps;
+                if update.index < steps.len() {
+                    steps[update.index] = update.step;
+                } else {
+                    steps.push(update.step);
+                }
+                changed = true;
+            }
+            Err(tokio::sync::mpsc::error::TryRecvError::Empty) => break,
+            Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => {
+                // Test finished
+                app.connection_test.receiver = None;
+                app.connection_test.task_handle = None;
+                changed = true;
+                break;
+            }
+        }
+    }
+    changed
+}
+
 pub fn edit_bucket_config(app: &mut App) {
     let panel_type = app.get_active_panel().panel_type.clone();
     let selected_index = app.get_active_panel().selected_index;
@@ -293,6 +352,15 @@ pub fn edit_bucket_config(app: &mut App) {
         {
             let bucket_config = bucket_config.clone();
 
+            if app.config_manager.is_project_bucket(&bucket_config) {
+                app.show_info(&format!(
+                    "Bucket '{}' is defined in {} - edit that file instead",
+                    bucket_config.name,
+                    bucket_config.source_label().unwrap_or_default()
+                ));
+                return;
+            }
+
             app.config_form.profile = profile;
             app.config_form.bucket = bucket_config.name.clone();
             app.config_form.base_prefix = bucket_config.base_prefix.clone().unwrap_or_default();
@@ -300,6 +368,8 @@ pub fn edit_bucket_config(app: &mut App) {
             app.config_form.region = bucket_config.region.clone();
             app.config_form.endpoint_url = bucket_config.endpoint_url.clone().unwrap_or_default();
             app.config_form.path_style = bucket_config.path_style.unwrap_or(false);
+            app.config_form.anonymous = bucket_config.is_anonymous();
+            app.config_form.bandwidth_limit = bucket_config.bandwidth_limit;
             app.config_form.roles = if bucket_config.role_chain.is_empty() {
                 vec![String::new()]
             } else {
@@ -333,10 +403,24 @@ pub fn delete_bucket_config(app: &mut App) -> Result<()> {
             ..
         }) = item
         {
+            let bucket_config = bucket_config.clone();
             let bucket_name = bucket_config.name.clone();
 
-            app.config_manager
-                .remove_bucket_from_profile(&profile, &bucket_name)?;
+            if app.config_manager.is_project_bucket(&bucket_config) {
+                let source = bucket_config.source_label().unwrap_or_default();
+                app.show_info(&format!(
+                    "Bucket '{bucket_name}' is defined in {source} - edit that file instead"
+                ));
+                return Ok(());
+            }
+
+            if let Err(e) = app
+                .config_manager
+                .remove_bucket_from_profile(&profile, &bucket_name)
+            {
+                app.show_error(&format!("Failed to delete bucket '{bucket_name}': {e}"));
+                return Ok(());
+            }
 
             let buckets = app.config_manager.get_buckets_for_profile(&profile);
             let panel = app.get_active_panel();
@@ -358,30 +442,24 @@ pub fn handle_profile_form_message(app: &mut App, msg: Message) -> Result<()> {
     use Message::*;
 
     match msg {
-        ProfileFormUp => {
-            if app.profile_form.field > 0 {
-                app.profile_form.field -= 1;
-                app.profile_form.cursor = match app.profile_form.field {
-                    0 => app.profile_form.description.len(),
-                    1 => app.profile_form.setup_script.len(),
-                    _ => 0,
-                };
-            }
+        ProfileFormUp if app.profile_form.field > 0 => {
+            app.profile_form.field -= 1;
+            app.profile_form.cursor = match app.profile_form.field {
+                0 => app.profile_form.description.len(),
+                1 => app.profile_form.setup_script.len(),
+                _ => 0,
+            };
         }
-        ProfileFormDown => {
-            if app.profile_form.field < 3 {
-                app.profile_form.field += 1;
-                app.profile_form.cursor = match app.profile_form.field {
-                    0 => app.profile_form.description.len(),
-                    1 => app.profile_form.setup_script.len(),
-                    _ => 0,
-                };
-            }
+        ProfileFormDown if app.profile_form.field < 3 => {
+            app.profile_form.field += 1;
+            app.profile_form.cursor = match app.profile_form.field {
+                0 => app.profile_form.description.len(),
+                1 => app.profile_form.setup_script.len(),
+                _ => 0,
+            };
         }
-        ProfileFormLeft => {
-            if app.profile_form.cursor > 0 {
-                app.profile_form.cursor -= 1;
-            }
+        ProfileFormLeft if app.profile_form.cursor > 0 => {
+            app.profile_form.cursor -= 1;
         }
         ProfileFormRight => {
             let max_cursor = match app.profile_form.field {
@@ -429,17 +507,15 @@ pub fn handle_profile_form_message(app: &mut App, msg: Message) -> Result<()> {
                 app.profile_form.cursor += 1;
             }
         }
-        ProfileFormBackspace => {
-            if app.profile_form.cursor > 0 {
-                if app.profile_form.field == 0 {
-                    app.profile_form.cursor -= 1;
-                    app.profile_form.description.remove(app.profile_form.cursor);
-                } else if app.profile_form.field == 1 {
-                    app.profile_form.cursor -= 1;
-                    app.profile_form
-                        .setup_script
-                        .remove(app.profile_form.cursor);
-                }
+        ProfileFormBackspace if app.profile_form.cursor > 0 => {
+            if app.profile_form.field == 0 {
+                app.profile_form.cursor -= 1;
+                app.profile_form.description.remove(app.profile_form.cursor);
+            } else if app.profile_form.field == 1 {
+                app.profile_form.cursor -= 1;
+                app.profile_form
+                    .setup_script
+                    .remove(app.profile_form.cursor);
             }
         }
         _ => {}
@@ -460,28 +536,8 @@ pub fn save_profile_config(app: &mut App) -> Result<()> {
         Some(app.profile_form.setup_script.clone())
     };
 
-    if let Some(profile_config) = app
-        .config_manager
-        .app_config
-        .profiles
-        .iter_mut()
-        .find(|p| p.name == app.profile_form.name)
-    {
-        profile_config.description = description;
-        profile_config.setup_script = setup_script;
-    } else {
-        app.config_manager
-            .app_config
-            .profiles
-            .push(crate::models::config::ProfileConfig {
-                name: app.profile_form.name.clone(),
-                buckets: Vec::new(),
-                setup_script,
-                description,
-            });
-    }
-
-    app.config_manager.save()?;
+    app.config_manager
+        .set_profile_details(&app.profile_form.name, description, setup_script)?;
     app.show_success("Profile configuration saved!");
     Ok(())
 }
@@ -14,6 +14,39 @@ pub async fn handle_input_submit(app: &mut App) -> Result<()> {
             app.input.mode = InputMode::None;
             apply_filter(app, pattern);
         }
+        InputMode::OpenUrl => {
+            let url = app.input.buffer.trim().to_string();
+            app.input.mode = InputMode::None;
+            crate::app::navigation::open_anonymous_url(app, &url).await?;
+        }
+        InputMode::BandwidthLimit => {
+            let value = app.input.buffer.trim().to_string();
+            app.input.mode = InputMode::None;
+            match crate::operations::throttle::parse_rate(&value) {
+                Some(limit) => {
+                    app.bandwidth.global().set_limit(limit);
+                    app.show_success(&match limit {
+                        Some(limit) => format!(
+                            "Bandwidth limit: {}/s",
+                            crate::ui::helpers::format_size(limit)
+                        ),
+                        None => "Bandwidth limit removed".to_string(),
+                    });
+                }
+                None => app.show_error(&format!("Invalid bandwidth limit: '{value}'")),
+            }
+        }
+        InputMode::PreviewSearch { backward } => {
+            let backward = *backward;
+            let pattern = app.input.buffer.clone();
+            app.input.mode = InputMode::None;
+            crate::app::handlers::start_preview_search(app, &pattern, backward);
+        }
+        InputMode::PreviewGotoOffset => {
+            let offset = app.input.buffer.clone();
+            app.input.mode = InputMode::None;
+            crate::app::handlers::goto_preview_offset(app, &offset).await;
+        }
         InputMode::Rename => {
             let old_path = app.input.rename_original_path.clone();
             let new_path = app.input.buffer.clone();
@@ -37,11 +70,10 @@ pub async fn handle_input_submit(app: &mut App) -> Result<()> {
                 _ => (None, None),
             };
 
-            let file_size = if let Ok(metadata) = std::fs::metadata(&path) {
-                metadata.len()
-            } else {
-                0
-            };
+            let source = std::fs::metadata(&path)
+                .map(|metadata| crate::operations::conflict::FileStat::from_metadata(&metadata))
+                .unwrap_or_default();
+            let file_size = source.size;
 
             // Queue-First: Always add to queue as Pending
             // Queue processing will start the transfer automatically
@@ -56,10 +88,15 @@ pub async fn handle_input_submit(app: &mut App) -> Result<()> {
                 bucket,
                 dest_profile: None,
                 dest_bucket: None,
+                upload_id: None,
+                etag: None,
+                retries: 0,
+                stats: Default::default(),
+                priority: Default::default(),
             };
 
-            // Add to queue - queue processing handles the rest
-            app.file_operation_queue.push(operation);
+            // Add to queue (asks first if the key exists) - queue processing handles the rest
+            app.enqueue_transfer(operation, source).await?;
         }
         _ => {}
     }
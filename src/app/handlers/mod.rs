@@ -1,10 +1,12 @@
 pub mod dialogs;
+pub mod external;
 pub mod forms;
 pub mod input;
 pub mod navigation;
 pub mod preview;
 
 pub use dialogs::*;
+pub use external::*;
 pub use forms::*;
 pub use input::*;
 pub use navigation::*;
@@ -45,7 +45,7 @@ pub fn navigate_end(app: &mut App) {
     update_scroll_offset(panel);
 }
 
-fn update_scroll_offset(panel: &mut Panel) {
+pub fn update_scroll_offset(panel: &mut Panel) {
     if panel.selected_index < panel.scroll_offset {
         panel.scroll_offset = panel.selected_index;
     } else if panel.selected_index >= panel.scroll_offset + panel.visible_height {
@@ -1,5 +1,8 @@
 use crate::app::{App, Screen};
-use crate::models::preview::PreviewSource;
+use crate::models::preview::{PreviewMode, PreviewSource};
+use crate::operations::preview::decompress::CompressedStream;
+use crate::operations::preview::file_loader;
+use crate::ui::preview::highlight::Syntax;
 use anyhow::Result;
 
 /// Zeigt Text-Datei-Vorschau an
@@ -24,6 +27,19 @@ pub async fn show_file_content_preview(app: &mut App, source: PreviewSource) ->
     }
 }
 
+pub fn is_compressed_preview(app: &App) -> bool {
+    app.file_content_preview
+        .as_ref()
+        .is_some_and(|p| p.compressed.is_some())
+}
+
+/// Parquet/Avro/JSON Lines: die Vorschau zeigt gelesene Datensätze statt der Datei-Bytes
+pub fn is_record_preview(app: &App) -> bool {
+    app.file_content_preview
+        .as_ref()
+        .is_some_and(|p| p.records.is_some())
+}
+
 /// Scrollt in Text-Vorschau nach oben
 pub fn scroll_file_preview_up(app: &mut App) {
     if let Some(ref mut preview) = app.file_content_preview {
@@ -56,6 +72,75 @@ pub fn scroll_file_preview_page_down(app: &mut App, page_size: usize) {
     }
 }
 
+/// Schaltet die eingerückte Darstellung von JSON um (`p`)
+pub fn toggle_pretty_json(app: &mut App) {
+    if super::is_hex_preview(app) {
+        app.show_info("Pretty-print works in the text view - press F4 to switch");
+        return;
+    }
+    if is_record_preview(app) {
+        app.show_info("Pretty-print is not available for record previews");
+        return;
+    }
+    if super::is_table_preview(app) {
+        app.show_info("Pretty-print works in the text view - press t to switch");
+        return;
+    }
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return;
+    };
+    let looks_like_json = Syntax::from_filename(preview.content_name()) == Syntax::Json
+        || preview.content.trim_start().starts_with(['{', '[']);
+    if preview.pretty.is_none() && !looks_like_json {
+        app.show_info("Pretty-print is only available for JSON");
+        return;
+    }
+    preview.toggle_pretty();
+}
+
+/// Liest einen Byte-Bereich der Vorschau-Datei (lokal oder über das S3Manager des Panels)
+pub(crate) async fn read_preview_range(
+    app: &mut App,
+    source: &PreviewSource,
+    start: i64,
+    len: i64,
+) -> Result<Vec<u8>> {
+    if len <= 0 {
+        return Ok(Vec::new());
+    }
+    match source {
+        PreviewSource::LocalFile { path } => {
+            file_loader::load_local_file_bytes(path, start, len).await
+        }
+        PreviewSource::S3Object { key, .. } => match &app.get_active_panel().s3_manager {
+            Some(s3_manager) => {
+                s3_manager
+                    .get_object_range(key, start, start + len - 1)
+                    .await
+            }
+            None => Err(anyhow::anyhow!("No S3 connection")),
+        },
+    }
+}
+
+/// Lädt Text ab `start` (bis zu einem Chunk) und dekodiert ihn. Gibt den Text und den
+/// Datei-Bereich zurück, den er abdeckt.
+pub(crate) async fn load_text_range(
+    app: &mut App,
+    source: &PreviewSource,
+    start: i64,
+    end: i64,
+    file_size: i64,
+) -> Result<(String, i64, i64)> {
+    let bytes = read_preview_range(app, source, start, end - start).await?;
+    let chunk = file_loader::decode_text_chunk(&bytes, start > 0, end < file_size)?;
+    Ok((
+        chunk.text,
+        start + chunk.start as i64,
+        start + chunk.end as i64,
+    ))
+}
+
 /// Springt zum Anfang der Datei (lädt head falls im Backward-Modus)
 pub async fn scroll_file_preview_home(app: &mut App) -> Result<()> {
     let preview_info = match &app.file_content_preview {
@@ -63,69 +148,34 @@ pub async fn scroll_file_preview_home(app: &mut App) -> Result<()> {
             p.source.clone(),
             p.preview_mode.clone(),
             p.content_start_offset,
+            p.file_size,
         ),
         None => return Ok(()),
     };
 
-    let (source, preview_mode, content_start_offset) = preview_info;
+    let (source, preview_mode, content_start_offset, file_size) = preview_info;
 
     // If in backward mode and not at beginning, load the head
-    if preview_mode == crate::models::preview::PreviewMode::Backward && content_start_offset > 0 {
-        let chunk_size = 100 * 1024;
-
-        match source {
-            crate::models::preview::PreviewSource::LocalFile { path } => {
-                // Load first 100KB
-                match crate::operations::preview::file_loader::load_local_file_range(
-                    &path, 0, chunk_size,
-                )
-                .await
-                {
-                    Ok(head_content) => {
-                        if let Some(ref mut preview) = app.file_content_preview {
-                            preview.content = head_content;
-                            preview.content_start_offset = 0;
-                            preview.byte_offset = chunk_size;
-                            preview.preview_mode = crate::models::preview::PreviewMode::Forward;
-                            preview.scroll_offset = 0;
-                            preview.chunk_load_count += 1;
-                        }
-                    }
-                    Err(e) => {
-                        app.show_error(&format!("Failed to load file head: {e}"));
-                        return Err(e);
-                    }
+    if preview_mode == PreviewMode::Backward && content_start_offset > 0 {
+        let end = file_loader::CHUNK_SIZE.min(file_size);
+        match load_text_range(app, &source, 0, end, file_size).await {
+            Ok((head_content, _, head_end)) => {
+                if let Some(ref mut preview) = app.file_content_preview {
+                    preview.replace_content(head_content, 0, head_end);
+                    preview.preview_mode = PreviewMode::Forward;
+                    preview.scroll_offset = 0;
+                    preview.chunk_load_count += 1;
                 }
             }
-            crate::models::preview::PreviewSource::S3Object { key, .. } => {
-                // Load first 100KB from S3
-                if let Some(s3_manager) = &app.get_active_panel().s3_manager {
-                    match s3_manager.get_object_range(&key, 0, chunk_size - 1).await {
-                        Ok(bytes) => {
-                            if let Ok(head_content) = String::from_utf8(bytes) {
-                                if let Some(ref mut preview) = app.file_content_preview {
-                                    preview.content = head_content;
-                                    preview.content_start_offset = 0;
-                                    preview.byte_offset = chunk_size;
-                                    preview.preview_mode =
-                                        crate::models::preview::PreviewMode::Forward;
-                                    preview.scroll_offset = 0;
-                                    preview.chunk_load_count += 1;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            app.show_error(&format!("Failed to load S3 file head: {e}"));
-                        }
-                    }
-                }
+            Err(e) => {
+                app.show_error(&format!("Failed to load file head: {e}"));
             }
         }
     } else {
         // Just scroll to top if already in forward mode or at beginning
         if let Some(ref mut preview) = app.file_content_preview {
             preview.scroll_offset = 0;
-            preview.preview_mode = crate::models::preview::PreviewMode::Forward;
+            preview.preview_mode = PreviewMode::Forward;
         }
     }
 
@@ -141,60 +191,27 @@ pub async fn scroll_file_preview_end(app: &mut App) -> Result<()> {
 
     let (source, byte_offset, file_size) = preview_info;
 
+    if is_compressed_preview(app) && byte_offset < file_size {
+        app.show_info(
+            "Compressed files can only be read from the start - scroll down to load more",
+        );
+        return Ok(());
+    }
+
     // Check if we need to load the tail (if we haven't loaded the entire file)
-    let needs_tail = byte_offset < file_size;
-
-    if needs_tail {
-        let chunk_size = 100 * 1024;
-        // Load the tail of the file
-        match source {
-            crate::models::preview::PreviewSource::LocalFile { path } => {
-                match crate::operations::preview::file_loader::load_local_file_tail(
-                    &path, file_size,
-                )
-                .await
-                {
-                    Ok(tail_content) => {
-                        if let Some(ref mut preview) = app.file_content_preview {
-                            let start_offset = file_size.saturating_sub(chunk_size).max(0);
-                            preview.content = tail_content;
-                            preview.byte_offset = file_size; // Mark as fully loaded
-                            preview.content_start_offset = start_offset;
-                            preview.preview_mode = crate::models::preview::PreviewMode::Backward;
-                            preview.chunk_load_count += 1;
-                        }
-                    }
-                    Err(e) => {
-                        app.show_error(&format!("Failed to load file tail: {e}"));
-                        return Err(e);
-                    }
+    if byte_offset < file_size {
+        let start = file_size.saturating_sub(file_loader::CHUNK_SIZE).max(0);
+        match load_text_range(app, &source, start, file_size, file_size).await {
+            Ok((tail_content, tail_start, _)) => {
+                if let Some(ref mut preview) = app.file_content_preview {
+                    preview.replace_content(tail_content, tail_start, file_size);
+                    preview.preview_mode = PreviewMode::Backward;
+                    preview.chunk_load_count += 1;
                 }
             }
-            crate::models::preview::PreviewSource::S3Object { key, .. } => {
-                // Load S3 tail
-                let start_offset = file_size.saturating_sub(chunk_size).max(0);
-                if let Some(s3_manager) = &app.get_active_panel().s3_manager {
-                    match s3_manager
-                        .get_object_range(&key, start_offset, file_size - 1)
-                        .await
-                    {
-                        Ok(bytes) => {
-                            if let Ok(tail_content) = String::from_utf8(bytes) {
-                                if let Some(ref mut preview) = app.file_content_preview {
-                                    preview.content = tail_content;
-                                    preview.byte_offset = file_size; // Mark as fully loaded
-                                    preview.content_start_offset = start_offset;
-                                    preview.preview_mode =
-                                        crate::models::preview::PreviewMode::Backward;
-                                    preview.chunk_load_count += 1;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            app.show_error(&format!("Failed to load S3 file tail: {e}"));
-                        }
-                    }
-                }
+            Err(e) => {
+                app.show_error(&format!("Failed to load file tail: {e}"));
+                return Ok(());
             }
         }
     }
@@ -216,73 +233,42 @@ pub async fn load_previous_file_content(app: &mut App) -> Result<()> {
             p.source.clone(),
             p.content_start_offset,
             p.preview_mode.clone(),
+            p.file_size,
         ),
         None => return Ok(()),
     };
 
-    let (source, content_start_offset, preview_mode) = preview_info;
+    let (source, content_start_offset, preview_mode, file_size) = preview_info;
 
     // Only load previous if in backward mode and not at beginning
-    if preview_mode != crate::models::preview::PreviewMode::Backward || content_start_offset == 0 {
+    if preview_mode != PreviewMode::Backward || content_start_offset == 0 {
         return Ok(());
     }
 
-    let chunk_size = 100 * 1024;
-    let new_start_offset = content_start_offset.saturating_sub(chunk_size).max(0);
-    let bytes_to_load = content_start_offset - new_start_offset;
-
-    if bytes_to_load == 0 {
-        return Ok(());
-    }
+    let new_start_offset = content_start_offset
+        .saturating_sub(file_loader::CHUNK_SIZE)
+        .max(0);
 
-    match source {
-        crate::models::preview::PreviewSource::LocalFile { path } => {
-            match crate::operations::preview::file_loader::load_local_file_range(
-                &path,
-                new_start_offset,
-                bytes_to_load,
-            )
-            .await
-            {
-                Ok(previous_content) => {
-                    if let Some(ref mut preview) = app.file_content_preview {
-                        let previous_lines = previous_content.lines().count();
-                        // Prepend content and adjust scroll offset
-                        preview.content = format!("{}{}", previous_content, preview.content);
-                        preview.content_start_offset = new_start_offset;
-                        preview.scroll_offset += previous_lines;
-                        preview.chunk_load_count += 1;
-                    }
-                }
-                Err(e) => {
-                    app.show_error(&format!("Failed to load previous content: {e}"));
-                }
+    match load_text_range(
+        app,
+        &source,
+        new_start_offset,
+        content_start_offset,
+        file_size,
+    )
+    .await
+    {
+        Ok((previous_content, previous_start, _)) => {
+            if let Some(ref mut preview) = app.file_content_preview {
+                // Prepend content and keep the same line at the top
+                let top = preview.byte_at_visual_line(preview.scroll_offset);
+                let shift = preview.prepend_content(&previous_content, previous_start);
+                preview.scroll_offset = preview.visual_line_at_byte(top + shift);
+                preview.chunk_load_count += 1;
             }
         }
-        crate::models::preview::PreviewSource::S3Object { key, .. } => {
-            if let Some(s3_manager) = &app.get_active_panel().s3_manager {
-                match s3_manager
-                    .get_object_range(&key, new_start_offset, content_start_offset - 1)
-                    .await
-                {
-                    Ok(bytes) => {
-                        if let Ok(previous_content) = String::from_utf8(bytes) {
-                            if let Some(ref mut preview) = app.file_content_preview {
-                                let previous_lines = previous_content.lines().count();
-                                // Prepend content and adjust scroll offset
-                                preview.content =
-                                    format!("{}{}", previous_content, preview.content);
-                                preview.content_start_offset = new_start_offset;
-                                preview.scroll_offset += previous_lines;
-                                preview.chunk_load_count += 1;
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        app.show_error(&format!("Failed to load previous S3 content: {e}"));
-                    }
-                }
-            }
+        Err(e) => {
+            app.show_error(&format!("Failed to load previous content: {e}"));
         }
     }
 
@@ -303,56 +289,73 @@ pub async fn load_more_file_content(app: &mut App) -> Result<()> {
     if byte_offset >= file_size {
         return Ok(());
     }
+    if is_compressed_preview(app) {
+        load_more_compressed(app).await;
+        return Ok(());
+    }
 
-    match source {
-        crate::models::preview::PreviewSource::LocalFile { path } => {
-            // Load more for local file
-            match crate::operations::preview::file_loader::load_more_local_file_content(
-                &path,
-                byte_offset,
-                file_size,
-            )
-            .await
-            {
-                Ok(additional_content) => {
-                    if let Some(ref mut preview) = app.file_content_preview {
-                        let chunk_size = additional_content.len() as i64;
-                        preview.content.push_str(&additional_content);
-                        preview.byte_offset += chunk_size;
-                        preview.chunk_load_count += 1;
-                    }
-                }
-                Err(e) => {
-                    app.show_error(&format!("Failed to load more content: {e}"));
-                }
+    let end = (byte_offset + file_loader::CHUNK_SIZE).min(file_size);
+    match load_text_range(app, &source, byte_offset, end, file_size).await {
+        Ok((additional_content, _, loaded_end)) => {
+            if let Some(ref mut preview) = app.file_content_preview {
+                preview.append_content(&additional_content, loaded_end);
+                preview.chunk_load_count += 1;
             }
         }
-        crate::models::preview::PreviewSource::S3Object { key, .. } => {
-            // Load more for S3 file
-            let chunk_size = 100 * 1024;
-            let end_byte = (byte_offset + chunk_size - 1).min(file_size - 1);
-
-            if let Some(s3_manager) = &app.get_active_panel().s3_manager {
-                match s3_manager
-                    .get_object_range(&key, byte_offset, end_byte)
-                    .await
-                {
-                    Ok(bytes) => {
-                        if let Ok(additional_content) = String::from_utf8(bytes) {
-                            if let Some(ref mut preview) = app.file_content_preview {
-                                preview.content.push_str(&additional_content);
-                                preview.byte_offset = end_byte + 1;
-                                preview.chunk_load_count += 1;
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        app.show_error(&format!("Failed to load more content: {e}"));
-                    }
-                }
-            }
+        Err(e) => {
+            app.show_error(&format!("Failed to load more content: {e}"));
         }
     }
 
     Ok(())
 }
+
+/// Dekomprimiert den nächsten Chunk einer komprimierten Datei und hängt ihn an
+async fn load_more_compressed(app: &mut App) {
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return;
+    };
+    let Some(mut stream) = preview.compressed.take() else {
+        return;
+    };
+    let source = preview.source.clone();
+    let result = decompress_next_chunk(app, &source, &mut stream).await;
+    let finished = stream.finished;
+
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return;
+    };
+    preview.compressed = Some(stream);
+    match result {
+        Ok(text) => {
+            let end = preview.byte_offset + text.len() as i64;
+            preview.file_size = if finished { end } else { end + 1 };
+            preview.append_content(&text, end);
+            preview.chunk_load_count += 1;
+        }
+        Err(e) => app.show_error(&format!("Failed to decompress more content: {e}")),
+    }
+}
+
+/// Liest komprimierte Chunks, bis ein Chunk Text dekomprimiert ist (oder die Datei endet)
+async fn decompress_next_chunk(
+    app: &mut App,
+    source: &PreviewSource,
+    stream: &mut CompressedStream,
+) -> Result<String> {
+    let chunk_size = file_loader::CHUNK_SIZE as usize;
+    let mut decoded = Vec::new();
+    while decoded.len() < chunk_size && !stream.finished {
+        if stream.needs_input() {
+            let len =
+                file_loader::CHUNK_SIZE.min(stream.compressed_size - stream.compressed_offset);
+            let bytes = read_preview_range(app, source, stream.compressed_offset, len).await?;
+            if bytes.is_empty() {
+                anyhow::bail!("File is shorter than expected");
+            }
+            stream.push_input(&bytes);
+        }
+        decoded.extend(stream.decode(chunk_size - decoded.len())?);
+    }
+    stream.decode_text(decoded)
+}
@@ -0,0 +1,168 @@
+use super::file::scroll_file_preview_end;
+use super::search::chunk_reader;
+use crate::app::{App, PreviewFollowTask, Screen};
+use crate::models::preview::{FollowState, PreviewMode, ViewMode};
+use crate::operations::preview::follow::{self, FollowLoad};
+use std::time::{Duration, Instant};
+
+/// Schaltet den Follow-Modus um (`f`): springt ans Ende und hängt neue Daten automatisch an
+pub async fn toggle_follow_mode(app: &mut App) {
+    let Some(preview) = &app.file_content_preview else {
+        return;
+    };
+    if preview.follow.is_some() {
+        if let Some(preview) = app.file_content_preview.as_mut() {
+            preview.follow = None;
+        }
+        if let Some(task) = app.preview_follow_task.take() {
+            task.task_handle.abort();
+        }
+        return;
+    }
+    if preview.compressed.is_some() {
+        app.show_info("Follow mode is not available for compressed files");
+        return;
+    }
+    if preview.records.is_some() {
+        app.show_info("Follow mode is not available for record previews");
+        return;
+    }
+    if preview.view_mode == ViewMode::Hex {
+        app.show_info("Follow mode works in the text view - press F4 to switch");
+        return;
+    }
+
+    if let Err(e) = scroll_file_preview_end(app).await {
+        app.show_error(&format!("Failed to load file tail: {e}"));
+        return;
+    }
+    if let Some(preview) = app.file_content_preview.as_mut() {
+        preview.reset_match();
+        preview.scroll_to_last_page();
+        preview.follow = Some(FollowState {
+            paused: false,
+            last_poll: Instant::now(),
+        });
+    }
+}
+
+/// Hält den Follow-Modus an (beim Zurückscrollen oder Suchen)
+pub fn pause_follow(app: &mut App) {
+    if let Some(follow) = app
+        .file_content_preview
+        .as_mut()
+        .and_then(|p| p.follow.as_mut())
+    {
+        follow.paused = true;
+    }
+}
+
+/// Setzt einen angehaltenen Follow-Modus fort (End)
+pub fn resume_follow(app: &mut App) {
+    if let Some(preview) = app.file_content_preview.as_mut() {
+        if let Some(follow) = preview.follow.as_mut() {
+            follow.paused = false;
+            preview.scroll_to_last_page();
+        }
+    }
+}
+
+/// Startet im Follow-Modus nach Ablauf des Intervalls die Abfrage im Hintergrund und
+/// übernimmt ihr Ergebnis. Gibt true zurück, wenn neu gezeichnet werden muss.
+pub async fn check_preview_follow(app: &mut App) -> bool {
+    if app.screen != Screen::FileContentPreview {
+        return false;
+    }
+    match &app.preview_follow_task {
+        Some(task) if task.task_handle.is_finished() => {}
+        Some(_) => return false,
+        None => {
+            start_follow_poll(app);
+            return false;
+        }
+    }
+    let Some(task) = app.preview_follow_task.take() else {
+        return false;
+    };
+
+    let load = match task.task_handle.await {
+        Ok(Ok(load)) => load,
+        Ok(Err(e)) => {
+            pause_follow(app);
+            app.show_error(&format!("Follow mode paused: {e}"));
+            return true;
+        }
+        Err(_) => return false, // Abgebrochen
+    };
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return false;
+    };
+    // Beendet, angehalten oder inzwischen anderer Inhalt geladen: beim nächsten Mal neu
+    let following = preview.follow.as_ref().is_some_and(|f| !f.paused);
+    if !following || preview.byte_offset != task.byte_offset || preview.file_size != task.file_size
+    {
+        return false;
+    }
+
+    match load {
+        FollowLoad::Unchanged => return false,
+        FollowLoad::Append { text, end, size } => {
+            preview.file_size = size;
+            preview.append_content(&text, end);
+        }
+        FollowLoad::Tail {
+            text,
+            start,
+            end,
+            size,
+            shrunk,
+        } => {
+            preview.file_size = size;
+            preview.replace_content(text, start, end);
+            preview.preview_mode = PreviewMode::Backward;
+            if shrunk {
+                app.show_info("File got smaller - reloaded its end");
+            }
+        }
+    }
+    if let Some(preview) = app.file_content_preview.as_mut() {
+        preview.chunk_load_count += 1;
+        preview.scroll_to_last_page();
+    }
+    true
+}
+
+/// Startet die nächste Abfrage, wenn das Intervall abgelaufen ist
+fn start_follow_poll(app: &mut App) {
+    let interval = Duration::from_secs(
+        app.config_manager
+            .app_config
+            .settings
+            .follow_interval_secs
+            .max(1),
+    );
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return;
+    };
+    let Some(follow) = preview.follow.as_mut() else {
+        return;
+    };
+    if follow.paused || preview.view_mode == ViewMode::Hex || follow.last_poll.elapsed() < interval
+    {
+        return;
+    }
+    follow.last_poll = Instant::now();
+    let source = preview.source.clone();
+    let (byte_offset, file_size) = (preview.byte_offset, preview.file_size);
+
+    let Some(reader) = chunk_reader(app, &source) else {
+        pause_follow(app);
+        app.show_error("Follow mode paused: no S3 connection");
+        return;
+    };
+    app.preview_follow_task = Some(PreviewFollowTask {
+        task_handle: tokio::spawn(follow::load_follow(reader, byte_offset, file_size)),
+        byte_offset,
+        file_size,
+    });
+}
@@ -0,0 +1,245 @@
+use super::file::{load_text_range, read_preview_range};
+use crate::app::App;
+use crate::message::Message;
+use crate::models::preview::{HexData, PreviewMode, ViewMode, HEX_BYTES_PER_ROW};
+use crate::operations::preview::file_loader::{NotText, CHUNK_SIZE};
+
+const ROW: i64 = HEX_BYTES_PER_ROW as i64;
+
+pub fn is_hex_preview(app: &App) -> bool {
+    app.file_content_preview
+        .as_ref()
+        .is_some_and(|p| p.view_mode == ViewMode::Hex)
+}
+
+/// Wechselt zwischen Text- und Hex-Ansicht (an derselben Stelle der Datei)
+pub async fn toggle_hex_view(app: &mut App) {
+    if super::is_compressed_preview(app) {
+        app.show_info("Hex view is not available for compressed files");
+        return;
+    }
+    if super::is_record_preview(app) {
+        app.show_info("Hex view is not available for record previews");
+        return;
+    }
+    let Some(preview) = &app.file_content_preview else {
+        return;
+    };
+    match preview.view_mode {
+        ViewMode::Text | ViewMode::Table => {
+            let offset = preview.text_top_offset();
+            show_hex_at(app, offset).await;
+        }
+        ViewMode::Hex => {
+            let offset = preview.hex.top_offset();
+            show_text_at(app, offset).await;
+        }
+    }
+}
+
+/// Springt zu einem Offset (dezimal oder 0x-hex) in der aktuellen Ansicht
+pub async fn goto_preview_offset(app: &mut App, input: &str) {
+    let Some(preview) = &app.file_content_preview else {
+        return;
+    };
+    let input = input.trim();
+    let parsed = match input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => input.parse::<i64>().ok(),
+    };
+    let offset = match parsed {
+        Some(offset) if offset >= 0 && offset < preview.file_size.max(1) => offset,
+        _ => {
+            app.show_error(&format!(
+                "Invalid offset: '{input}' (0 - {})",
+                preview.file_size.saturating_sub(1)
+            ));
+            return;
+        }
+    };
+
+    match preview.view_mode {
+        ViewMode::Hex => show_hex_at(app, offset).await,
+        ViewMode::Text | ViewMode::Table => show_text_at(app, offset).await,
+    }
+}
+
+/// Hex-Ansicht mit der Zeile von `offset` oben
+async fn show_hex_at(app: &mut App, offset: i64) {
+    let window_start = offset / ROW * ROW;
+    if load_hex_window(app, offset, window_start).await {
+        if let Some(preview) = &mut app.file_content_preview {
+            preview.hex.scroll_to(offset);
+            preview.view_mode = ViewMode::Hex;
+        }
+    }
+}
+
+/// Lädt einen Chunk ab `window_start`, falls `offset` noch nicht geladen ist
+async fn load_hex_window(app: &mut App, offset: i64, window_start: i64) -> bool {
+    let Some(preview) = &app.file_content_preview else {
+        return false;
+    };
+    if preview.hex.contains(offset) || preview.file_size == 0 {
+        return true;
+    }
+    let source = preview.source.clone();
+    let end = (window_start + CHUNK_SIZE).min(preview.file_size);
+
+    match read_preview_range(app, &source, window_start, end - window_start).await {
+        Ok(bytes) => {
+            if let Some(preview) = &mut app.file_content_preview {
+                preview.hex = HexData {
+                    bytes,
+                    start_offset: window_start,
+                    scroll_row: 0,
+                };
+                preview.chunk_load_count += 1;
+            }
+            true
+        }
+        Err(e) => {
+            app.show_error(&format!("Failed to load file range: {e}"));
+            false
+        }
+    }
+}
+
+/// Text- bzw. Tabellen-Ansicht ab `offset` (nutzt den geladenen Text, wenn er die Stelle enthält)
+async fn show_text_at(app: &mut App, offset: i64) {
+    let Some(preview) = &mut app.file_content_preview else {
+        return;
+    };
+    let relative = offset - preview.content_start_offset;
+    if relative >= 0
+        && offset < preview.byte_offset
+        && preview.content.is_char_boundary(relative as usize)
+    {
+        preview.show_text_at_byte(preview.text_pos(relative as usize));
+        return;
+    }
+
+    let source = preview.source.clone();
+    let file_size = preview.file_size;
+    let end = (offset + CHUNK_SIZE).min(file_size);
+    match load_text_range(app, &source, offset, end, file_size).await {
+        Ok((text, start, loaded_end)) => {
+            if let Some(preview) = &mut app.file_content_preview {
+                preview.replace_content(text, start, loaded_end);
+                // Backward-Modus lädt beim Hochscrollen den Text davor nach
+                preview.preview_mode = if start > 0 {
+                    PreviewMode::Backward
+                } else {
+                    PreviewMode::Forward
+                };
+                preview.show_text_at_byte(0);
+                preview.chunk_load_count += 1;
+            }
+        }
+        Err(e) if e.is::<NotText>() => {
+            app.show_error("Cannot show as text: this part of the file is not valid UTF-8");
+        }
+        Err(e) => app.show_error(&format!("Failed to load file range: {e}")),
+    }
+}
+
+/// Scrollt die Hex-Ansicht um `delta` Zeilen; nahe den Rändern wird nachgeladen
+pub fn scroll_hex_view(app: &mut App, delta: isize) -> Option<Message> {
+    let preview = app.file_content_preview.as_mut()?;
+    let hex = &mut preview.hex;
+    let last_row = hex.row_count().saturating_sub(1);
+    hex.scroll_row = hex.scroll_row.saturating_add_signed(delta).min(last_row);
+
+    if last_row - hex.scroll_row < 50 && hex.end_offset() < preview.file_size {
+        Some(Message::LoadMoreFileContent)
+    } else if hex.scroll_row < 50 && hex.start_offset > 0 {
+        Some(Message::LoadPreviousFileContent)
+    } else {
+        None
+    }
+}
+
+pub async fn hex_view_home(app: &mut App) {
+    if super::is_compressed_preview(app) {
+        // Nur der erste dekomprimierte Chunk ist geladen
+        if let Some(preview) = &mut app.file_content_preview {
+            preview.hex.scroll_to(0);
+        }
+        return;
+    }
+    show_hex_at(app, 0).await;
+}
+
+/// Letzte Zeile oben, mit einem Chunk davor zum Hochscrollen
+pub async fn hex_view_end(app: &mut App) {
+    if super::is_compressed_preview(app) {
+        if let Some(preview) = &mut app.file_content_preview {
+            let last = (preview.hex.end_offset() - 1).max(0) / ROW * ROW;
+            preview.hex.scroll_to(last);
+        }
+        return;
+    }
+    let Some(preview) = &app.file_content_preview else {
+        return;
+    };
+    let last = (preview.file_size - 1).max(0) / ROW * ROW;
+    let window_start = (preview.file_size - CHUNK_SIZE).max(0) / ROW * ROW;
+    if load_hex_window(app, last, window_start).await {
+        if let Some(preview) = &mut app.file_content_preview {
+            preview.hex.scroll_to(last);
+        }
+    }
+}
+
+/// Hängt den nächsten Chunk an die Hex-Daten an
+pub async fn load_more_hex(app: &mut App) {
+    let Some(preview) = &app.file_content_preview else {
+        return;
+    };
+    let start = preview.hex.end_offset();
+    let end = (start + CHUNK_SIZE).min(preview.file_size);
+    if start >= end {
+        return;
+    }
+    let source = preview.source.clone();
+
+    match read_preview_range(app, &source, start, end - start).await {
+        Ok(bytes) => {
+            if let Some(preview) = &mut app.file_content_preview {
+                preview.hex.bytes.extend(bytes);
+                preview.chunk_load_count += 1;
+            }
+        }
+        Err(e) => app.show_error(&format!("Failed to load more content: {e}")),
+    }
+}
+
+/// Stellt den Chunk vor den Hex-Daten voran
+pub async fn load_previous_hex(app: &mut App) {
+    let Some(preview) = &app.file_content_preview else {
+        return;
+    };
+    let end = preview.hex.start_offset;
+    let start = (end - CHUNK_SIZE).max(0);
+    if start >= end {
+        return;
+    }
+    let source = preview.source.clone();
+
+    match read_preview_range(app, &source, start, end - start).await {
+        Ok(mut bytes) => {
+            if let Some(preview) = &mut app.file_content_preview {
+                let hex = &mut preview.hex;
+                hex.scroll_row += bytes.len() / HEX_BYTES_PER_ROW;
+                bytes.append(&mut hex.bytes);
+                hex.bytes = bytes;
+                hex.start_offset = start;
+                preview.chunk_load_count += 1;
+            }
+        }
+        Err(e) => app.show_error(&format!("Failed to load previous content: {e}")),
+    }
+}
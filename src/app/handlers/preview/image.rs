@@ -1,12 +1,52 @@
 use crate::app::{App, Screen};
-use crate::models::preview::PreviewSource;
+use crate::models::list::{ItemType, PanelItem};
+use crate::models::preview::{PendingImage, PreviewSource};
+use crate::operations::preview::image_loader;
 use anyhow::Result;
 
-/// Zeigt Bild-Vorschau an (sofort mit Loading-State, dann async laden)
+/// Zeigt Bild-Vorschau an (sofort mit Loading-State, dann async laden).
+/// Bilder über dem Größenlimit werden erst nach Bestätigung geladen.
 pub async fn show_image_preview(app: &mut App, source: PreviewSource) -> Result<()> {
+    let limit = app.config_manager.app_config.settings.image_max_size_mb * 1024 * 1024;
+    if limit > 0 {
+        let s3_manager = app.get_active_panel().s3_manager.clone();
+        match image_loader::image_file_size(&source, s3_manager.as_ref()).await {
+            Ok(size) if size > limit => {
+                app.prev_screen = Some(app.screen.clone());
+                app.screen = Screen::ImagePreview;
+                app.image_preview = None;
+                app.image_preview_loading = false;
+                app.image_preview_pending = Some(PendingImage {
+                    filename: source_filename(&source),
+                    source,
+                    size,
+                    limit,
+                });
+                return Ok(());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                app.show_error(&format!("Cannot load image: {e}"));
+                return Ok(());
+            }
+        }
+    }
+
     // Sofort in Preview-Screen wechseln mit Loading-State
     app.prev_screen = Some(app.screen.clone());
     app.screen = Screen::ImagePreview;
+    start_image_loading(app, source);
+    Ok(())
+}
+
+/// Lädt ein Bild über dem Größenlimit nach Bestätigung (Enter)
+pub fn confirm_image_loading(app: &mut App) {
+    if let Some(pending) = app.image_preview_pending.take() {
+        start_image_loading(app, pending.source);
+    }
+}
+
+fn start_image_loading(app: &mut App, source: PreviewSource) {
     app.image_preview_loading = true;
     app.image_preview = None;
 
@@ -27,12 +67,7 @@ pub async fn show_image_preview(app: &mut App, source: PreviewSource) -> Result<
             let s3_manager = app.get_active_panel().s3_manager.clone();
             tokio::spawn(async move {
                 let result = if let Some(s3_manager) = s3_manager {
-                    crate::operations::preview::image_loader::load_s3_image(
-                        &key,
-                        &bucket,
-                        &s3_manager,
-                    )
-                    .await
+                    image_loader::load_s3_image(&key, &bucket, &s3_manager).await
                 } else {
                     Err(anyhow::anyhow!("No S3 connection available"))
                 };
@@ -40,8 +75,72 @@ pub async fn show_image_preview(app: &mut App, source: PreviewSource) -> Result<
             });
         }
     }
+}
 
-    Ok(())
+fn source_filename(source: &PreviewSource) -> String {
+    let path = match source {
+        PreviewSource::LocalFile { path } => path,
+        PreviewSource::S3Object { key, .. } => key,
+    };
+    path.rsplit(['/', std::path::MAIN_SEPARATOR])
+        .next()
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Zoomt das Bild (+1 hinein, -1 heraus, 0 zurücksetzen)
+pub fn zoom_image(app: &mut App, direction: i32) {
+    if let Some(preview) = app.image_preview.as_mut() {
+        match direction {
+            d if d > 0 => preview.zoom_in(),
+            d if d < 0 => preview.zoom_out(),
+            _ => preview.reset_zoom(),
+        }
+    }
+}
+
+/// Verschiebt den sichtbaren Ausschnitt eines gezoomten Bildes
+pub fn pan_image(app: &mut App, dx: i32, dy: i32) {
+    if let Some(preview) = app.image_preview.as_mut() {
+        preview.pan(dx, dy);
+    }
+}
+
+/// Blendet die Format- und EXIF-Angaben ein/aus
+pub fn toggle_image_info(app: &mut App) {
+    if let Some(preview) = app.image_preview.as_mut() {
+        preview.show_info = !preview.show_info;
+    }
+}
+
+/// Zeigt das nächste bzw. vorherige Bild des aktiven Panels
+pub async fn show_adjacent_image(app: &mut App, forward: bool) -> Result<()> {
+    let panel = app.get_active_panel();
+    let current = panel.selected_index;
+    let is_image = |item: &PanelItem| {
+        item.item_type == ItemType::File && image_loader::is_image_file(&item.name)
+    };
+    let next = if forward {
+        (current + 1..panel.list_model.len())
+            .find(|&i| panel.list_model.get_item(i).is_some_and(is_image))
+    } else {
+        (0..current)
+            .rev()
+            .find(|&i| panel.list_model.get_item(i).is_some_and(is_image))
+    };
+    let Some(index) = next else {
+        app.show_info(if forward {
+            "No further image in this folder"
+        } else {
+            "No previous image in this folder"
+        });
+        return Ok(());
+    };
+
+    panel.selected_index = index;
+    crate::app::handlers::update_scroll_offset(panel);
+    app.go_back();
+    crate::operations::view_file(app).await
 }
 
 /// Prüft ob Image-Loading abgeschlossen ist (wird im Event-Loop gecheckt)
@@ -1,5 +1,13 @@
 pub mod file;
+pub mod follow;
+pub mod hex;
 pub mod image;
+pub mod search;
+pub mod table;
 
 pub use file::*;
+pub use follow::*;
+pub use hex::*;
 pub use image::*;
+pub use search::*;
+pub use table::*;
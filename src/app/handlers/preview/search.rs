@@ -0,0 +1,227 @@
+use crate::app::{App, PreviewSearchTask};
+use crate::models::preview::{PreviewMode, PreviewSearch, PreviewSource};
+use crate::operations::preview::search::{self, ChunkReader, SEARCH_CHUNK_SIZE};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Startet eine neue Suche (`/` vorwärts, `?` rückwärts)
+pub fn start_preview_search(app: &mut App, pattern: &str, backward: bool) {
+    if pattern.is_empty() {
+        return;
+    }
+    // Smart case: Groß-/Kleinschreibung nur beachten, wenn das Muster Großbuchstaben enthält
+    let regex = regex::RegexBuilder::new(pattern)
+        .multi_line(true)
+        .case_insensitive(!pattern.chars().any(char::is_uppercase))
+        .build();
+    let regex = match regex {
+        Ok(regex) => regex,
+        Err(e) => {
+            app.show_error(&format!("Invalid search pattern: {e}"));
+            return;
+        }
+    };
+
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return;
+    };
+    preview.search = Some(PreviewSearch {
+        pattern: pattern.to_string(),
+        regex,
+        backward,
+        current: None,
+    });
+    find_in_preview(app, backward);
+}
+
+/// `n` (gleiche Richtung) bzw. `N` (umgekehrte Richtung)
+pub fn preview_search_next(app: &mut App, reverse: bool) {
+    if app.preview_search_task.is_some() {
+        return;
+    }
+    if super::is_hex_preview(app) {
+        app.show_info("Search works in the text view - press F4 to switch");
+        return;
+    }
+    let Some(search) = app
+        .file_content_preview
+        .as_ref()
+        .and_then(|p| p.search.as_ref())
+    else {
+        app.show_info("No search pattern - press / or ? to search");
+        return;
+    };
+    let backward = search.backward != reverse;
+    find_in_preview(app, backward);
+}
+
+/// Bricht das Nachladen einer laufenden Suche ab
+pub fn cancel_preview_search(app: &mut App) {
+    if let Some(task) = app.preview_search_task.take() {
+        task.task_handle.abort();
+        app.show_info("Search cancelled");
+    }
+}
+
+/// Springt zum nächsten Treffer im geladenen Inhalt oder lädt im Hintergrund weiter
+fn find_in_preview(app: &mut App, backward: bool) {
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return;
+    };
+    if let Some(range) = preview.find_match(backward) {
+        preview.select_match(range);
+        return;
+    }
+
+    let Some(search) = preview.search.as_ref() else {
+        return;
+    };
+    let has_more = if backward {
+        preview.content_start_offset > 0
+    } else {
+        preview.byte_offset < preview.file_size
+    };
+    if !has_more {
+        let message = format!("Pattern not found: {}", search.pattern);
+        app.show_info(&message);
+        return;
+    }
+    if preview.compressed.is_some() {
+        // Dekomprimieren geht nur der Reihe nach; die Suche lädt daher nicht selbst nach
+        let message = format!(
+            "'{}' not found in the decompressed part so far - scroll down to load more",
+            search.pattern
+        );
+        app.show_info(&message);
+        return;
+    }
+
+    // Angeschnittene Zeile am Rand des geladenen Inhalts mitdurchsuchen
+    let content = &preview.content;
+    let carry = if backward {
+        content[..content.find('\n').map_or(content.len(), |i| i + 1)].to_string()
+    } else {
+        content[content.rfind('\n').map_or(0, |i| i + 1)..].to_string()
+    };
+    let (from, total) = if backward {
+        (preview.content_start_offset, preview.content_start_offset)
+    } else {
+        (preview.byte_offset, preview.file_size - preview.byte_offset)
+    };
+    let regex = search.regex.clone();
+    let file_size = preview.file_size;
+
+    let source = preview.source.clone();
+    let Some(reader) = chunk_reader(app, &source) else {
+        app.show_error("Cannot search: no S3 connection");
+        return;
+    };
+
+    let progress = Arc::new(AtomicU64::new(0));
+    let task_handle = tokio::spawn(search::load_until_match(
+        reader,
+        regex,
+        from,
+        file_size,
+        backward,
+        carry,
+        progress.clone(),
+    ));
+    app.preview_search_task = Some(PreviewSearchTask {
+        task_handle,
+        progress,
+        total: total as u64,
+        backward,
+    });
+}
+
+/// Leser für Nachladungen im Hintergrund; None ohne S3-Verbindung
+pub(crate) fn chunk_reader(app: &mut App, source: &PreviewSource) -> Option<ChunkReader> {
+    match source {
+        PreviewSource::LocalFile { path } => Some(ChunkReader::Local(path.clone())),
+        PreviewSource::S3Object { key, .. } => {
+            let manager = app.get_active_panel().s3_manager.clone()?;
+            Some(ChunkReader::S3 {
+                manager: Box::new(manager),
+                key: key.clone(),
+            })
+        }
+    }
+}
+
+/// Übernimmt das Ergebnis einer fertigen Such-Nachladung. Gibt true zurück, wenn neu
+/// gezeichnet werden muss.
+pub async fn check_preview_search_complete(app: &mut App) -> bool {
+    match &app.preview_search_task {
+        Some(task) if task.task_handle.is_finished() => {}
+        Some(_) => return true, // Fortschritt aktualisieren
+        None => return false,
+    }
+    let task = app.preview_search_task.take().unwrap();
+
+    let load = match task.task_handle.await {
+        Ok(Ok(load)) => load,
+        Ok(Err(e)) => {
+            app.show_error(&format!("Search failed: {e}"));
+            return true;
+        }
+        Err(_) => return true, // Abgebrochen
+    };
+
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return true;
+    };
+    let loaded = task.progress.load(Ordering::Relaxed);
+    preview.chunk_load_count += loaded.div_ceil(SEARCH_CHUNK_SIZE as u64) as u32;
+
+    if !load.contiguous {
+        // Weit gesprungen: Inhalt durch das Fenster um die Fundstelle ersetzen (wie bei End)
+        preview.replace_content(load.text, load.start, load.end);
+        preview.preview_mode = if load.start > 0 {
+            PreviewMode::Backward
+        } else {
+            PreviewMode::Forward
+        };
+        preview.scroll_offset = if task.backward {
+            preview.calculate_visual_line_count()
+        } else {
+            0
+        };
+    } else if task.backward {
+        // Voranstellen: Scrollposition und aktuellen Treffer mitverschieben
+        let top = preview.byte_at_visual_line(preview.scroll_offset);
+        let shift = preview.prepend_content(&load.text, load.start);
+        preview.scroll_offset = preview.visual_line_at_byte(top + shift);
+    } else {
+        preview.append_content(&load.text, load.end);
+    }
+
+    if let Some(range) = preview.find_match(task.backward) {
+        preview.select_match(range);
+        return true;
+    }
+
+    let Some(search) = preview.search.as_ref() else {
+        return true;
+    };
+    if load.limit_reached {
+        let message = format!(
+            "'{}' not found in the next {} - press {} to continue",
+            search.pattern,
+            crate::ui::helpers::format_size(loaded),
+            if search.backward == task.backward {
+                "n"
+            } else {
+                "N"
+            }
+        );
+        // Nicht hinter das Ende des Fensters gescrollt stehen bleiben
+        let last_line = preview.calculate_visual_line_count().saturating_sub(1);
+        preview.scroll_offset = preview.scroll_offset.min(last_line);
+        app.show_info(&message);
+    } else {
+        // Treffer lag vor dem aktuellen (z.B. in der mitdurchsuchten Zeile): weiter suchen
+        find_in_preview(app, task.backward);
+    }
+    true
+}
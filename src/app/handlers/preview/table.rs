@@ -0,0 +1,142 @@
+use crate::app::{App, Screen};
+use crate::message::Message;
+use crate::models::preview::ViewMode;
+
+pub fn is_table_preview(app: &App) -> bool {
+    app.file_content_preview
+        .as_ref()
+        .is_some_and(|p| p.view_mode == ViewMode::Table)
+}
+
+/// Wechselt bei CSV/TSV zwischen Tabelle und Text (`t`)
+pub fn toggle_table_view(app: &mut App) {
+    let Some(preview) = app.file_content_preview.as_mut() else {
+        return;
+    };
+    if preview.table.is_none() {
+        app.show_info("Table view is available for .csv and .tsv files");
+    } else if preview.view_mode == ViewMode::Hex {
+        app.show_info("Press F4 to leave the hex view first");
+    } else {
+        preview.toggle_table();
+    }
+}
+
+/// Scrollt die Tabelle um `delta` Zeilen; nahe den Rändern wird nachgeladen
+pub fn scroll_table_view(app: &mut App, delta: isize) -> Option<Message> {
+    let preview = app.file_content_preview.as_mut()?;
+    let table = preview.table.as_mut()?;
+    let last_row = table.rows.len().saturating_sub(1);
+    table.scroll_row = table.scroll_row.saturating_add_signed(delta).min(last_row);
+
+    if last_row - table.scroll_row < 50 && preview.byte_offset < preview.file_size {
+        Some(Message::LoadMoreFileContent)
+    } else if table.scroll_row < 50 && preview.content_start_offset > 0 {
+        Some(Message::LoadPreviousFileContent)
+    } else {
+        None
+    }
+}
+
+/// Oberste bzw. unterste Zeile (nach dem Laden von Anfang/Ende der Datei)
+pub fn scroll_table_to(app: &mut App, end: bool) {
+    if let Some(table) = app
+        .file_content_preview
+        .as_mut()
+        .and_then(|p| p.table.as_mut())
+    {
+        // Beim Zeichnen auf die letzte volle Seite begrenzt
+        table.scroll_row = if end { table.rows.len() } else { 0 };
+    }
+}
+
+/// Horizontal um eine (sichtbare) Spalte scrollen
+pub fn scroll_table_columns(app: &mut App, delta: isize) {
+    let Some(table) = app
+        .file_content_preview
+        .as_mut()
+        .and_then(|p| p.table.as_mut())
+    else {
+        return;
+    };
+    let mut column = table.first_column;
+    loop {
+        let Some(next) = column.checked_add_signed(delta) else {
+            return;
+        };
+        if next >= table.column_count() {
+            return;
+        }
+        column = next;
+        if !table.hidden[column] {
+            table.first_column = column;
+            return;
+        }
+    }
+}
+
+/// Spaltenwähler (`c`): Spalten ein-/ausblenden oder zu einer Spalte springen
+pub fn show_column_picker(app: &mut App) {
+    let Some(table) = app
+        .file_content_preview
+        .as_mut()
+        .and_then(|p| p.table.as_mut())
+    else {
+        return;
+    };
+    if table.column_count() == 0 {
+        return;
+    }
+    table.picker_selected = table.first_column;
+    app.prev_screen = Some(app.screen.clone());
+    app.screen = Screen::ColumnPicker;
+}
+
+pub fn column_picker_move(app: &mut App, delta: isize) {
+    if let Some(table) = app
+        .file_content_preview
+        .as_mut()
+        .and_then(|p| p.table.as_mut())
+    {
+        let last = table.column_count().saturating_sub(1);
+        table.picker_selected = table.picker_selected.saturating_add_signed(delta).min(last);
+    }
+}
+
+pub fn toggle_column_hidden(app: &mut App) {
+    let Some(table) = app
+        .file_content_preview
+        .as_mut()
+        .and_then(|p| p.table.as_mut())
+    else {
+        return;
+    };
+    let selected = table.picker_selected;
+    let visible = table.hidden.iter().filter(|h| !**h).count();
+    if !table.hidden[selected] && visible == 1 {
+        app.show_info("At least one column must stay visible");
+        return;
+    }
+    table.hidden[selected] = !table.hidden[selected];
+
+    // Erste sichtbare Spalte darf nicht ausgeblendet sein
+    if table.hidden[table.first_column] {
+        table.first_column = (0..table.column_count())
+            .find(|c| !table.hidden[*c])
+            .unwrap_or(0);
+    }
+}
+
+/// Springt zur gewählten Spalte (blendet sie bei Bedarf wieder ein)
+pub fn jump_to_column(app: &mut App) {
+    if let Some(table) = app
+        .file_content_preview
+        .as_mut()
+        .and_then(|p| p.table.as_mut())
+    {
+        let selected = table.picker_selected;
+        table.hidden[selected] = false;
+        table.first_column = selected;
+    }
+    app.go_back();
+}
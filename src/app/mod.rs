@@ -1,6 +1,7 @@
 pub mod converters;
 pub mod handlers;
 pub mod navigation;
+pub mod session;
 mod state;
 mod update;
 
@@ -9,11 +10,15 @@ pub use update::update;
 
 use crate::models::config::ConfigManager;
 use crate::models::list::PanelListModel;
+use crate::operations::archive::ArchiveIndex;
+use crate::operations::queue::ThroughputHistory;
 use crate::operations::s3::S3Manager;
+use crate::operations::throttle::BandwidthLimits;
 use crate::operations::FileOperation;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum PanelType {
     ModeSelection,
@@ -30,6 +35,56 @@ pub enum PanelType {
     LocalFilesystem {
         path: PathBuf,
     },
+    Archive {
+        location: ArchiveLocation,
+        prefix: String, // Ordner im Archiv ("" oder "dir/")
+    },
+}
+
+/// Archiv, das als virtuelles Verzeichnis geöffnet ist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ArchiveLocation {
+    Local {
+        path: PathBuf,
+    },
+    S3 {
+        profile: String,
+        bucket: String,
+        key: String,
+    },
+}
+
+impl ArchiveLocation {
+    pub fn name(&self) -> &str {
+        match self {
+            ArchiveLocation::Local { path } => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default(),
+            ArchiveLocation::S3 { key, .. } => key.rsplit('/').next().unwrap_or(key),
+        }
+    }
+
+    /// Verzeichnis, in dem das Archiv liegt
+    pub fn parent(&self) -> PanelType {
+        match self {
+            ArchiveLocation::Local { path } => PanelType::LocalFilesystem {
+                path: path.parent().map(PathBuf::from).unwrap_or_default(),
+            },
+            ArchiveLocation::S3 {
+                profile,
+                bucket,
+                key,
+            } => PanelType::S3Browser {
+                profile: profile.clone(),
+                bucket: bucket.clone(),
+                prefix: key
+                    .rfind('/')
+                    .map(|i| key[..=i].to_string())
+                    .unwrap_or_default(),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -48,12 +103,14 @@ pub struct Panel {
     pub visible_height: usize,
     pub list_model: PanelListModel,
     pub s3_manager: Option<S3Manager>,
+    pub archive: Option<ArchiveIndex>, // Inhaltsverzeichnis bei PanelType::Archive
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Screen {
     DualPanel,
     ConfigForm,
+    ConnectionTest,
     ProfileConfigForm,
     SortDialog,
     DeleteConfirmation,
@@ -61,9 +118,13 @@ pub enum Screen {
     ImagePreview,
     Input,
     Help,
+    QueueDetails,
+    TransferConflict,
+    ColumnPicker,
+    ExternalEditUpload,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ActivePanel {
     Left,
     Right,
@@ -88,10 +149,14 @@ pub struct App {
 
     // Consolidated UI State
     pub config_form: ConfigFormState,
+    pub connection_test: ConnectionTestState,
     pub profile_form: ProfileFormState,
     pub file_content_preview: Option<crate::models::preview::FileContentPreview>,
     pub image_preview: Option<crate::models::preview::ImagePreview>,
     pub image_preview_loading: bool,
+    pub image_preview_pending: Option<crate::models::preview::PendingImage>,
+    pub preview_search_task: Option<PreviewSearchTask>,
+    pub preview_follow_task: Option<PreviewFollowTask>,
     pub image_preview_receiver: Option<
         tokio::sync::oneshot::Receiver<anyhow::Result<crate::models::preview::ImagePreview>>,
     >,
@@ -99,6 +164,8 @@ pub struct App {
     pub input: InputState,
     pub sort_dialog: SortDialogState,
     pub script: ScriptState,
+    pub external_edit: ExternalEditState,
+    pub transfer_conflict: TransferConflictState,
 
     // File Operations Queue
     pub file_operation_queue: Vec<FileOperation>,
@@ -106,9 +173,17 @@ pub struct App {
     pub selected_queue_index: usize,
     pub queue_focused: bool, // Whether queue panel has focus for navigation
     pub background_transfer_task: Option<BackgroundTransferTask>,
+    pub bandwidth: BandwidthLimits, // Global limit changeable at runtime ('b' in queue)
+    pub throughput: ThroughputHistory, // Per-second speed for the queue details
 
     // Background S3 list operation
     pub background_list_task: Option<BackgroundListTask>,
+
+    // Locations from the command line, opened one after another by the run loop
+    pub start_locations: std::collections::VecDeque<(ActivePanel, crate::cli::Location)>,
+
+    // Last selected entry per directory (persisted with the session)
+    pub cursor_memory: session::CursorMemory,
 }
 
 /// Background file transfer task (non-blocking)
@@ -118,17 +193,47 @@ pub struct BackgroundTransferTask {
     pub operation: std::sync::Arc<tokio::sync::Mutex<FileOperation>>,
 }
 
+/// Result of a background list: the objects and, if the bucket turned out to be
+/// in another region, the region the S3Manager switched to
+pub type ListTaskResult = (Vec<crate::operations::s3::S3Object>, Option<String>);
+
+/// Search in the file preview that loads further chunks in the background
+pub struct PreviewSearchTask {
+    pub task_handle:
+        tokio::task::JoinHandle<anyhow::Result<crate::operations::preview::search::SearchLoad>>,
+    pub progress: std::sync::Arc<std::sync::atomic::AtomicU64>, // Bytes loaded so far
+    pub total: u64,                                             // Bytes left in this direction
+    pub backward: bool,
+}
+
+/// Follow-mode poll of the file preview (size check and new tail) in the background
+pub struct PreviewFollowTask {
+    pub task_handle:
+        tokio::task::JoinHandle<anyhow::Result<crate::operations::preview::follow::FollowLoad>>,
+    pub byte_offset: i64, // Loaded content the result applies to
+    pub file_size: i64,
+}
+
 /// Background S3 list operation task (non-blocking)
 pub struct BackgroundListTask {
-    pub task_handle: tokio::task::JoinHandle<anyhow::Result<Vec<crate::operations::s3::S3Object>>>,
+    pub task_handle: tokio::task::JoinHandle<anyhow::Result<ListTaskResult>>,
     pub profile: String,
     pub bucket: String,
     pub prefix: String,
     pub target_panel: ActivePanel,
     pub start_time: std::time::Instant,
+    /// Name of the entry to put the cursor on once the list is loaded
+    pub select: Option<String>,
 }
 
 impl Panel {
+    /// Puts the cursor on the entry with the given name (if it is visible)
+    pub fn select_by_name(&mut self, name: &str) {
+        if let Some(index) = self.list_model.iter().position(|item| item.name == name) {
+            self.selected_index = index;
+        }
+    }
+
     pub fn new_mode_selection() -> Self {
         Self {
             panel_type: PanelType::ModeSelection,
@@ -137,6 +242,7 @@ impl Panel {
             visible_height: 10,
             list_model: PanelListModel::empty(),
             s3_manager: None,
+            archive: None,
         }
     }
 
@@ -148,6 +254,7 @@ impl Panel {
             visible_height: 10,
             list_model: PanelListModel::empty(),
             s3_manager: None,
+            archive: None,
         }
     }
 
@@ -161,6 +268,7 @@ impl Panel {
             visible_height: 10,
             list_model: PanelListModel::empty(),
             s3_manager: None,
+            archive: None,
         }
     }
 }
@@ -168,6 +276,7 @@ impl Panel {
 impl App {
     pub fn new() -> anyhow::Result<Self> {
         let config_manager = ConfigManager::new()?;
+        let bandwidth = BandwidthLimits::new(config_manager.app_config.settings.bandwidth_limit);
 
         let mut app = Self {
             config_manager,
@@ -184,21 +293,31 @@ impl App {
             success_message: String::new(),
             info_message: String::new(),
             config_form: ConfigFormState::default(),
+            connection_test: ConnectionTestState::default(),
             profile_form: ProfileFormState::default(),
             file_content_preview: None,
             image_preview: None,
             image_preview_loading: false,
+            image_preview_pending: None,
+            preview_search_task: None,
+            preview_follow_task: None,
             image_preview_receiver: None,
             delete_confirmation: DeleteConfirmationState::default(),
             input: InputState::default(),
             sort_dialog: SortDialogState::default(),
             script: ScriptState::default(),
+            external_edit: ExternalEditState::default(),
+            transfer_conflict: TransferConflictState::default(),
             file_operation_queue: Vec::new(),
             current_transfer_index: None,
             selected_queue_index: 0,
             queue_focused: false, // Start unfocused
             background_transfer_task: None,
+            bandwidth,
+            throughput: ThroughputHistory::default(),
             background_list_task: None,
+            start_locations: std::collections::VecDeque::new(),
+            cursor_memory: session::CursorMemory::default(),
         };
 
         // Load local files for right panel
@@ -217,6 +336,14 @@ impl App {
             .list_model
             .set_items(converters::modes_to_items());
 
+        app.restore_session();
+        app.restore_queue();
+
+        if !app.config_manager.load_errors().is_empty() {
+            let errors = app.config_manager.load_errors().join("\n");
+            app.show_error(&format!("Config problems:\n{errors}"));
+        }
+
         Ok(app)
     }
 
@@ -269,6 +396,15 @@ impl App {
         if self.screen == Screen::ImagePreview {
             self.image_preview = None;
             self.image_preview_loading = false;
+            self.image_preview_pending = None;
+        }
+        if self.screen == Screen::FileContentPreview {
+            if let Some(task) = self.preview_search_task.take() {
+                task.task_handle.abort();
+            }
+            if let Some(task) = self.preview_follow_task.take() {
+                task.task_handle.abort();
+            }
         }
 
         if let Some(prev) = self.prev_screen.take() {
@@ -1,5 +1,6 @@
-use super::{App, LocalFile, Panel, PanelType};
+use super::{App, ArchiveLocation, LocalFile, Panel, PanelType};
 use crate::models::list::{ItemData, ItemType, PanelItem};
+use crate::operations::archive::{ArchiveData, ArchiveFormat, ArchiveIndex};
 use anyhow::{Context, Result};
 use std::path::PathBuf;
 
@@ -186,6 +187,21 @@ pub async fn enter_selected(app: &mut App) -> Result<()> {
                     let key = obj.key.clone();
                     navigate_to_s3_prefix(app, profile, bucket, key).await?;
                 }
+                Some(PanelItem {
+                    item_type: ItemType::File,
+                    data: ItemData::S3Object(obj),
+                    name,
+                    ..
+                }) => {
+                    if let Some(format) = ArchiveFormat::from_filename(name) {
+                        let location = ArchiveLocation::S3 {
+                            profile,
+                            bucket,
+                            key: obj.key.clone(),
+                        };
+                        open_archive(app, location, format).await;
+                    }
+                }
                 _ => {}
             }
         }
@@ -249,6 +265,49 @@ pub async fn enter_selected(app: &mut App) -> Result<()> {
                     let target_path = path.clone();
                     navigate_to_local_dir(app, target_path).await?;
                 }
+                Some(PanelItem {
+                    item_type: ItemType::File,
+                    data: ItemData::LocalFile { path, .. },
+                    name,
+                    ..
+                }) => {
+                    if let Some(format) = ArchiveFormat::from_filename(name) {
+                        let location = ArchiveLocation::Local { path: path.clone() };
+                        open_archive(app, location, format).await;
+                    }
+                }
+                _ => {}
+            }
+        }
+        PanelType::Archive { location, prefix } => {
+            let item = app.get_active_panel().list_model.get_item(selected_index);
+
+            match item {
+                Some(PanelItem {
+                    item_type: ItemType::ParentDir,
+                    ..
+                }) if prefix.is_empty() => {
+                    leave_archive(app, location).await?;
+                }
+                Some(PanelItem {
+                    item_type: ItemType::ParentDir,
+                    ..
+                }) => {
+                    let parent = prefix
+                        .trim_end_matches('/')
+                        .rsplit_once('/')
+                        .map(|(p, _)| format!("{p}/"))
+                        .unwrap_or_default();
+                    show_archive_dir(app, location, parent);
+                }
+                Some(PanelItem {
+                    item_type: ItemType::Directory,
+                    data: ItemData::ArchiveEntry { path },
+                    ..
+                }) => {
+                    let dir = format!("{path}/");
+                    show_archive_dir(app, location, dir);
+                }
                 _ => {}
             }
         }
@@ -257,6 +316,70 @@ pub async fn enter_selected(app: &mut App) -> Result<()> {
     Ok(())
 }
 
+/// Öffnet ein Archiv als virtuelles Verzeichnis (bei Zip auf S3 nur per Range-Reads)
+async fn open_archive(app: &mut App, location: ArchiveLocation, format: ArchiveFormat) {
+    let data = match &location {
+        ArchiveLocation::Local { path } => ArchiveData::Local(path.clone()),
+        ArchiveLocation::S3 { key, .. } => {
+            let Some(manager) = app.get_active_panel().s3_manager.clone() else {
+                return;
+            };
+            ArchiveData::S3 {
+                manager: Box::new(manager),
+                key: key.clone(),
+            }
+        }
+    };
+
+    match ArchiveIndex::open(format, data).await {
+        Ok(index) => {
+            app.get_active_panel().archive = Some(index);
+            show_archive_dir(app, location, String::new());
+        }
+        Err(e) => app.show_error(&format!("Cannot open archive '{}': {e}", location.name())),
+    }
+}
+
+/// Zeigt den Ordner `prefix` des geöffneten Archivs
+fn show_archive_dir(app: &mut App, location: ArchiveLocation, prefix: String) {
+    let side = app.active_panel.clone();
+    app.remember_cursor(&side);
+    let panel = app.get_active_panel();
+    let Some(index) = &panel.archive else {
+        return;
+    };
+    let items = super::converters::archive_entries_to_items(&index.entries, &prefix);
+    panel.panel_type = PanelType::Archive { location, prefix };
+    panel.list_model.set_items(items);
+    panel.selected_index = 0;
+    app.restore_cursor(&side);
+}
+
+/// Zurück in das Verzeichnis, in dem das Archiv liegt (Cursor auf dem Archiv)
+async fn leave_archive(app: &mut App, location: ArchiveLocation) -> Result<()> {
+    let name = location.name().to_string();
+    match location.parent() {
+        PanelType::LocalFilesystem { path } => {
+            app.get_active_panel().archive = None;
+            navigate_to_local_dir(app, path).await?;
+            app.get_active_panel().select_by_name(&name);
+        }
+        PanelType::S3Browser {
+            profile,
+            bucket,
+            prefix,
+        } => {
+            // Das Archiv wird geschlossen, sobald die Liste geladen ist
+            navigate_to_s3_prefix(app, profile, bucket, prefix).await?;
+            if let Some(task) = app.background_list_task.as_mut() {
+                task.select = Some(name);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 async fn load_s3_bucket(app: &mut App, profile: String, bucket: String) -> Result<()> {
     load_s3_bucket_no_script(app, profile, bucket).await
 }
@@ -265,6 +388,16 @@ pub async fn load_s3_bucket_no_script(
     app: &mut App,
     profile: String,
     bucket: String,
+) -> Result<()> {
+    load_s3_bucket_at(app, profile, bucket, None).await
+}
+
+/// Connects to a bucket and lists `prefix` (defaults to the configured base_prefix)
+pub async fn load_s3_bucket_at(
+    app: &mut App,
+    profile: String,
+    bucket: String,
+    prefix: Option<String>,
 ) -> Result<()> {
     let buckets = app.config_manager.get_buckets_for_profile(&profile);
     let bucket_config = buckets
@@ -272,32 +405,25 @@ pub async fn load_s3_bucket_no_script(
         .find(|b| b.name == bucket)
         .context("Bucket config not found")?;
 
-    let s3_manager = match crate::operations::s3::S3Manager::new(
-        &profile,
-        bucket.clone(),
-        bucket_config.role_chain.clone(),
-        &bucket_config.region,
-        bucket_config.endpoint_url.as_deref(),
-        bucket_config.path_style,
-    )
-    .await
-    {
-        Ok(manager) => manager,
-        Err(e) => {
-            let error_msg = format!("{e}");
-            if error_msg.contains("AccessDenied") {
-                app.show_error(&format!(
-                    "Access denied to bucket '{bucket}': Check permissions"
-                ));
-            } else {
-                app.show_error(&format!("Failed to connect to bucket '{bucket}': {e}"));
+    let s3_manager =
+        match crate::operations::s3::S3Manager::from_bucket_config(&profile, bucket_config).await {
+            Ok(manager) => manager,
+            Err(e) => {
+                let error_msg = format!("{e}");
+                if error_msg.contains("AccessDenied") {
+                    app.show_error(&format!(
+                        "Access denied to bucket '{bucket}': Check permissions"
+                    ));
+                } else {
+                    app.show_error(&format!("Failed to connect to bucket '{bucket}': {e}"));
+                }
+                return Ok(());
             }
-            return Ok(());
-        }
-    };
+        };
 
     // Use base_prefix if configured
-    let initial_prefix = bucket_config.base_prefix.clone().unwrap_or_default();
+    let initial_prefix =
+        prefix.unwrap_or_else(|| bucket_config.base_prefix.clone().unwrap_or_default());
 
     // Store s3_manager in panel first
     let panel = app.get_active_panel();
@@ -309,6 +435,184 @@ pub async fn load_s3_bucket_no_script(
     Ok(())
 }
 
+/// Opens `s3://bucket/prefix` without credentials and without a config entry.
+/// The bucket is registered for this session under the anonymous profile; the region
+/// is corrected automatically on the first redirect.
+pub async fn open_anonymous_url(app: &mut App, url: &str) -> Result<()> {
+    use crate::models::config::{BucketConfig, ANONYMOUS_PROFILE};
+
+    let Some((bucket, key)) = crate::operations::s3::parse_s3_url(url) else {
+        app.show_error("Invalid S3 URL, expected s3://bucket/prefix");
+        return Ok(());
+    };
+
+    // Only list "directories": cut a trailing object name
+    let prefix = match key.rfind('/') {
+        Some(pos) => key[..=pos].to_string(),
+        None if key.is_empty() => String::new(),
+        None => format!("{key}/"),
+    };
+
+    app.config_manager.add_session_bucket(
+        ANONYMOUS_PROFILE,
+        BucketConfig {
+            name: bucket.clone(),
+            role_chain: Vec::new(),
+            region: "us-east-1".to_string(),
+            description: Some("opened via URL".to_string()),
+            base_prefix: None,
+            endpoint_url: None,
+            path_style: None,
+            anonymous: Some(true),
+            bandwidth_limit: None,
+            source: None,
+        },
+    );
+
+    load_s3_bucket_at(app, ANONYMOUS_PROFILE.to_string(), bucket, Some(prefix)).await
+}
+
+/// Resolves the command line locations and queues them for the run loop.
+/// Setup scripts run here, before the TUI takes over the terminal.
+pub fn prepare_start_locations(app: &mut App, args: &[String]) -> Result<()> {
+    use crate::cli::{resolve_start_location, Location, StartArgs};
+
+    let start = StartArgs::parse(args)?;
+    let mut scripts_done = std::collections::HashSet::new();
+
+    // Right first, so the left panel ends up active
+    for (side, arg) in [
+        (super::ActivePanel::Right, start.right),
+        (super::ActivePanel::Left, start.left),
+    ] {
+        let Some(arg) = arg else { continue };
+        let location = resolve_start_location(&arg, &app.config_manager)?;
+
+        if let Location::S3 { profile, .. } = &location {
+            let script = app
+                .config_manager
+                .get_profile_config(profile)
+                .and_then(|p| p.setup_script.clone())
+                .filter(|s| !s.trim().is_empty());
+            if let Some(script) = script {
+                if scripts_done.insert(profile.clone()) {
+                    println!("Running setup script: {script}");
+                    match crate::operations::run::setup_script_command(&script).status() {
+                        Ok(status) if status.success() => {}
+                        Ok(_) => {
+                            app.show_error("Setup script failed");
+                            continue;
+                        }
+                        Err(e) => {
+                            app.show_error(&format!("Failed to execute setup script: {e}"));
+                            continue;
+                        }
+                    }
+                }
+            }
+        }
+
+        app.start_locations.push_back((side, location));
+    }
+
+    Ok(())
+}
+
+/// Opens a command line location in the given panel
+pub async fn open_start_location(
+    app: &mut App,
+    side: super::ActivePanel,
+    location: crate::cli::Location,
+) -> Result<()> {
+    use crate::cli::Location;
+
+    app.active_panel = side;
+
+    // A key/path without trailing slash may be a file: open its parent, cursor on the entry
+    match location {
+        Location::Local(path) => {
+            if path.is_file() {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string());
+                let parent = path.parent().map(PathBuf::from).unwrap_or_default();
+                navigate_to_local_dir(app, parent).await?;
+                if let Some(name) = name {
+                    app.get_active_panel().select_by_name(&name);
+                }
+            } else {
+                navigate_to_local_dir(app, path).await?;
+            }
+        }
+        Location::S3 {
+            profile,
+            bucket,
+            key,
+        } => {
+            let (prefix, select) = split_start_key(&key);
+            // Empty key: start at the configured base folder
+            let prefix = (!key.is_empty()).then_some(prefix);
+            load_s3_bucket_at(app, profile, bucket, prefix).await?;
+            if let Some(task) = app.background_list_task.as_mut() {
+                task.select = select;
+            }
+        }
+        Location::Public { bucket, key } => {
+            let (prefix, select) = split_start_key(&key);
+            open_anonymous_url(app, &format!("s3://{bucket}/{prefix}")).await?;
+            if let Some(task) = app.background_list_task.as_mut() {
+                task.select = select;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Connects a panel restored from the session (S3 location without S3Manager).
+/// The panel falls back to the bucket list first, so a failed connect leaves it usable.
+pub async fn reconnect_s3_panel(
+    app: &mut App,
+    profile: String,
+    bucket: String,
+    prefix: String,
+) -> Result<()> {
+    let buckets = app.config_manager.get_buckets_for_profile(&profile);
+    let panel = app.get_active_panel();
+    panel.panel_type = PanelType::BucketList {
+        profile: profile.clone(),
+    };
+    panel
+        .list_model
+        .set_items(super::converters::buckets_to_items(buckets));
+    panel.selected_index = 0;
+    panel.select_by_name(&bucket);
+
+    // Same as entering the profile: run its setup script first
+    if let Some(profile_config) = app.config_manager.get_profile_config(&profile) {
+        if let Some(script) = &profile_config.setup_script {
+            if !script.trim().is_empty() {
+                app.script.pending_script = Some(script.clone());
+                app.script.pending_profile = Some(profile);
+                app.script.pending_bucket = Some(Some(bucket));
+                app.script.pending_prefix = Some(prefix);
+                app.script.needs_terminal = true;
+                return Ok(());
+            }
+        }
+    }
+
+    load_s3_bucket_at(app, profile, bucket, Some(prefix)).await
+}
+
+/// `logs/2024/app.log` -> (`logs/2024/`, Some(`app.log`)), `logs/` -> (`logs/`, None)
+fn split_start_key(key: &str) -> (String, Option<String>) {
+    match key.rfind('/') {
+        Some(pos) if pos + 1 == key.len() => (key.to_string(), None),
+        Some(pos) => (key[..=pos].to_string(), Some(key[pos + 1..].to_string())),
+        None if key.is_empty() => (String::new(), None),
+        None => (String::new(), Some(key.to_string())),
+    }
+}
+
 async fn navigate_to_s3_prefix(
     app: &mut App,
     profile: String,
@@ -325,12 +629,15 @@ async fn navigate_to_local_dir(app: &mut App, path: PathBuf) -> Result<()> {
     let has_parent = true;
     match read_local_directory(&path) {
         Ok(files) => {
+            let side = app.active_panel.clone();
+            app.remember_cursor(&side);
             let panel = app.get_active_panel();
             panel.panel_type = PanelType::LocalFilesystem { path };
             panel
                 .list_model
                 .set_items(super::converters::local_files_to_items(files, has_parent));
             panel.selected_index = 0;
+            app.restore_cursor(&side);
         }
         Err(e) => {
             let error_msg = format!("{e}");
@@ -509,8 +816,13 @@ pub fn start_background_list_objects(
     // Clone prefix for async task
     let prefix_clone = prefix.clone();
 
-    // Spawn background task
-    let task_handle = tokio::spawn(async move { s3_manager.list_objects(&prefix_clone).await });
+    // Spawn background task (the manager switches region itself on a redirect)
+    let task_handle = tokio::spawn(async move {
+        let region = s3_manager.region();
+        let objects = s3_manager.list_objects(&prefix_clone).await?;
+        let switched = Some(s3_manager.region()).filter(|current| *current != region);
+        Ok((objects, switched))
+    });
 
     // Store task in app
     app.background_list_task = Some(super::BackgroundListTask {
@@ -520,6 +832,7 @@ pub fn start_background_list_objects(
         prefix,
         target_panel,
         start_time: std::time::Instant::now(),
+        select: None,
     });
 
     // Don't show loading indicator immediately - will show after 1 second if still running
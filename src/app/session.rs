@@ -0,0 +1,282 @@
+//! Session state: panel locations, sort, filter and cursor positions.
+//! Saved to `session.json` next to the config on quit and restored on start.
+//! The transfer queue is kept separately in `queue.json` (see operations::queue).
+
+use super::{converters, navigation, ActivePanel, App, ArchiveLocation, Panel, PanelType};
+use crate::models::config::write_atomic;
+use crate::models::list::{FilterCriteria, SortCriteria};
+use crate::operations::{queue, OperationStatus};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const SESSION_FILE: &str = "session.json";
+
+/// Cursor positions beyond this are dropped (oldest first)
+const MAX_CURSOR_ENTRIES: usize = 500;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub left: Option<PanelState>,
+    pub right: Option<PanelState>,
+    pub active_panel: Option<ActivePanel>,
+    pub cursors: CursorMemory,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PanelState {
+    pub panel_type: PanelType,
+    pub sort: SortCriteria,
+    #[serde(default)]
+    pub filter: FilterCriteria,
+}
+
+/// Last selected entry per directory (location key, entry name), oldest first
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CursorMemory(Vec<(String, String)>);
+
+impl CursorMemory {
+    pub fn remember(&mut self, location: String, name: String) {
+        self.0.retain(|(key, _)| key != &location);
+        self.0.push((location, name));
+        if self.0.len() > MAX_CURSOR_ENTRIES {
+            let excess = self.0.len() - MAX_CURSOR_ENTRIES;
+            self.0.drain(..excess);
+        }
+    }
+
+    pub fn get(&self, location: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == location)
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+impl SessionState {
+    /// A missing or unreadable state file is not an error - the session just starts fresh
+    pub fn load(config_dir: &Path) -> Self {
+        std::fs::read_to_string(config_dir.join(SESSION_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&config_dir.join(SESSION_FILE), content.as_bytes())
+    }
+}
+
+impl Panel {
+    /// Key for the per-directory cursor memory (only directory-like panels)
+    pub fn location_key(&self) -> Option<String> {
+        match &self.panel_type {
+            PanelType::S3Browser {
+                profile,
+                bucket,
+                prefix,
+            } => Some(format!("s3:{profile}:{bucket}/{prefix}")),
+            PanelType::LocalFilesystem { path } => Some(format!("local:{}", path.display())),
+            PanelType::Archive { location, prefix } => match location {
+                ArchiveLocation::Local { path } => {
+                    Some(format!("archive:local:{}/{prefix}", path.display()))
+                }
+                ArchiveLocation::S3 {
+                    profile,
+                    bucket,
+                    key,
+                } => Some(format!("archive:s3:{profile}:{bucket}/{key}/{prefix}")),
+            },
+            _ => None,
+        }
+    }
+
+    fn selected_name(&self) -> Option<String> {
+        self.list_model
+            .get_item(self.selected_index)
+            .map(|item| item.name.clone())
+    }
+
+    fn to_state(&self) -> PanelState {
+        // Archive werden nicht wieder geöffnet, nur das Verzeichnis, in dem sie liegen
+        let panel_type = match &self.panel_type {
+            PanelType::Archive { location, .. } => location.parent(),
+            panel_type => panel_type.clone(),
+        };
+        PanelState {
+            panel_type,
+            sort: self.list_model.get_current_sort(),
+            filter: self.list_model.get_filter().clone(),
+        }
+    }
+}
+
+impl App {
+    fn panel_mut(&mut self, side: &ActivePanel) -> &mut Panel {
+        match side {
+            ActivePanel::Left => &mut self.left_panel,
+            ActivePanel::Right => &mut self.right_panel,
+        }
+    }
+
+    /// Remembers the cursor of a panel before it leaves its directory
+    pub fn remember_cursor(&mut self, side: &ActivePanel) {
+        let panel = self.panel_mut(side);
+        if let (Some(key), Some(name)) = (panel.location_key(), panel.selected_name()) {
+            // ".." is the default position anyway
+            if name != ".." {
+                self.cursor_memory.remember(key, name);
+            }
+        }
+    }
+
+    /// Puts the cursor back on the entry selected when the directory was last left
+    pub fn restore_cursor(&mut self, side: &ActivePanel) {
+        let panel = match side {
+            ActivePanel::Left => &mut self.left_panel,
+            ActivePanel::Right => &mut self.right_panel,
+        };
+        if let Some(name) = panel
+            .location_key()
+            .and_then(|key| self.cursor_memory.get(&key))
+        {
+            panel.select_by_name(name);
+        }
+    }
+
+    /// Restores the last session. Local directories are listed right away,
+    /// S3 locations connect lazily once their panel becomes active.
+    pub fn restore_session(&mut self) {
+        if !self.config_manager.app_config.settings.restore_session {
+            return;
+        }
+
+        let state = SessionState::load(&self.config_manager.config_dir());
+        self.cursor_memory = state.cursors;
+
+        for (side, panel_state) in [
+            (ActivePanel::Left, state.left),
+            (ActivePanel::Right, state.right),
+        ] {
+            let Some(panel_state) = panel_state else {
+                continue;
+            };
+            let panel = self.panel_mut(&side);
+            panel.list_model.set_sort(panel_state.sort);
+            panel.list_model.set_filter(panel_state.filter);
+            panel.selected_index = 0;
+
+            self.restore_panel(&side, panel_state.panel_type);
+            self.restore_cursor(&side);
+        }
+
+        if let Some(active_panel) = state.active_panel {
+            self.active_panel = active_panel;
+        }
+    }
+
+    fn restore_panel(&mut self, side: &ActivePanel, panel_type: PanelType) {
+        match panel_type {
+            PanelType::LocalFilesystem { path } if path.is_dir() => {
+                if let Ok(files) = navigation::read_local_directory(&path) {
+                    let panel = self.panel_mut(side);
+                    panel.panel_type = PanelType::LocalFilesystem { path };
+                    panel
+                        .list_model
+                        .set_items(converters::local_files_to_items(files, true));
+                }
+            }
+            // No connection yet - see navigation::reconnect_s3_panel
+            PanelType::S3Browser {
+                profile,
+                bucket,
+                prefix,
+            } if self
+                .config_manager
+                .get_bucket_config(&profile, &bucket)
+                .is_some() =>
+            {
+                let panel = self.panel_mut(side);
+                panel.panel_type = PanelType::S3Browser {
+                    profile,
+                    bucket,
+                    prefix,
+                };
+                panel.list_model.set_items(Vec::new());
+                panel.s3_manager = None;
+            }
+            // Bucket list and profile list: back to the profile list, so entering
+            // the profile runs its setup script as usual
+            PanelType::BucketList { profile } => {
+                self.restore_panel(side, PanelType::ProfileList);
+                self.panel_mut(side).select_by_name(&profile);
+            }
+            PanelType::ProfileList => {
+                let profiles = self.config_manager.aws_profiles.clone();
+                let panel = self.panel_mut(side);
+                panel.panel_type = PanelType::ProfileList;
+                panel
+                    .list_model
+                    .set_items(converters::profiles_to_items(&profiles));
+            }
+            _ => {}
+        }
+    }
+
+    /// Writes the current session (called on quit)
+    pub fn save_session(&mut self) -> Result<()> {
+        if !self.config_manager.app_config.settings.restore_session {
+            return Ok(());
+        }
+
+        self.remember_cursor(&ActivePanel::Left);
+        self.remember_cursor(&ActivePanel::Right);
+
+        let state = SessionState {
+            left: Some(self.left_panel.to_state()),
+            right: Some(self.right_panel.to_state()),
+            active_panel: Some(self.active_panel.clone()),
+            cursors: self.cursor_memory.clone(),
+        };
+        state.save(&self.config_manager.config_dir())
+    }
+
+    /// Brings back the unfinished transfers of the last run (paused until resumed)
+    pub fn restore_queue(&mut self) {
+        self.file_operation_queue = queue::load_queue(&self.config_manager.config_dir());
+
+        let paused = self
+            .file_operation_queue
+            .iter()
+            .filter(|op| op.status == OperationStatus::Paused)
+            .count();
+        if paused > 0 {
+            self.show_info(&format!(
+                "{paused} unfinished transfer(s) restored - press 'r' to resume the queue"
+            ));
+        }
+    }
+
+    /// Writes the unfinished transfers (on quit and whenever the queue moves on)
+    pub fn save_queue(&mut self) -> Result<()> {
+        // The upload id of a running multipart upload only lives in its task so far
+        if let (Some(task), Some(index)) =
+            (&self.background_transfer_task, self.current_transfer_index)
+        {
+            if let (Ok(running), Some(op)) = (
+                task.operation.try_lock(),
+                self.file_operation_queue.get_mut(index),
+            ) {
+                op.upload_id = running.upload_id.clone();
+            }
+        }
+
+        queue::save_queue(
+            &self.config_manager.config_dir(),
+            &self.file_operation_queue,
+        )
+    }
+}
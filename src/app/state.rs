@@ -11,10 +11,30 @@ pub struct ConfigFormState {
     pub roles: Vec<String>,
     pub endpoint_url: String,
     pub path_style: bool,
+    pub anonymous: bool,
+    // Not editable in the form, kept when saving
+    pub bandwidth_limit: Option<u64>,
     pub field: usize,
     pub cursor: usize,
 }
 
+impl ConfigFormState {
+    /// Fields 0-4 are text inputs, 5 and 6 the checkboxes, then one field per role
+    pub const FIRST_ROLE_FIELD: usize = 7;
+
+    /// Index of the first button (Save, followed by Test and Cancel)
+    pub fn button_field(&self) -> usize {
+        Self::FIRST_ROLE_FIELD + self.roles.len()
+    }
+
+    /// Index into `roles` if the focused field is a role
+    pub fn role_index(&self) -> Option<usize> {
+        (Self::FIRST_ROLE_FIELD..self.button_field())
+            .contains(&self.field)
+            .then(|| self.field - Self::FIRST_ROLE_FIELD)
+    }
+}
+
 /// State for the profile configuration form
 #[derive(Debug, Clone, Default)]
 pub struct ProfileFormState {
@@ -34,6 +54,15 @@ pub struct DeleteConfirmationState {
     pub button: usize,
 }
 
+/// Transfer waiting for a decision because its destination exists
+#[derive(Debug, Clone, Default)]
+pub struct TransferConflictState {
+    pub operation: Option<crate::operations::FileOperation>,
+    pub source: crate::operations::conflict::FileStat,
+    pub destination: crate::operations::conflict::FileStat,
+    pub selected: usize,
+}
+
 /// State for generic input dialog
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum InputMode {
@@ -42,6 +71,12 @@ pub enum InputMode {
     CreateFolder,
     Filter,
     Rename,
+    OpenUrl,
+    BandwidthLimit,
+    PreviewSearch {
+        backward: bool,
+    },
+    PreviewGotoOffset,
     UploadPath {
         local_file_path: PathBuf,
         local_file_name: String,
@@ -63,11 +98,58 @@ pub struct SortDialogState {
     pub selected: usize,
 }
 
+/// State for the connection test screen
+#[derive(Debug, Default)]
+pub struct ConnectionTestState {
+    pub title: String,
+    pub steps: Vec<crate::operations::diagnostics::DiagnosticStep>,
+    pub receiver: Option<
+        tokio::sync::mpsc::UnboundedReceiver<crate::operations::diagnostics::DiagnosticUpdate>,
+    >,
+    pub task_handle: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl ConnectionTestState {
+    pub fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+}
+
+/// File opened in an external editor or pager (o / v)
+#[derive(Clone)]
+pub struct ExternalFile {
+    pub name: String,
+    pub path: PathBuf, // Local file or temp copy of the S3 object
+    pub edit: bool,    // $EDITOR, otherwise $PAGER
+    pub s3: Option<ExternalS3Origin>,
+    pub fingerprint: Option<crate::operations::external::FileFingerprint>,
+}
+
+/// S3 object behind a temp copy, with the ETag it had when downloaded
+#[derive(Clone)]
+pub struct ExternalS3Origin {
+    pub manager: crate::operations::s3::S3Manager,
+    pub bucket: String,
+    pub key: String,
+    pub etag: Option<String>,
+}
+
+/// State for external editing: the file waiting for the terminal and the
+/// write-back dialog after an S3 copy was changed
+#[derive(Clone, Default)]
+pub struct ExternalEditState {
+    pub pending: Option<ExternalFile>,
+    pub upload: Option<ExternalFile>,
+    pub modified_remotely: bool, // ETag changed since the download
+    pub button: usize,
+}
+
 /// State for pending script execution
 #[derive(Debug, Clone, Default)]
 pub struct ScriptState {
     pub pending_script: Option<String>,
     pub pending_profile: Option<String>,
     pub pending_bucket: Option<Option<String>>,
+    pub pending_prefix: Option<String>,
     pub needs_terminal: bool,
 }
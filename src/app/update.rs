@@ -69,6 +69,13 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
 
         // ===== File Preview Navigation =====
         Message::FilePreviewUp => {
+            handlers::pause_follow(app);
+            if handlers::is_hex_preview(app) {
+                return Ok(handlers::scroll_hex_view(app, -1));
+            }
+            if handlers::is_table_preview(app) {
+                return Ok(handlers::scroll_table_view(app, -1));
+            }
             handlers::scroll_file_preview_up(app);
             // Auto-load previous content when near top (for Backward mode)
             if let Some(preview) = &app.file_content_preview {
@@ -82,10 +89,16 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(None)
         }
         Message::FilePreviewDown => {
+            if handlers::is_hex_preview(app) {
+                return Ok(handlers::scroll_hex_view(app, 1));
+            }
+            if handlers::is_table_preview(app) {
+                return Ok(handlers::scroll_table_view(app, 1));
+            }
             handlers::scroll_file_preview_down(app);
             // Auto-load more content when near end (for S3 files)
             if let Some(preview) = &app.file_content_preview {
-                let line_count = preview.content.lines().count();
+                let line_count = preview.text().lines().count();
                 if line_count.saturating_sub(preview.scroll_offset) < 50 {
                     return Ok(Some(Message::LoadMoreFileContent));
                 }
@@ -93,6 +106,13 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(None)
         }
         Message::FilePreviewPageUp => {
+            handlers::pause_follow(app);
+            if handlers::is_hex_preview(app) {
+                return Ok(handlers::scroll_hex_view(app, -20));
+            }
+            if handlers::is_table_preview(app) {
+                return Ok(handlers::scroll_table_view(app, -20));
+            }
             handlers::scroll_file_preview_page_up(app, 20);
             // Auto-load previous content when near top (for Backward mode)
             if let Some(preview) = &app.file_content_preview {
@@ -106,10 +126,16 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(None)
         }
         Message::FilePreviewPageDown => {
+            if handlers::is_hex_preview(app) {
+                return Ok(handlers::scroll_hex_view(app, 20));
+            }
+            if handlers::is_table_preview(app) {
+                return Ok(handlers::scroll_table_view(app, 20));
+            }
             handlers::scroll_file_preview_page_down(app, 20);
             // Auto-load more content when near end (for S3 files)
             if let Some(preview) = &app.file_content_preview {
-                let line_count = preview.content.lines().count();
+                let line_count = preview.text().lines().count();
                 if line_count.saturating_sub(preview.scroll_offset) < 50 {
                     return Ok(Some(Message::LoadMoreFileContent));
                 }
@@ -117,21 +143,152 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(None)
         }
         Message::FilePreviewHome => {
+            handlers::pause_follow(app);
+            if handlers::is_hex_preview(app) {
+                handlers::hex_view_home(app).await;
+                return Ok(None);
+            }
             handlers::scroll_file_preview_home(app).await?;
+            if let Some(preview) = &mut app.file_content_preview {
+                preview.reset_match();
+            }
+            if handlers::is_table_preview(app) {
+                handlers::scroll_table_to(app, false);
+            }
             Ok(None)
         }
         Message::FilePreviewEnd => {
+            if handlers::is_hex_preview(app) {
+                handlers::hex_view_end(app).await;
+                return Ok(None);
+            }
             handlers::scroll_file_preview_end(app).await?;
+            if let Some(preview) = &mut app.file_content_preview {
+                preview.reset_match();
+            }
+            if handlers::is_table_preview(app) {
+                handlers::scroll_table_to(app, true);
+            }
+            handlers::resume_follow(app);
             Ok(None)
         }
         Message::LoadMoreFileContent => {
+            if handlers::is_hex_preview(app) {
+                handlers::load_more_hex(app).await;
+                return Ok(None);
+            }
             handlers::load_more_file_content(app).await?;
             Ok(None)
         }
         Message::LoadPreviousFileContent => {
+            if handlers::is_hex_preview(app) {
+                handlers::load_previous_hex(app).await;
+                return Ok(None);
+            }
             handlers::load_previous_file_content(app).await?;
             Ok(None)
         }
+        Message::ShowPreviewSearch { backward } => {
+            handlers::pause_follow(app);
+            handlers::show_preview_search_prompt(app, backward);
+            Ok(None)
+        }
+        Message::PreviewSearchNext => {
+            handlers::pause_follow(app);
+            handlers::preview_search_next(app, false);
+            Ok(None)
+        }
+        Message::PreviewSearchPrevious => {
+            handlers::pause_follow(app);
+            handlers::preview_search_next(app, true);
+            Ok(None)
+        }
+        Message::CancelPreviewSearch => {
+            handlers::cancel_preview_search(app);
+            Ok(None)
+        }
+        Message::ToggleHexView => {
+            handlers::toggle_hex_view(app).await;
+            Ok(None)
+        }
+        Message::ShowGotoOffsetPrompt => {
+            handlers::show_goto_offset_prompt(app);
+            Ok(None)
+        }
+        Message::TogglePrettyJson => {
+            handlers::toggle_pretty_json(app);
+            Ok(None)
+        }
+        Message::ToggleTableView => {
+            handlers::toggle_table_view(app);
+            Ok(None)
+        }
+        Message::ToggleFollowMode => {
+            handlers::toggle_follow_mode(app).await;
+            Ok(None)
+        }
+
+        // ===== Image Preview =====
+        Message::ConfirmImageLoad => {
+            handlers::confirm_image_loading(app);
+            Ok(None)
+        }
+        Message::ImageZoomIn => {
+            handlers::zoom_image(app, 1);
+            Ok(None)
+        }
+        Message::ImageZoomOut => {
+            handlers::zoom_image(app, -1);
+            Ok(None)
+        }
+        Message::ImageZoomReset => {
+            handlers::zoom_image(app, 0);
+            Ok(None)
+        }
+        Message::ImagePan { dx, dy } => {
+            handlers::pan_image(app, dx, dy);
+            Ok(None)
+        }
+        Message::ToggleImageInfo => {
+            handlers::toggle_image_info(app);
+            Ok(None)
+        }
+        Message::NextImage => {
+            handlers::show_adjacent_image(app, true).await?;
+            Ok(None)
+        }
+        Message::PreviousImage => {
+            handlers::show_adjacent_image(app, false).await?;
+            Ok(None)
+        }
+        Message::TableScrollLeft => {
+            handlers::scroll_table_columns(app, -1);
+            Ok(None)
+        }
+        Message::TableScrollRight => {
+            handlers::scroll_table_columns(app, 1);
+            Ok(None)
+        }
+        Message::ShowColumnPicker => {
+            handlers::show_column_picker(app);
+            Ok(None)
+        }
+        Message::ColumnPickerUp => {
+            handlers::column_picker_move(app, -1);
+            Ok(None)
+        }
+        Message::ColumnPickerDown => {
+            handlers::column_picker_move(app, 1);
+            Ok(None)
+        }
+        Message::ToggleColumnHidden => {
+            handlers::toggle_column_hidden(app);
+            Ok(None)
+        }
+        Message::JumpToColumn => {
+            handlers::jump_to_column(app);
+            Ok(None)
+        }
 
         // ===== Sort Dialog =====
         Message::ShowSortDialog => {
@@ -161,6 +318,16 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(None)
         }
 
+        Message::ShowOpenUrlPrompt => {
+            handlers::show_open_url_prompt(app);
+            Ok(None)
+        }
+
+        Message::ShowBandwidthPrompt => {
+            handlers::show_bandwidth_prompt(app);
+            Ok(None)
+        }
+
         // ===== Config & Profile Forms =====
         Message::ShowConfigForm => {
             handlers::show_config_form(app);
@@ -179,6 +346,27 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(None)
         }
 
+        // ===== Transfer Conflict =====
+        Message::TransferConflictUp => {
+            app.transfer_conflict.selected = app.transfer_conflict.selected.saturating_sub(1);
+            Ok(None)
+        }
+        Message::TransferConflictDown => {
+            let last = crate::operations::conflict::ConflictResolution::ALL.len() - 1;
+            app.transfer_conflict.selected = (app.transfer_conflict.selected + 1).min(last);
+            Ok(None)
+        }
+        Message::ConfirmTransferConflict => {
+            app.go_back();
+            app.confirm_transfer_conflict().await?;
+            Ok(None)
+        }
+        Message::SkipTransferConflict => {
+            app.go_back();
+            app.skip_transfer_conflict();
+            Ok(None)
+        }
+
         // ===== Delete Confirmation =====
         Message::DeleteConfirmationLeft => {
             if app.delete_confirmation.button > 0 {
@@ -199,6 +387,35 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(Some(Message::GoBack))
         }
 
+        // ===== External Editor =====
+        Message::EditFile => {
+            handlers::open_in_external_program(app, true).await;
+            Ok(None)
+        }
+        Message::OpenInPager => {
+            handlers::open_in_external_program(app, false).await;
+            Ok(None)
+        }
+        Message::ExternalUploadLeft => {
+            app.external_edit.button = 0;
+            Ok(None)
+        }
+        Message::ExternalUploadRight => {
+            app.external_edit.button = 1;
+            Ok(None)
+        }
+        Message::ConfirmExternalUpload => {
+            if handlers::confirm_external_upload(app).await {
+                Ok(Some(Message::GoBack))
+            } else {
+                Ok(None)
+            }
+        }
+        Message::CancelExternalUpload => {
+            handlers::cancel_external_upload(app);
+            Ok(Some(Message::GoBack))
+        }
+
         // ===== Messages/Errors =====
         Message::ShowError { message } => {
             app.show_error(&message);
@@ -230,9 +447,21 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             handlers::handle_config_form_message(app, msg)?;
             Ok(None)
         }
-        Message::SaveConfigForm => {
-            handlers::save_config_form(app)?;
-            Ok(Some(Message::GoBack))
+        Message::SaveConfigForm => match handlers::save_config_form(app) {
+            Ok(()) => Ok(Some(Message::GoBack)),
+            Err(e) => {
+                // Keep the form open so the input can be corrected
+                app.show_error(&format!("Failed to save configuration: {e}"));
+                Ok(None)
+            }
+        },
+        Message::TestConnection => {
+            handlers::start_connection_test(app);
+            Ok(None)
+        }
+        Message::CloseConnectionTest => {
+            handlers::close_connection_test(app);
+            Ok(None)
         }
         Message::EditBucketConfig => {
             handlers::edit_bucket_config(app);
@@ -256,10 +485,14 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             handlers::handle_profile_form_message(app, msg)?;
             Ok(None)
         }
-        Message::SaveProfileConfig => {
-            handlers::save_profile_config(app)?;
-            Ok(Some(Message::GoBack))
-        }
+        Message::SaveProfileConfig => match handlers::save_profile_config(app) {
+            Ok(()) => Ok(Some(Message::GoBack)),
+            Err(e) => {
+                // Keep the form open so the input can be corrected
+                app.show_error(&format!("Failed to save configuration: {e}"));
+                Ok(None)
+            }
+        },
 
         // ===== Input Messages =====
         Message::InputChar { c, ctrl } => {
@@ -327,6 +560,10 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
         }
         Message::InputSubmit => {
             handlers::handle_input_submit(app).await?;
+            // The upload path may have opened the conflict dialog instead
+            if app.screen == super::Screen::TransferConflict {
+                return Ok(None);
+            }
             Ok(Some(Message::GoBack))
         }
         Message::InputCancel => {
@@ -372,8 +609,12 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
                 // Update operation status
                 let mut operation = task.operation.lock().await;
                 operation.status = crate::operations::OperationStatus::Cancelled;
+                operation.mark_finished();
                 let operation_type = operation.operation_type.clone();
 
+                crate::operations::run::discard_multipart_upload(app, &operation);
+                operation.upload_id = None;
+
                 // Update current operation in queue
                 if let Some(index) = app.current_transfer_index {
                     if let Some(op) = app.file_operation_queue.get_mut(index) {
@@ -401,6 +642,13 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             Ok(None)
         }
         Message::ClearCompletedTransfers => {
+            // Failed uploads may still hold a multipart upload
+            for op in &app.file_operation_queue {
+                if matches!(op.status, crate::operations::OperationStatus::Failed(_)) {
+                    crate::operations::run::discard_multipart_upload(app, op);
+                }
+            }
+
             // Remove all completed, failed, and cancelled transfers
             app.file_operation_queue.retain(|op| {
                 !matches!(
@@ -439,7 +687,8 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
                 .iter()
                 .rposition(|op| op.status != crate::operations::OperationStatus::InProgress)
             {
-                app.file_operation_queue.remove(pos);
+                let removed = app.file_operation_queue.remove(pos);
+                crate::operations::run::discard_multipart_upload(app, &removed);
 
                 // Fix current_transfer_index if it was affected by remove()
                 if let Some(current_idx) = app.current_transfer_index {
@@ -460,6 +709,132 @@ pub async fn update(app: &mut App, msg: Message) -> Result<Option<Message>> {
             }
             Ok(None)
         }
+        Message::ShowQueueDetails => {
+            app.prev_screen = Some(app.screen.clone());
+            app.screen = super::Screen::QueueDetails;
+            Ok(None)
+        }
+        Message::ResumeQueue => {
+            // Paused transfers go back to the queue, processing picks them up
+            for op in &mut app.file_operation_queue {
+                if op.status == crate::operations::OperationStatus::Paused {
+                    op.status = crate::operations::OperationStatus::Pending;
+                }
+            }
+            Ok(None)
+        }
+        Message::TogglePauseTransfer => {
+            // Selected item when the queue is focused, otherwise the running transfer
+            let index = if app.queue_focused {
+                Some(app.selected_queue_index)
+            } else {
+                app.current_transfer_index
+            };
+            let Some(index) = index else {
+                return Ok(None);
+            };
+            let status = app
+                .file_operation_queue
+                .get(index)
+                .map(|op| op.status.clone());
+            match status {
+                Some(crate::operations::OperationStatus::InProgress)
+                    if app.current_transfer_index == Some(index) =>
+                {
+                    crate::operations::run::pause_running_transfer(app).await;
+                    app.show_info("Transfer paused - press 'p' with the queue focused to resume");
+                }
+                Some(crate::operations::OperationStatus::Pending) => {
+                    app.file_operation_queue[index].status =
+                        crate::operations::OperationStatus::Paused;
+                }
+                Some(crate::operations::OperationStatus::Paused) => {
+                    app.file_operation_queue[index].status =
+                        crate::operations::OperationStatus::Pending;
+                }
+                _ => {}
+            }
+            Ok(None)
+        }
+        Message::MoveQueueItemUp | Message::MoveQueueItemDown => {
+            // Display is newest first: up = towards the end of the queue
+            let index = app.selected_queue_index;
+            let other = if matches!(msg, Message::MoveQueueItemUp) {
+                index + 1
+            } else {
+                index.wrapping_sub(1)
+            };
+            let queue = &mut app.file_operation_queue;
+            if queue.get(index).is_some_and(|op| op.is_waiting())
+                && queue.get(other).is_some_and(|op| op.is_waiting())
+            {
+                queue.swap(index, other);
+                app.selected_queue_index = other;
+            }
+            Ok(None)
+        }
+        Message::RunQueueItemNext => {
+            let index = app.selected_queue_index;
+            let queue = &mut app.file_operation_queue;
+            if !queue.get(index).is_some_and(|op| op.is_waiting()) {
+                return Ok(None);
+            }
+            // Front of the waiting items, with the top priority among them
+            let front = queue.iter().position(|op| op.is_waiting()).unwrap_or(index);
+            let top_priority = queue
+                .iter()
+                .filter(|op| op.is_waiting())
+                .map(|op| op.priority)
+                .max()
+                .unwrap_or_default();
+            let mut op = queue.remove(index);
+            op.priority = op.priority.max(top_priority);
+            if op.status == crate::operations::OperationStatus::Paused {
+                op.status = crate::operations::OperationStatus::Pending;
+            }
+            queue.insert(front, op);
+
+            // Items in between moved back by one
+            if let Some(current) = app.current_transfer_index.as_mut() {
+                if (front..index).contains(current) {
+                    *current += 1;
+                }
+            }
+            app.selected_queue_index = front;
+            Ok(None)
+        }
+        Message::RaiseQueuePriority | Message::LowerQueuePriority => {
+            if let Some(op) = app.file_operation_queue.get_mut(app.selected_queue_index) {
+                if op.is_waiting() {
+                    op.priority = if matches!(msg, Message::RaiseQueuePriority) {
+                        op.priority.raised()
+                    } else {
+                        op.priority.lowered()
+                    };
+                }
+            }
+            Ok(None)
+        }
+        Message::RetryTransfer => {
+            if let Some(op) = app.file_operation_queue.get_mut(app.selected_queue_index) {
+                if matches!(
+                    op.status,
+                    crate::operations::OperationStatus::Failed(_)
+                        | crate::operations::OperationStatus::Cancelled
+                ) {
+                    op.retry();
+                }
+            }
+            Ok(None)
+        }
+        Message::RetryAllFailed => {
+            for op in &mut app.file_operation_queue {
+                if matches!(op.status, crate::operations::OperationStatus::Failed(_)) {
+                    op.retry();
+                }
+            }
+            Ok(None)
+        }
         Message::QueueNavigateUp => {
             // Move selection up in display (towards newer items = higher index)
             let max_index = app.file_operation_queue.len().saturating_sub(1);
@@ -0,0 +1,523 @@
+use super::{usage_error, Context, Location, Options};
+use crate::operations::s3::S3Manager;
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Default validity of presigned URLs (1 hour)
+const DEFAULT_PRESIGN_EXPIRY: u64 = 3600;
+
+/// One side of a transfer: local file/directory or object/prefix in a bucket
+#[derive(Clone)]
+enum Endpoint {
+    Local(PathBuf),
+    Remote {
+        manager: Arc<S3Manager>,
+        /// None for public buckets (no credentials, no server-side copy)
+        profile: Option<String>,
+        key: String,
+    },
+}
+
+impl Endpoint {
+    async fn open(ctx: &mut Context, location: &Location) -> Result<Self> {
+        Ok(match location {
+            Location::Local(path) => Endpoint::Local(path.clone()),
+            Location::S3 { profile, key, .. } => Endpoint::Remote {
+                manager: Arc::new(ctx.connect(location).await?),
+                profile: Some(profile.clone()),
+                key: key.clone(),
+            },
+            Location::Public { key, .. } => Endpoint::Remote {
+                manager: Arc::new(ctx.connect(location).await?),
+                profile: None,
+                key: key.clone(),
+            },
+        })
+    }
+
+    fn is_local(&self) -> bool {
+        matches!(self, Endpoint::Local(_))
+    }
+
+    /// Entry below this directory/prefix (`rel` uses '/' as separator)
+    fn child(&self, rel: &str) -> Self {
+        match self {
+            Endpoint::Local(path) => Endpoint::Local(path.join(rel)),
+            Endpoint::Remote {
+                manager,
+                profile,
+                key,
+            } => Endpoint::Remote {
+                manager: manager.clone(),
+                profile: profile.clone(),
+                key: format!("{}{rel}", dir_prefix(key)),
+            },
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Endpoint::Local(path) => path.display().to_string(),
+            Endpoint::Remote { manager, key, .. } => format!("s3://{}/{key}", manager.bucket),
+        }
+    }
+}
+
+/// File or object found while listing a directory/prefix
+struct Entry {
+    rel: String,
+    size: u64,
+    modified: Option<DateTime<Utc>>,
+}
+
+/// `dir` -> `dir/`, empty stays empty (bucket root)
+fn dir_prefix(key: &str) -> String {
+    if key.is_empty() || key.ends_with('/') {
+        key.to_string()
+    } else {
+        format!("{key}/")
+    }
+}
+
+fn base_name(key: &str) -> &str {
+    key.trim_end_matches('/').rsplit('/').next().unwrap_or(key)
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| " ".repeat(19))
+}
+
+pub async fn ls(ctx: &mut Context, opts: &Options) -> Result<()> {
+    let location = match opts.positional.as_slice() {
+        [] => {
+            for profile in &ctx.config_manager.app_config.profiles {
+                for bucket in &profile.buckets {
+                    println!("{}:{}", profile.name, bucket.name);
+                }
+            }
+            return Ok(());
+        }
+        [arg] => ctx.location(arg)?,
+        _ => return Err(usage_error("ls takes at most one location")),
+    };
+
+    // "profile:" lists the buckets of the profile
+    if let Location::S3 {
+        profile, bucket, ..
+    } = &location
+    {
+        if bucket.is_empty() {
+            for bucket in ctx.config_manager.get_buckets_for_profile(profile) {
+                println!("{profile}:{}", bucket.name);
+            }
+            return Ok(());
+        }
+    }
+
+    let manager = ctx.connect(&location).await?;
+    let prefix = location.key();
+
+    if opts.recursive {
+        for obj in manager.list_objects_recursive(prefix).await? {
+            println!(
+                "{} {:>12} {}",
+                format_time(obj.last_modified),
+                obj.size,
+                obj.key
+            );
+        }
+        return Ok(());
+    }
+
+    // Names relative to the "directory" of the prefix, like `aws s3 ls`
+    let base = prefix.rfind('/').map(|i| &prefix[..=i]).unwrap_or("");
+    for obj in manager.list_objects(prefix).await? {
+        let name = obj.key.strip_prefix(base).unwrap_or(&obj.key);
+        if obj.is_prefix {
+            println!("{:>32} {name}", "PRE");
+        } else {
+            println!("{} {:>12} {name}", format_time(obj.last_modified), obj.size);
+        }
+    }
+    Ok(())
+}
+
+/// `cp` and `mv` (with `delete_source`)
+pub async fn cp(ctx: &mut Context, opts: &Options, delete_source: bool) -> Result<()> {
+    let command = if delete_source { "mv" } else { "cp" };
+    let [src_arg, dst_arg] = opts.positional.as_slice() else {
+        return Err(usage_error(format!(
+            "{command} needs a source and a destination"
+        )));
+    };
+    let src_location = ctx.location(src_arg)?;
+    let dst_location = ctx.location(dst_arg)?;
+    if src_location.is_local() && dst_location.is_local() {
+        return Err(usage_error(format!(
+            "{command}: source or destination must be an S3 location"
+        )));
+    }
+
+    let src = Endpoint::open(ctx, &src_location).await?;
+    let dst = Endpoint::open(ctx, &dst_location).await?;
+
+    let src_is_dir = match &src {
+        Endpoint::Local(path) => path.is_dir(),
+        Endpoint::Remote { key, .. } => key.is_empty() || key.ends_with('/'),
+    };
+
+    if opts.recursive {
+        let entries = list_entries(&src).await?;
+        if entries.is_empty() {
+            return Err(anyhow::anyhow!("{src_arg}: nothing to {command}"));
+        }
+        for entry in entries {
+            let from = src.child(&entry.rel);
+            let to = dst.child(&entry.rel);
+            if let Err(e) = transfer(ctx, &from, &to, entry.size, delete_source).await {
+                ctx.fail(&from.label(), &e);
+            }
+        }
+        return Ok(());
+    }
+
+    if src_is_dir {
+        return Err(usage_error(format!(
+            "{src_arg} is a directory/prefix, use {command} -r"
+        )));
+    }
+
+    let size = match &src {
+        Endpoint::Local(path) => tokio::fs::metadata(path)
+            .await
+            .with_context(|| format!("Cannot read {}", path.display()))?
+            .len(),
+        Endpoint::Remote { manager, key, .. } => manager.get_object_size(key).await?.max(0) as u64,
+    };
+
+    // Destination directory/prefix: keep the source file name
+    let dst = match &dst {
+        Endpoint::Local(path) if path.is_dir() || dst_arg.ends_with(['/', '\\']) => {
+            dst.child(&source_name(&src))
+        }
+        Endpoint::Remote { key, .. } if key.is_empty() || key.ends_with('/') => {
+            dst.child(&source_name(&src))
+        }
+        _ => dst,
+    };
+
+    transfer(ctx, &src, &dst, size, delete_source).await
+}
+
+fn source_name(src: &Endpoint) -> String {
+    match src {
+        Endpoint::Local(path) => path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        Endpoint::Remote { key, .. } => base_name(key).to_string(),
+    }
+}
+
+/// Copies (or moves) one file/object, choosing upload, download, server-side or stream copy
+async fn transfer(
+    ctx: &Context,
+    src: &Endpoint,
+    dst: &Endpoint,
+    size: u64,
+    delete_source: bool,
+) -> Result<()> {
+    let src_label = src.label();
+    let dst_label = dst.label();
+    let progress = ctx.progress(&src_label, size);
+
+    let verb = match (src, dst) {
+        (Endpoint::Local(path), Endpoint::Remote { manager, key, .. }) => {
+            manager
+                .upload_file_with_progress(path, key, progress)
+                .await?;
+            "upload"
+        }
+        (Endpoint::Remote { manager, key, .. }, Endpoint::Local(path)) => {
+            manager
+                .download_file_with_progress(key, path, progress)
+                .await?;
+            "download"
+        }
+        (
+            Endpoint::Remote {
+                manager: src_manager,
+                profile: src_profile,
+                key: src_key,
+            },
+            Endpoint::Remote {
+                manager: dst_manager,
+                profile: dst_profile,
+                key: dst_key,
+            },
+        ) => {
+            if src_profile.is_some()
+                && src_profile == dst_profile
+                && src_manager.bucket == dst_manager.bucket
+                && src_key == dst_key
+            {
+                anyhow::bail!("Cannot copy file to itself");
+            }
+
+            // Same profile: server-side copy first, stream copy as fallback (like the TUI queue)
+            let server_side = if src_profile.is_some() && src_profile == dst_profile {
+                dst_manager
+                    .copy_from_bucket_with_progress(
+                        &src_manager.bucket,
+                        src_key,
+                        dst_key,
+                        progress.clone(),
+                    )
+                    .await
+                    .is_ok()
+            } else {
+                false
+            };
+            if !server_side {
+                dst_manager
+                    .stream_copy_from_with_progress(src_manager, src_key, dst_key, progress)
+                    .await?;
+            }
+            "copy"
+        }
+        (Endpoint::Local(_), Endpoint::Local(_)) => {
+            anyhow::bail!("Local to local copies are not supported")
+        }
+    };
+
+    if delete_source {
+        delete(src).await?;
+        ctx.status(&format!("move: {src_label} -> {dst_label}"));
+    } else {
+        ctx.status(&format!("{verb}: {src_label} -> {dst_label}"));
+    }
+    Ok(())
+}
+
+async fn delete(endpoint: &Endpoint) -> Result<()> {
+    match endpoint {
+        Endpoint::Local(path) => tokio::fs::remove_file(path)
+            .await
+            .with_context(|| format!("Failed to delete {}", path.display())),
+        Endpoint::Remote { manager, key, .. } => manager.delete_object(key).await,
+    }
+}
+
+/// All files below a local directory or S3 prefix (relative paths with '/')
+async fn list_entries(endpoint: &Endpoint) -> Result<Vec<Entry>> {
+    match endpoint {
+        Endpoint::Local(root) => {
+            let root = root.clone();
+            tokio::task::spawn_blocking(move || walk_local(&root)).await?
+        }
+        Endpoint::Remote { manager, key, .. } => {
+            let prefix = dir_prefix(key);
+            let objects = manager.list_objects_recursive(&prefix).await?;
+            Ok(objects
+                .into_iter()
+                .filter(|obj| !obj.key.ends_with('/'))
+                .filter_map(|obj| {
+                    let rel = obj.key.strip_prefix(&prefix)?.to_string();
+                    Some(Entry {
+                        rel,
+                        size: obj.size.max(0) as u64,
+                        modified: obj.last_modified,
+                    })
+                })
+                .collect())
+        }
+    }
+}
+
+fn walk_local(root: &Path) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    if !root.exists() {
+        return Ok(entries);
+    }
+
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let read_dir =
+            std::fs::read_dir(&dir).with_context(|| format!("Cannot read {}", dir.display()))?;
+        for dir_entry in read_dir {
+            let path = dir_entry?.path();
+            let metadata = std::fs::metadata(&path)?;
+            if metadata.is_dir() {
+                dirs.push(path);
+            } else if metadata.is_file() {
+                let rel = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                entries.push(Entry {
+                    rel,
+                    size: metadata.len(),
+                    modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                });
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| a.rel.cmp(&b.rel));
+    Ok(entries)
+}
+
+pub async fn rm(ctx: &mut Context, opts: &Options) -> Result<()> {
+    if opts.positional.is_empty() {
+        return Err(usage_error("rm needs at least one S3 location"));
+    }
+
+    for arg in &opts.positional {
+        let location = ctx.location(arg)?;
+        if location.is_local() {
+            return Err(usage_error(format!("rm: '{arg}' is not an S3 location")));
+        }
+        let manager = ctx.connect(&location).await?;
+        let key = location.key();
+
+        if !opts.recursive {
+            if key.is_empty() || key.ends_with('/') {
+                return Err(usage_error(format!("{arg} is a prefix, use rm -r")));
+            }
+            manager.delete_object(key).await?;
+            ctx.status(&format!("delete: s3://{}/{key}", manager.bucket));
+            continue;
+        }
+
+        let prefix = dir_prefix(key);
+        for obj in manager.list_objects_recursive(&prefix).await? {
+            let label = format!("s3://{}/{}", manager.bucket, obj.key);
+            match manager.delete_object(&obj.key).await {
+                Ok(()) => ctx.status(&format!("delete: {label}")),
+                Err(e) => ctx.fail(&label, &e),
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn cat(ctx: &mut Context, opts: &Options) -> Result<()> {
+    if opts.positional.is_empty() {
+        return Err(usage_error("cat needs at least one S3 location"));
+    }
+
+    let mut stdout = tokio::io::stdout();
+    for arg in &opts.positional {
+        let location = ctx.location(arg)?;
+        if location.key().is_empty() {
+            return Err(usage_error(format!("cat: '{arg}' is not an S3 object")));
+        }
+        let manager = ctx.connect(&location).await?;
+        manager.write_object_to(location.key(), &mut stdout).await?;
+    }
+    Ok(())
+}
+
+pub async fn sync(ctx: &mut Context, opts: &Options) -> Result<()> {
+    let [src_arg, dst_arg] = opts.positional.as_slice() else {
+        return Err(usage_error("sync needs a source and a destination"));
+    };
+    let src_location = ctx.location(src_arg)?;
+    let dst_location = ctx.location(dst_arg)?;
+    if src_location.is_local() && dst_location.is_local() {
+        return Err(usage_error(
+            "sync: source or destination must be an S3 location",
+        ));
+    }
+    if let Location::Local(path) = &src_location {
+        if !path.is_dir() {
+            return Err(usage_error(format!("sync: {src_arg} is not a directory")));
+        }
+    }
+
+    let src = Endpoint::open(ctx, &src_location).await?;
+    let dst = Endpoint::open(ctx, &dst_location).await?;
+
+    let src_entries = list_entries(&src).await?;
+    let dst_entries: HashMap<String, Entry> = list_entries(&dst)
+        .await?
+        .into_iter()
+        .map(|e| (e.rel.clone(), e))
+        .collect();
+
+    // Copy if missing, different size or newer than the destination
+    for entry in &src_entries {
+        let needs_copy = match dst_entries.get(&entry.rel) {
+            None => true,
+            Some(existing) => {
+                existing.size != entry.size
+                    || matches!((entry.modified, existing.modified), (Some(s), Some(d)) if s.timestamp() > d.timestamp())
+            }
+        };
+        if !needs_copy {
+            continue;
+        }
+
+        let from = src.child(&entry.rel);
+        let to = dst.child(&entry.rel);
+        if opts.dry_run {
+            let verb = match (src.is_local(), dst.is_local()) {
+                (true, _) => "upload",
+                (_, true) => "download",
+                _ => "copy",
+            };
+            println!("(dryrun) {verb}: {} -> {}", from.label(), to.label());
+            continue;
+        }
+        if let Err(e) = transfer(ctx, &from, &to, entry.size, false).await {
+            ctx.fail(&from.label(), &e);
+        }
+    }
+
+    if opts.delete {
+        let src_names: std::collections::HashSet<&str> =
+            src_entries.iter().map(|e| e.rel.as_str()).collect();
+        let mut obsolete: Vec<&String> = dst_entries
+            .keys()
+            .filter(|rel| !src_names.contains(rel.as_str()))
+            .collect();
+        obsolete.sort();
+
+        for rel in obsolete {
+            let target = dst.child(rel);
+            if opts.dry_run {
+                println!("(dryrun) delete: {}", target.label());
+                continue;
+            }
+            match delete(&target).await {
+                Ok(()) => ctx.status(&format!("delete: {}", target.label())),
+                Err(e) => ctx.fail(&target.label(), &e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn presign(ctx: &mut Context, opts: &Options) -> Result<()> {
+    let [arg] = opts.positional.as_slice() else {
+        return Err(usage_error("presign needs exactly one S3 object"));
+    };
+    let location = ctx.location(arg)?;
+    if location.key().is_empty() || location.key().ends_with('/') {
+        return Err(usage_error(format!("presign: '{arg}' is not an S3 object")));
+    }
+
+    let manager = ctx.connect(&location).await?;
+    let expires = std::time::Duration::from_secs(opts.expires.unwrap_or(DEFAULT_PRESIGN_EXPIRY));
+    let url = manager.presign_get_object(location.key(), expires).await?;
+    println!("{url}");
+    Ok(())
+}
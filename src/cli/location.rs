@@ -0,0 +1,144 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Address of a file/directory on the command line.
+///
+/// - `profile:bucket/key` - bucket entry from the config (role chain, endpoint, region);
+///   only if `profile` is a configured profile
+/// - `s3://bucket/key` - public bucket, anonymous access
+/// - everything else - local path; `./name:rest` stays local even if `name` is a profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    Local(PathBuf),
+    S3 {
+        profile: String,
+        bucket: String,
+        key: String,
+    },
+    Public {
+        bucket: String,
+        key: String,
+    },
+}
+
+impl Location {
+    /// `is_profile` tells whether a name is a configured profile
+    pub fn parse(arg: &str, is_profile: impl Fn(&str) -> bool) -> Self {
+        if let Some(path) = arg.strip_prefix("s3://") {
+            let (bucket, key) = path.split_once('/').unwrap_or((path, ""));
+            return Location::Public {
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+            };
+        }
+
+        // Other names before ':' (C:\data, notes:2024.txt) are part of a local path
+        if let Some((profile, path)) = arg.split_once(':') {
+            if is_profile(profile) {
+                let (bucket, key) = path.split_once('/').unwrap_or((path, ""));
+                return Location::S3 {
+                    profile: profile.to_string(),
+                    bucket: bucket.to_string(),
+                    key: key.to_string(),
+                };
+            }
+        }
+
+        Location::Local(expand_home(arg))
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, Location::Local(_))
+    }
+
+    /// Key or prefix inside the bucket (empty for local paths)
+    pub fn key(&self) -> &str {
+        match self {
+            Location::Local(_) => "",
+            Location::S3 { key, .. } | Location::Public { key, .. } => key,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Local(path) => write!(f, "{}", path.display()),
+            Location::S3 {
+                profile,
+                bucket,
+                key,
+            } => write!(f, "{profile}:{bucket}/{key}"),
+            Location::Public { bucket, key } => write!(f, "s3://{bucket}/{key}"),
+        }
+    }
+}
+
+/// `~/data` -> `/home/user/data` (for quoted arguments the shell did not expand)
+pub fn expand_home(path: &str) -> PathBuf {
+    if path == "~" {
+        return dirs::home_dir().unwrap_or_else(|| PathBuf::from(path));
+    }
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(arg: &str) -> Location {
+        Location::parse(arg, |name| name == "dev")
+    }
+
+    #[test]
+    fn configured_profile_is_an_s3_location() {
+        assert_eq!(
+            parse("dev:bucket-one/logs/a.txt"),
+            Location::S3 {
+                profile: "dev".to_string(),
+                bucket: "bucket-one".to_string(),
+                key: "logs/a.txt".to_string(),
+            }
+        );
+        assert_eq!(
+            parse("dev:"),
+            Location::S3 {
+                profile: "dev".to_string(),
+                bucket: String::new(),
+                key: String::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn s3_url_is_a_public_location() {
+        assert_eq!(
+            parse("s3://bucket/key/x"),
+            Location::Public {
+                bucket: "bucket".to_string(),
+                key: "key/x".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn other_colons_are_local_paths() {
+        assert_eq!(
+            parse("notes:2024.txt"),
+            Location::Local(PathBuf::from("notes:2024.txt"))
+        );
+        assert_eq!(
+            parse("./dev:bucket-one"),
+            Location::Local(PathBuf::from("./dev:bucket-one"))
+        );
+        assert_eq!(
+            parse("C:\\data"),
+            Location::Local(PathBuf::from("C:\\data"))
+        );
+    }
+}
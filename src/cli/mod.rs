@@ -0,0 +1,429 @@
+//! Headless subcommands (`s3c ls`, `s3c cp`, ...) for scripts.
+//! Same ConfigManager bucket entries and S3Manager as the TUI; progress and
+//! messages go to stderr, stdout only carries the actual output.
+
+mod commands;
+mod location;
+mod start;
+
+pub use location::Location;
+pub use start::{resolve_start_location, StartArgs};
+
+use crate::models::config::ConfigManager;
+use crate::operations::s3::{ProgressCallback, S3Manager};
+use crate::operations::throttle::BandwidthLimits;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::io::{IsTerminal, Write};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub const EXIT_OK: i32 = 0;
+/// Operation failed (or some items of a recursive operation failed)
+pub const EXIT_FAILURE: i32 = 1;
+/// Invalid arguments
+pub const EXIT_USAGE: i32 = 2;
+/// Unknown profile/bucket, failed setup script
+pub const EXIT_CONFIG: i32 = 3;
+
+/// Short options that can be combined (`-rq`)
+const SHORT_OPTIONS: &[char] = &['r', 'q'];
+
+const SUBCOMMANDS: &[&str] = &["ls", "cp", "mv", "rm", "cat", "sync", "presign", "help"];
+
+const USAGE: &str = "\
+Usage: s3c [LEFT] [RIGHT]           start the TUI (optionally at the given locations)
+       s3c --left LOCATION --right LOCATION
+       s3c ls [-r] [LOCATION]       list objects (no location: configured buckets)
+       s3c cp [-r] SRC DST          copy (upload, download or S3 to S3)
+       s3c mv [-r] SRC DST          move
+       s3c rm [-r] LOCATION...      delete objects
+       s3c cat LOCATION...          write objects to stdout
+       s3c sync [--delete] [--dry-run] SRC DST
+                                    copy new and changed files
+       s3c presign [--expires SECS] LOCATION
+                                    print a presigned download URL
+
+Locations:
+  profile:bucket/key   bucket entry from the s3c config (profile must exist,
+                       use ./name:rest for a local file with ':' in its name)
+  s3://bucket/key      public bucket (anonymous access); when starting the TUI
+                       also s3://profile/bucket/key or a configured bucket
+  anything else        local path
+
+Options:
+  -r, --recursive      operate on all objects below a prefix/directory
+  -q, --quiet          no progress and status messages on stderr
+
+Exit codes: 0 ok, 1 operation failed, 2 usage error, 3 config error";
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    Config(String),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(msg) | CliError::Config(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+pub(crate) fn usage_error(msg: impl Into<String>) -> anyhow::Error {
+    CliError::Usage(msg.into()).into()
+}
+
+pub(crate) fn config_error(msg: impl Into<String>) -> anyhow::Error {
+    CliError::Config(msg.into()).into()
+}
+
+/// True if the arguments ask for a subcommand instead of the TUI
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    args.first().is_some_and(|first| {
+        SUBCOMMANDS.contains(&first.as_str())
+            || matches!(first.as_str(), "-h" | "--help" | "-V" | "--version")
+    })
+}
+
+/// Runs a subcommand and returns the process exit code
+pub async fn run(args: &[String]) -> i32 {
+    match dispatch(args).await {
+        Ok(code) => code,
+        Err(e) => report_error(&e),
+    }
+}
+
+/// Prints the error to stderr and returns the matching exit code
+pub fn report_error(e: &anyhow::Error) -> i32 {
+    match e.downcast_ref::<CliError>() {
+        Some(CliError::Usage(msg)) => {
+            eprintln!("s3c: {msg}");
+            eprintln!("Try 's3c help' for more information.");
+            EXIT_USAGE
+        }
+        Some(CliError::Config(msg)) => {
+            eprintln!("s3c: {msg}");
+            EXIT_CONFIG
+        }
+        None => {
+            eprintln!("s3c: {e:#}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+async fn dispatch(args: &[String]) -> Result<i32> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(usage_error("missing command"));
+    };
+
+    match command.as_str() {
+        "help" | "-h" | "--help" => {
+            println!("{USAGE}");
+            return Ok(EXIT_OK);
+        }
+        "-V" | "--version" => {
+            println!("s3c {}", env!("CARGO_PKG_VERSION"));
+            return Ok(EXIT_OK);
+        }
+        _ => {}
+    }
+
+    let opts = Options::parse(command, rest)?;
+    let mut ctx = Context::new(opts.quiet)?;
+
+    match command.as_str() {
+        "ls" => commands::ls(&mut ctx, &opts).await?,
+        "cp" => commands::cp(&mut ctx, &opts, false).await?,
+        "mv" => commands::cp(&mut ctx, &opts, true).await?,
+        "rm" => commands::rm(&mut ctx, &opts).await?,
+        "cat" => commands::cat(&mut ctx, &opts).await?,
+        "sync" => commands::sync(&mut ctx, &opts).await?,
+        "presign" => commands::presign(&mut ctx, &opts).await?,
+        other => return Err(usage_error(format!("unknown command '{other}'"))),
+    }
+
+    Ok(if ctx.failures > 0 {
+        EXIT_FAILURE
+    } else {
+        EXIT_OK
+    })
+}
+
+/// Parsed flags and positional arguments of a subcommand
+#[derive(Debug, Default)]
+pub struct Options {
+    pub positional: Vec<String>,
+    pub recursive: bool,
+    pub quiet: bool,
+    pub delete: bool,
+    pub dry_run: bool,
+    pub expires: Option<u64>,
+}
+
+impl Options {
+    fn parse(command: &str, args: &[String]) -> Result<Self> {
+        let mut opts = Options::default();
+        // "-rq" -> "-r" "-q" (only clusters of known short options, nothing after "--")
+        let mut expanded = Vec::new();
+        let mut rest = args.iter();
+        for arg in rest.by_ref() {
+            if arg == "--" {
+                expanded.push(arg.clone());
+                break;
+            }
+            match arg.strip_prefix('-') {
+                Some(cluster)
+                    if cluster.len() > 1 && cluster.chars().all(|c| SHORT_OPTIONS.contains(&c)) =>
+                {
+                    expanded.extend(cluster.chars().map(|c| format!("-{c}")));
+                }
+                _ => expanded.push(arg.clone()),
+            }
+        }
+        expanded.extend(rest.cloned());
+        let args = expanded;
+        let mut iter = args.iter();
+        let allows_recursive = matches!(command, "ls" | "cp" | "mv" | "rm");
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--" => {
+                    opts.positional.extend(iter.by_ref().cloned());
+                    break;
+                }
+                "-q" | "--quiet" => opts.quiet = true,
+                "-r" | "--recursive" if allows_recursive => opts.recursive = true,
+                "--delete" if command == "sync" => opts.delete = true,
+                "--dry-run" if command == "sync" => opts.dry_run = true,
+                "--expires" if command == "presign" => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| usage_error("--expires needs a value in seconds"))?;
+                    let secs = value.parse::<u64>().map_err(|_| {
+                        usage_error(format!("invalid value for --expires: '{value}'"))
+                    })?;
+                    opts.expires = Some(secs);
+                }
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(usage_error(format!(
+                        "unknown option '{flag}' for '{command}'"
+                    )));
+                }
+                _ => opts.positional.push(arg.clone()),
+            }
+        }
+
+        Ok(opts)
+    }
+}
+
+/// Shared state of a CLI run: config, setup scripts already executed, error count
+pub struct Context {
+    pub config_manager: ConfigManager,
+    pub quiet: bool,
+    pub failures: usize,
+    show_progress: bool,
+    setup_done: HashSet<String>,
+    bandwidth: BandwidthLimits,
+}
+
+impl Context {
+    fn new(quiet: bool) -> Result<Self> {
+        let config_manager = ConfigManager::new()?;
+        if !quiet {
+            for error in config_manager.load_errors() {
+                eprintln!("s3c: warning: {error}");
+            }
+        }
+
+        let bandwidth = BandwidthLimits::new(config_manager.app_config.settings.bandwidth_limit);
+
+        Ok(Self {
+            config_manager,
+            quiet,
+            failures: 0,
+            show_progress: !quiet && std::io::stderr().is_terminal(),
+            setup_done: HashSet::new(),
+            bandwidth,
+        })
+    }
+
+    /// Location of a command line argument (`profile:` only for configured profiles)
+    pub fn location(&self, arg: &str) -> Result<Location> {
+        let location = Location::parse(arg, |name| {
+            self.config_manager.get_profile_config(name).is_some()
+        });
+        // "name:..." without such a local file is most likely a misspelled profile
+        if let (Location::Local(path), Some((name, _))) = (&location, arg.split_once(':')) {
+            if name.len() > 1 && !name.contains(['/', '\\']) && !path.exists() {
+                return Err(config_error(format!(
+                    "unknown profile '{name}' (use ./{arg} for a local path)"
+                )));
+            }
+        }
+        Ok(location)
+    }
+
+    /// Creates the S3Manager for an S3 location (runs the profile's setup script once)
+    pub async fn connect(&mut self, location: &Location) -> Result<S3Manager> {
+        match location {
+            Location::Local(path) => Err(usage_error(format!(
+                "'{}' is not an S3 location",
+                path.display()
+            ))),
+            Location::S3 {
+                profile, bucket, ..
+            } => {
+                if bucket.is_empty() {
+                    return Err(usage_error(format!("'{location}': missing bucket")));
+                }
+                let bucket_config = self
+                    .config_manager
+                    .get_bucket_config(profile, bucket)
+                    .ok_or_else(|| {
+                        config_error(format!(
+                            "bucket '{bucket}' is not configured for profile '{profile}'"
+                        ))
+                    })?;
+                self.run_setup_script(profile)?;
+                let throttles =
+                    self.bandwidth
+                        .for_bucket(profile, bucket, bucket_config.bandwidth_limit);
+                Ok(S3Manager::from_bucket_config(profile, &bucket_config)
+                    .await?
+                    .with_throttles(throttles))
+            }
+            Location::Public { bucket, .. } => {
+                if bucket.is_empty() {
+                    return Err(usage_error(format!("'{location}': missing bucket")));
+                }
+                let manager = S3Manager::anonymous(bucket.clone(), "us-east-1", None, None)
+                    .await
+                    .with_throttles(vec![self.bandwidth.global().clone()]);
+                match manager.detect_region().await {
+                    Ok(region) if region != manager.region() => Ok(manager.with_region(&region)),
+                    _ => Ok(manager),
+                }
+            }
+        }
+    }
+
+    fn run_setup_script(&mut self, profile: &str) -> Result<()> {
+        if !self.setup_done.insert(profile.to_string()) {
+            return Ok(());
+        }
+        let Some(script) = self
+            .config_manager
+            .get_profile_config(profile)
+            .and_then(|p| p.setup_script.clone())
+            .filter(|s| !s.trim().is_empty())
+        else {
+            return Ok(());
+        };
+
+        if !self.quiet {
+            eprintln!("Running setup script: {script}");
+        }
+
+        // stdout of the script goes to stderr, stdout belongs to the command output
+        let status = crate::operations::run::setup_script_command(&script)
+            .stdout(std::io::stderr())
+            .status();
+
+        match status {
+            Ok(exit_status) if exit_status.success() => Ok(()),
+            Ok(_) => Err(config_error(format!(
+                "setup script for profile '{profile}' failed"
+            ))),
+            Err(e) => Err(config_error(format!(
+                "failed to execute setup script for profile '{profile}': {e}"
+            ))),
+        }
+    }
+
+    /// Progress line on stderr (only if stderr is a terminal)
+    pub fn progress(&self, label: &str, total: u64) -> Option<ProgressCallback> {
+        if !self.show_progress {
+            return None;
+        }
+        let label = label.to_string();
+        let mut last_percent = u64::MAX;
+        let callback: ProgressCallback = Arc::new(Mutex::new(move |done: u64| {
+            let percent = (done.min(total) * 100).checked_div(total).unwrap_or(100);
+            if percent != last_percent {
+                last_percent = percent;
+                let mut stderr = std::io::stderr();
+                let _ = write!(
+                    stderr,
+                    "\r\x1b[2K{label}  {} / {} ({percent}%)",
+                    crate::ui::helpers::format_size(done),
+                    crate::ui::helpers::format_size(total)
+                );
+                let _ = stderr.flush();
+            }
+        }));
+        Some(callback)
+    }
+
+    /// Status line for a finished item (replaces the progress line)
+    pub fn status(&self, line: &str) {
+        if self.show_progress {
+            eprint!("\r\x1b[2K");
+        }
+        if !self.quiet {
+            eprintln!("{line}");
+        }
+    }
+
+    /// Reports a failed item of a recursive operation and continues
+    pub fn fail(&mut self, label: &str, error: &anyhow::Error) {
+        if self.show_progress {
+            eprint!("\r\x1b[2K");
+        }
+        eprintln!("failed: {label}: {error:#}");
+        self.failures += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(command: &str, args: &[&str]) -> Result<Options> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        Options::parse(command, &args)
+    }
+
+    #[test]
+    fn combined_short_options_are_split() {
+        let opts = parse("rm", &["-rq", "dev:bucket/logs/"]).unwrap();
+        assert!(opts.recursive && opts.quiet);
+        assert_eq!(opts.positional, ["dev:bucket/logs/"]);
+    }
+
+    #[test]
+    fn arguments_after_double_dash_are_positional() {
+        let opts = parse("rm", &["-r", "--", "-rq", "--quiet"]).unwrap();
+        assert!(opts.recursive && !opts.quiet);
+        assert_eq!(opts.positional, ["-rq", "--quiet"]);
+    }
+
+    #[test]
+    fn unknown_short_options_are_rejected() {
+        let err = parse("cp", &["-rx", "a", "dev:bucket/"]).unwrap_err();
+        assert!(err.to_string().contains("'-rx'"));
+        assert!(parse("cat", &["-r", "dev:bucket/a"]).is_err());
+    }
+
+    #[test]
+    fn expires_takes_a_value() {
+        let opts = parse("presign", &["--expires", "600", "dev:bucket/a"]).unwrap();
+        assert_eq!(opts.expires, Some(600));
+        assert!(parse("presign", &["--expires", "soon", "dev:bucket/a"]).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use super::{config_error, usage_error, Location};
+use crate::models::config::ConfigManager;
+use anyhow::Result;
+
+/// Start locations of the TUI panels (`s3c [LEFT] [RIGHT]`, `--left`, `--right`)
+#[derive(Debug, Default)]
+pub struct StartArgs {
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl StartArgs {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut start = StartArgs::default();
+        let mut positional = Vec::new();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            match flag {
+                "--left" | "--right" => {
+                    let value = match inline_value {
+                        Some(value) => value,
+                        None => iter
+                            .next()
+                            .cloned()
+                            .ok_or_else(|| usage_error(format!("{flag} needs a location")))?,
+                    };
+                    if flag == "--left" {
+                        start.left = Some(value);
+                    } else {
+                        start.right = Some(value);
+                    }
+                }
+                "--" => positional.extend(iter.by_ref().cloned()),
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(usage_error(format!("unknown option '{flag}'")));
+                }
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        // Positional arguments fill the panels not given via --left/--right
+        for location in positional {
+            if start.left.is_none() {
+                start.left = Some(location);
+            } else if start.right.is_none() {
+                start.right = Some(location);
+            } else {
+                return Err(usage_error(format!("too many locations: '{location}'")));
+            }
+        }
+
+        Ok(start)
+    }
+}
+
+/// Resolves a start location against the config:
+/// `s3://profile/bucket/prefix` and `s3://bucket/prefix` of a configured bucket
+/// become config entries, unknown `s3://` buckets are opened anonymously.
+pub fn resolve_start_location(arg: &str, config: &ConfigManager) -> Result<Location> {
+    match Location::parse(arg, |name| config.get_profile_config(name).is_some()) {
+        Location::Public { bucket, key } => {
+            if bucket.is_empty() {
+                return Err(usage_error(format!("'{arg}': missing bucket")));
+            }
+
+            if config.get_profile_config(&bucket).is_some() {
+                let (profile_bucket, rest) = key.split_once('/').unwrap_or((&key, ""));
+                if config.get_bucket_config(&bucket, profile_bucket).is_some() {
+                    return Ok(Location::S3 {
+                        profile: bucket.clone(),
+                        bucket: profile_bucket.to_string(),
+                        key: rest.to_string(),
+                    });
+                }
+            }
+
+            let profiles: Vec<&str> = config
+                .app_config
+                .profiles
+                .iter()
+                .filter(|p| p.buckets.iter().any(|b| b.name == bucket))
+                .map(|p| p.name.as_str())
+                .collect();
+            match profiles.as_slice() {
+                [] => Ok(Location::Public { bucket, key }),
+                [profile] => Ok(Location::S3 {
+                    profile: profile.to_string(),
+                    bucket,
+                    key,
+                }),
+                _ => Err(usage_error(format!(
+                    "bucket '{bucket}' is configured in several profiles ({}), use profile:{bucket}/...",
+                    profiles.join(", ")
+                ))),
+            }
+        }
+        Location::S3 {
+            profile, bucket, ..
+        } if config.get_bucket_config(&profile, &bucket).is_none() => Err(config_error(format!(
+            "bucket '{bucket}' is not configured for profile '{profile}'"
+        ))),
+        location => Ok(location),
+    }
+}
@@ -16,19 +16,24 @@ pub fn key_to_message(app: &App, key: KeyCode, modifiers: KeyModifiers) -> Optio
     }
 
     match app.screen {
-        Screen::DualPanel => dual_panel_key_to_message(app, key),
+        Screen::DualPanel => dual_panel_key_to_message(app, key, modifiers),
         Screen::ConfigForm => config_form_key_to_message(app, key),
+        Screen::ConnectionTest => connection_test_key_to_message(key),
         Screen::ProfileConfigForm => profile_form_key_to_message(app, key),
         Screen::SortDialog => sort_dialog_key_to_message(key),
         Screen::DeleteConfirmation => delete_confirmation_key_to_message(key),
-        Screen::FileContentPreview => file_content_preview_key_to_message(key),
+        Screen::FileContentPreview => file_content_preview_key_to_message(app, key),
         Screen::ImagePreview => image_preview_key_to_message(key),
         Screen::Input => input_key_to_message(key, modifiers),
         Screen::Help => Some(Message::GoBack),
+        Screen::QueueDetails => queue_details_key_to_message(key),
+        Screen::TransferConflict => transfer_conflict_key_to_message(key),
+        Screen::ColumnPicker => column_picker_key_to_message(key),
+        Screen::ExternalEditUpload => external_upload_key_to_message(key),
     }
 }
 
-fn dual_panel_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
+fn dual_panel_key_to_message(app: &App, key: KeyCode, modifiers: KeyModifiers) -> Option<Message> {
     // Get active panel reference
     let active_panel = match app.active_panel {
         crate::app::ActivePanel::Left => &app.left_panel,
@@ -61,6 +66,12 @@ fn dual_panel_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
                 None
             }
         }
+        KeyCode::Char('p') | KeyCode::Char('P')
+            if app.background_transfer_task.is_some() || app.queue_focused =>
+        {
+            // Pause/resume the selected transfer (queue focused) or the running one
+            Some(Message::TogglePauseTransfer)
+        }
         KeyCode::Char('c') | KeyCode::Char('C') => {
             // Clear completed transfers
             if !app.file_operation_queue.is_empty() {
@@ -69,6 +80,48 @@ fn dual_panel_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
                 None
             }
         }
+        KeyCode::Char('r')
+            if app.queue_focused
+                && app
+                    .file_operation_queue
+                    .get(app.selected_queue_index)
+                    .is_some_and(|op| {
+                        matches!(
+                            op.status,
+                            crate::operations::OperationStatus::Failed(_)
+                                | crate::operations::OperationStatus::Cancelled
+                        )
+                    }) =>
+        {
+            // Retry the selected failed/cancelled transfer (only when queue focused)
+            Some(Message::RetryTransfer)
+        }
+        KeyCode::Char('R')
+            if app
+                .file_operation_queue
+                .iter()
+                .any(|op| matches!(op.status, crate::operations::OperationStatus::Failed(_))) =>
+        {
+            // Retry all failed transfers
+            Some(Message::RetryAllFailed)
+        }
+        KeyCode::Char('r')
+            if app
+                .file_operation_queue
+                .iter()
+                .any(|op| op.status == crate::operations::OperationStatus::Paused) =>
+        {
+            // Resume transfers restored from the last session
+            Some(Message::ResumeQueue)
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') if !app.file_operation_queue.is_empty() => {
+            // Expanded queue screen (only when queue visible)
+            Some(Message::ShowQueueDetails)
+        }
+        KeyCode::Char('b') | KeyCode::Char('B') if !app.file_operation_queue.is_empty() => {
+            // Change the bandwidth limit (only when queue visible)
+            Some(Message::ShowBandwidthPrompt)
+        }
         KeyCode::Char('q') | KeyCode::Char('Q') if !app.file_operation_queue.is_empty() => {
             // Toggle queue focus (only when queue visible)
             Some(Message::ToggleQueueFocus)
@@ -77,6 +130,20 @@ fn dual_panel_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
             // Delete selected from queue (only when queue focused)
             Some(Message::DeleteFromQueue)
         }
+        KeyCode::Up if app.queue_focused && modifiers.contains(KeyModifiers::SHIFT) => {
+            // Move selected waiting transfer up in the queue display
+            Some(Message::MoveQueueItemUp)
+        }
+        KeyCode::Down if app.queue_focused && modifiers.contains(KeyModifiers::SHIFT) => {
+            // Move selected waiting transfer down in the queue display
+            Some(Message::MoveQueueItemDown)
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') if app.queue_focused => {
+            // Start selected transfer next
+            Some(Message::RunQueueItemNext)
+        }
+        KeyCode::Char('+') if app.queue_focused => Some(Message::RaiseQueuePriority),
+        KeyCode::Char('-') if app.queue_focused => Some(Message::LowerQueuePriority),
         KeyCode::Up if app.queue_focused => {
             // Navigate up in queue (only when queue focused)
             Some(Message::QueueNavigateUp)
@@ -91,6 +158,8 @@ fn dual_panel_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
         }
         KeyCode::Char('q') => Some(Message::Quit),
         KeyCode::Char('?') => Some(Message::ShowHelp),
+        KeyCode::Char('o') | KeyCode::Char('O') => Some(Message::EditFile),
+        KeyCode::Char('v') | KeyCode::Char('V') => Some(Message::OpenInPager),
         KeyCode::F(12) => Some(Message::ToggleLocalFilesystem),
         KeyCode::Up => Some(Message::NavigateUp),
         KeyCode::Down => Some(Message::NavigateDown),
@@ -115,6 +184,38 @@ fn sort_dialog_key_to_message(key: KeyCode) -> Option<Message> {
     }
 }
 
+fn column_picker_key_to_message(key: KeyCode) -> Option<Message> {
+    match key {
+        KeyCode::Up => Some(Message::ColumnPickerUp),
+        KeyCode::Down => Some(Message::ColumnPickerDown),
+        KeyCode::Char(' ') => Some(Message::ToggleColumnHidden),
+        KeyCode::Enter => Some(Message::JumpToColumn),
+        KeyCode::Esc | KeyCode::Char('c') => Some(Message::GoBack),
+        _ => None,
+    }
+}
+
+fn transfer_conflict_key_to_message(key: KeyCode) -> Option<Message> {
+    match key {
+        KeyCode::Up => Some(Message::TransferConflictUp),
+        KeyCode::Down => Some(Message::TransferConflictDown),
+        KeyCode::Enter => Some(Message::ConfirmTransferConflict),
+        KeyCode::Esc => Some(Message::SkipTransferConflict),
+        _ => None,
+    }
+}
+
+fn external_upload_key_to_message(key: KeyCode) -> Option<Message> {
+    match key {
+        KeyCode::Left => Some(Message::ExternalUploadLeft),
+        KeyCode::Right => Some(Message::ExternalUploadRight),
+        KeyCode::Tab => Some(Message::ExternalUploadRight),
+        KeyCode::Enter => Some(Message::ConfirmExternalUpload),
+        KeyCode::Esc => Some(Message::CancelExternalUpload),
+        _ => None,
+    }
+}
+
 fn delete_confirmation_key_to_message(key: KeyCode) -> Option<Message> {
     match key {
         KeyCode::Left => Some(Message::DeleteConfirmationLeft),
@@ -160,15 +261,18 @@ fn config_form_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
         KeyCode::Home => Some(Message::ConfigFormHome),
         KeyCode::End => Some(Message::ConfigFormEnd),
         KeyCode::Delete => Some(Message::ConfigFormDelete),
+        KeyCode::F(5) => Some(Message::TestConnection),
         KeyCode::F(7) => Some(Message::ConfigFormAddRole),
         KeyCode::F(8) => Some(Message::ConfigFormRemoveRole),
         KeyCode::Char(c) => Some(Message::ConfigFormChar { c }),
         KeyCode::Backspace => Some(Message::ConfigFormBackspace),
         KeyCode::Enter => {
-            let button_field = app.config_form.roles.len() + 6; // Changed from 4 to 6
+            let button_field = app.config_form.button_field();
             if app.config_form.field == button_field {
                 Some(Message::SaveConfigForm)
             } else if app.config_form.field == button_field + 1 {
+                Some(Message::TestConnection)
+            } else if app.config_form.field == button_field + 2 {
                 Some(Message::GoBack)
             } else {
                 Some(Message::ConfigFormDown)
@@ -179,8 +283,32 @@ fn config_form_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
     }
 }
 
-fn file_content_preview_key_to_message(key: KeyCode) -> Option<Message> {
+fn connection_test_key_to_message(key: KeyCode) -> Option<Message> {
     match key {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => Some(Message::CloseConnectionTest),
+        KeyCode::F(5) | KeyCode::Char('r') => Some(Message::TestConnection),
+        _ => None,
+    }
+}
+
+fn file_content_preview_key_to_message(app: &App, key: KeyCode) -> Option<Message> {
+    // Während eine Suche nachlädt, nur Abbrechen zulassen
+    if app.preview_search_task.is_some() {
+        return (key == KeyCode::Esc).then_some(Message::CancelPreviewSearch);
+    }
+    match key {
+        KeyCode::Char('/') => Some(Message::ShowPreviewSearch { backward: false }),
+        KeyCode::Char('?') => Some(Message::ShowPreviewSearch { backward: true }),
+        KeyCode::Char('n') => Some(Message::PreviewSearchNext),
+        KeyCode::Char('N') => Some(Message::PreviewSearchPrevious),
+        KeyCode::F(4) | KeyCode::Char('h') => Some(Message::ToggleHexView),
+        KeyCode::Char('g') => Some(Message::ShowGotoOffsetPrompt),
+        KeyCode::Char('p') => Some(Message::TogglePrettyJson),
+        KeyCode::Char('t') => Some(Message::ToggleTableView),
+        KeyCode::Char('f') | KeyCode::Char('F') => Some(Message::ToggleFollowMode),
+        KeyCode::Char('c') => Some(Message::ShowColumnPicker),
+        KeyCode::Left => Some(Message::TableScrollLeft),
+        KeyCode::Right => Some(Message::TableScrollRight),
         KeyCode::Up => Some(Message::FilePreviewUp),
         KeyCode::Down => Some(Message::FilePreviewDown),
         KeyCode::PageUp => Some(Message::FilePreviewPageUp),
@@ -192,9 +320,31 @@ fn file_content_preview_key_to_message(key: KeyCode) -> Option<Message> {
     }
 }
 
+fn queue_details_key_to_message(key: KeyCode) -> Option<Message> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('e') => Some(Message::GoBack),
+        KeyCode::Up => Some(Message::QueueNavigateUp),
+        KeyCode::Down => Some(Message::QueueNavigateDown),
+        KeyCode::Char('r') => Some(Message::RetryTransfer),
+        KeyCode::Char('R') => Some(Message::RetryAllFailed),
+        _ => None,
+    }
+}
+
 fn image_preview_key_to_message(key: KeyCode) -> Option<Message> {
     match key {
         KeyCode::Esc | KeyCode::Char('q') => Some(Message::GoBack),
+        KeyCode::Enter => Some(Message::ConfirmImageLoad),
+        KeyCode::Char('+') | KeyCode::Char('=') => Some(Message::ImageZoomIn),
+        KeyCode::Char('-') => Some(Message::ImageZoomOut),
+        KeyCode::Char('0') => Some(Message::ImageZoomReset),
+        KeyCode::Left => Some(Message::ImagePan { dx: -1, dy: 0 }),
+        KeyCode::Right => Some(Message::ImagePan { dx: 1, dy: 0 }),
+        KeyCode::Up => Some(Message::ImagePan { dx: 0, dy: -1 }),
+        KeyCode::Down => Some(Message::ImagePan { dx: 0, dy: 1 }),
+        KeyCode::Char('i') => Some(Message::ToggleImageInfo),
+        KeyCode::Char('n') | KeyCode::Char(' ') | KeyCode::PageDown => Some(Message::NextImage),
+        KeyCode::Char('N') | KeyCode::Char('p') | KeyCode::PageUp => Some(Message::PreviousImage),
         _ => None,
     }
 }
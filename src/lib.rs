@@ -1,4 +1,5 @@
 pub mod app;
+pub mod cli;
 pub mod handlers;
 pub mod menu;
 pub mod message;
@@ -15,7 +16,7 @@ pub use handlers::key_to_message;
 pub use menu::{get_advanced_menu, get_menu_items, MenuItem};
 pub use message::Message;
 pub use operations::{
-    process_background_list_task, process_background_tasks, process_setup_script, run_app,
-    OperationStatus,
+    process_background_list_task, process_background_tasks, process_setup_script,
+    process_start_locations, run_app, OperationStatus,
 };
 pub use ui::draw;
@@ -7,6 +7,7 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 
 mod app;
+mod cli;
 mod handlers;
 mod menu;
 mod message;
@@ -19,13 +20,22 @@ use operations::run_app;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::is_cli_invocation(&args) {
+        std::process::exit(cli::run(&args).await);
+    }
+
+    let mut app = App::new()?;
+    if let Err(e) = app::navigation::prepare_start_locations(&mut app, &args) {
+        std::process::exit(cli::report_error(&e));
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, cursor::Hide)?; // Hide cursor for better Windows performance
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new()?;
     let res = run_app(&mut terminal, &mut app).await;
 
     disable_raw_mode()?;
@@ -36,5 +46,13 @@ async fn main() -> Result<()> {
         println!("Error: {err:?}");
     }
 
+    if let Err(err) = app.save_session() {
+        println!("Failed to save session: {err:?}");
+    }
+
+    if let Err(err) = app.save_queue() {
+        println!("Failed to save transfer queue: {err:?}");
+    }
+
     Ok(())
 }
@@ -78,7 +78,7 @@ pub fn get_menu_items(_app: &App, panel: &Panel) -> Vec<MenuItem> {
             MenuItem::static_item("04", "Filter", ShowFilterPrompt),
             MenuItem::empty("05"),
             MenuItem::empty("06"),
-            MenuItem::empty("07"),
+            MenuItem::static_item("07", "Open URL", ShowOpenUrlPrompt),
             MenuItem::empty("08"),
             MenuItem::static_item("09", "Advanced", ToggleAdvancedMode),
             MenuItem::static_item("10", "Quit", Quit),
@@ -102,7 +102,7 @@ pub fn get_menu_items(_app: &App, panel: &Panel) -> Vec<MenuItem> {
             MenuItem::static_item("04", "Filter", ShowFilterPrompt),
             MenuItem::empty("05"),
             MenuItem::empty("06"),
-            MenuItem::empty("07"),
+            MenuItem::static_item("07", "Open URL", ShowOpenUrlPrompt),
             MenuItem::empty("08"),
             MenuItem::static_item("09", "Advanced", ToggleAdvancedMode),
             MenuItem::static_item("10", "Quit", Quit),
@@ -131,6 +131,37 @@ pub fn get_menu_items(_app: &App, panel: &Panel) -> Vec<MenuItem> {
             MenuItem::static_item("09", "Advanced", ToggleAdvancedMode),
             MenuItem::static_item("10", "Quit", Quit),
         ],
+        PanelType::Archive { .. } => vec![
+            MenuItem::static_item("01", "Help", ShowHelp),
+            MenuItem::static_item("02", "Sort", ShowSortDialog),
+            MenuItem::static_item("03", "View", ViewFile),
+            MenuItem::static_item("04", "Filter", ShowFilterPrompt),
+            MenuItem::dynamic("05", get_extract_label, get_extract_action),
+            MenuItem::empty("06"),
+            MenuItem::empty("07"),
+            MenuItem::empty("08"),
+            MenuItem::static_item("09", "Advanced", ToggleAdvancedMode),
+            MenuItem::static_item("10", "Quit", Quit),
+        ],
+    }
+}
+
+/// Im Archiv entpackt F5 Dateien und ganze Ordner ins andere Panel
+fn get_extract_label(app: &App, panel: &Panel) -> &'static str {
+    if get_extract_action(app, panel).is_some() {
+        "Extract"
+    } else {
+        ""
+    }
+}
+
+fn get_extract_action(_app: &App, panel: &Panel) -> Option<Message> {
+    use crate::models::list::ItemType;
+
+    let item = panel.list_model.get_item(panel.selected_index)?;
+    match item.item_type {
+        ItemType::File | ItemType::Directory => Some(Message::CopyToOtherPanel),
+        ItemType::ParentDir => None,
     }
 }
 
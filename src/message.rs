@@ -30,6 +30,8 @@ pub enum Message {
     ShowProfileConfigForm,
     ShowCreateFolderPrompt,
     ShowRenamePrompt,
+    ShowOpenUrlPrompt,
+    ShowBandwidthPrompt,
     FilePreviewUp,
     FilePreviewDown,
     FilePreviewPageUp,
@@ -38,6 +40,35 @@ pub enum Message {
     FilePreviewEnd,
     LoadMoreFileContent,
     LoadPreviousFileContent,
+    ShowPreviewSearch {
+        backward: bool,
+    },
+    PreviewSearchNext,
+    PreviewSearchPrevious,
+    CancelPreviewSearch,
+    ToggleHexView,
+    ShowGotoOffsetPrompt,
+    TogglePrettyJson,
+    ToggleTableView,
+    ToggleFollowMode,
+    ConfirmImageLoad,
+    ImageZoomIn,
+    ImageZoomOut,
+    ImageZoomReset,
+    ImagePan {
+        dx: i32,
+        dy: i32,
+    },
+    ToggleImageInfo,
+    NextImage,
+    PreviousImage,
+    TableScrollLeft,
+    TableScrollRight,
+    ShowColumnPicker,
+    ColumnPickerUp,
+    ColumnPickerDown,
+    ToggleColumnHidden,
+    JumpToColumn,
     ToggleAdvancedMode,
 
     // ===== Sort Dialog =====
@@ -54,6 +85,16 @@ pub enum Message {
     CancelTransfer,
     ClearCompletedTransfers,
     DeleteFromQueue,
+    ResumeQueue,
+    TogglePauseTransfer,
+    MoveQueueItemUp,
+    MoveQueueItemDown,
+    RunQueueItemNext,
+    RaiseQueuePriority,
+    LowerQueuePriority,
+    ShowQueueDetails,
+    RetryTransfer,
+    RetryAllFailed,
     QueueNavigateUp,
     QueueNavigateDown,
     ToggleQueueFocus,
@@ -64,8 +105,18 @@ pub enum Message {
         name: String,
         is_dir: bool,
     },
+    TransferConflictUp,
+    TransferConflictDown,
+    ConfirmTransferConflict,
+    SkipTransferConflict,
     DeleteConfirmationLeft,
     DeleteConfirmationRight,
+    EditFile,
+    OpenInPager,
+    ExternalUploadLeft,
+    ExternalUploadRight,
+    ConfirmExternalUpload,
+    CancelExternalUpload,
     ConfirmDelete,
 
     // ===== Config Form =====
@@ -83,6 +134,8 @@ pub enum Message {
     ConfigFormAddRole,
     ConfigFormRemoveRole,
     SaveConfigForm,
+    TestConnection,
+    CloseConnectionTest,
     EditBucketConfig,
     DeleteBucketConfig,
 
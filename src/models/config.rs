@@ -1,7 +1,17 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Current schema version of the config file.
+/// Bump this and append a step to `MIGRATIONS` whenever the on-disk format changes.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Profile name under which ad-hoc opened public buckets are listed
+pub const ANONYMOUS_PROFILE: &str = "(anonymous)";
+
+/// Migration steps: `MIGRATIONS[n]` upgrades a config from version `n` to `n + 1`
+const MIGRATIONS: &[fn(&mut serde_json::Value) -> Result<()>] = &[migrate_v0_to_v1];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BucketConfig {
@@ -18,6 +28,15 @@ pub struct BucketConfig {
     pub endpoint_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub path_style: Option<bool>,
+    /// Public bucket: no credentials, unsigned requests
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub anonymous: Option<bool>,
+    /// Bandwidth limit for transfers of this bucket in bytes per second
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub bandwidth_limit: Option<u64>,
+    /// File this entry was loaded from (not serialized)
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
 }
 
 fn default_region() -> String {
@@ -34,15 +53,243 @@ pub struct ProfileConfig {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub settings: Settings,
+    #[serde(default)]
     pub profiles: Vec<ProfileConfig>,
 }
 
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            settings: Settings::default(),
+            profiles: Vec::new(),
+        }
+    }
+}
+
+/// General application settings (only read from the user config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Write a region detected from a redirect back into the config
+    pub persist_detected_region: bool,
+    /// Save panel locations, sort and filter on quit and restore them on start
+    pub restore_session: bool,
+    /// Automatic retries of a transfer after throttling, 5xx or network errors
+    pub transfer_retries: u32,
+    /// Bandwidth limit for all transfers together in bytes per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bandwidth_limit: Option<u64>,
+    /// Poll interval of the preview follow mode in seconds
+    pub follow_interval_secs: u64,
+    /// Images larger than this (in MB) are only loaded after confirmation, 0 disables the check
+    pub image_max_size_mb: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            persist_detected_region: false,
+            restore_session: true,
+            transfer_retries: 3,
+            bandwidth_limit: None,
+            follow_interval_secs: 2,
+            image_max_size_mb: 20,
+        }
+    }
+}
+
+impl BucketConfig {
+    pub fn is_anonymous(&self) -> bool {
+        self.anonymous == Some(true)
+    }
+
+    /// Short display form of the source file (home directory abbreviated as `~`)
+    pub fn source_label(&self) -> Option<String> {
+        let source = self.source.as_ref()?;
+        let label = match dirs::home_dir().and_then(|home| source.strip_prefix(home).ok()) {
+            Some(relative) => format!("~/{}", relative.display()),
+            None => source.display().to_string(),
+        };
+        Some(label)
+    }
+
+    /// Checks the bucket entry for values that would only fail later at connect time
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let name = &self.name;
+
+        if name.trim().is_empty() {
+            errors.push("Bucket name must not be empty".to_string());
+        } else if name.contains('/') || name.contains(char::is_whitespace) {
+            errors.push(format!(
+                "Bucket '{name}': name must not contain '/' or whitespace"
+            ));
+        }
+        if self.region.trim().is_empty() {
+            errors.push(format!("Bucket '{name}': region must not be empty"));
+        }
+        if let Some(endpoint) = &self.endpoint_url {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                errors.push(format!(
+                    "Bucket '{name}': endpoint_url must start with http:// or https://"
+                ));
+            }
+        }
+        if let Some(base_prefix) = &self.base_prefix {
+            if !base_prefix.is_empty() && !base_prefix.ends_with('/') {
+                errors.push(format!(
+                    "Bucket '{name}': base_prefix must end with '/' (e.g. \"{base_prefix}/\")"
+                ));
+            }
+        }
+        if self.is_anonymous() && !self.role_chain.is_empty() {
+            errors.push(format!(
+                "Bucket '{name}': anonymous access can't be combined with a role chain"
+            ));
+        }
+        for role in &self.role_chain {
+            if !role.starts_with("arn:") {
+                errors.push(format!("Bucket '{name}': invalid role ARN '{role}'"));
+            }
+        }
+
+        errors
+    }
+}
+
+impl AppConfig {
+    /// Returns all validation errors of the config (empty if valid)
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for (index, profile) in self.profiles.iter().enumerate() {
+            if profile.name.trim().is_empty() {
+                errors.push(format!("Profile #{} has an empty name", index + 1));
+            }
+            if self.profiles[..index]
+                .iter()
+                .any(|p| p.name == profile.name)
+            {
+                errors.push(format!("Profile '{}' is defined twice", profile.name));
+            }
+
+            for (bucket_index, bucket) in profile.buckets.iter().enumerate() {
+                if profile.buckets[..bucket_index]
+                    .iter()
+                    .any(|b| b.name == bucket.name)
+                {
+                    errors.push(format!(
+                        "Bucket '{}' is defined twice in profile '{}'",
+                        bucket.name, profile.name
+                    ));
+                }
+                errors.extend(
+                    bucket
+                        .validate()
+                        .into_iter()
+                        .map(|e| format!("Profile '{}': {e}", profile.name)),
+                );
+            }
+        }
+
+        errors
+    }
+}
+
+fn migrate_v0_to_v1(value: &mut serde_json::Value) -> Result<()> {
+    // v0 was the unversioned format; the layout is unchanged, only the version field is new
+    let object = value
+        .as_object_mut()
+        .context("Config root must be a JSON object")?;
+    object.insert("version".to_string(), serde_json::Value::from(1));
+    Ok(())
+}
+
+/// Supported config file formats, detected by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::Toml,
+            Some("yaml") | Some("yml") => Self::Yaml,
+            _ => Self::Json,
+        }
+    }
+
+    fn parse(self, content: &str) -> std::result::Result<serde_json::Value, String> {
+        let value = match self {
+            Self::Json => serde_json::from_str(content).map_err(|e| {
+                format!(
+                    "not valid JSON (line {}, column {}): {e}",
+                    e.line(),
+                    e.column()
+                )
+            })?,
+            Self::Toml => toml::from_str(content).map_err(|e| format!("not valid TOML: {e}"))?,
+            Self::Yaml => {
+                serde_yaml::from_str(content).map_err(|e| format!("not valid YAML: {e}"))?
+            }
+        };
+        // An empty YAML document is null, treat it like an empty config
+        Ok(match value {
+            serde_json::Value::Null => serde_json::Value::Object(Default::default()),
+            value => value,
+        })
+    }
+
+    fn serialize(self, config: &AppConfig) -> Result<String> {
+        Ok(match self {
+            Self::Json => serde_json::to_string_pretty(config)?,
+            Self::Toml => toml::to_string_pretty(config)?,
+            Self::Yaml => serde_yaml::to_string(config)?,
+        })
+    }
+}
+
+/// User config candidates in the config dir, first existing file wins
+const USER_CONFIG_FILES: &[&str] = &["config.toml", "config.yaml", "config.yml", "config.json"];
+
+/// Project config candidates, searched from the CWD upwards
+const PROJECT_CONFIG_FILES: &[&str] = &[".s3c.toml", ".s3c.yaml", ".s3c.yml", ".s3c.json"];
+
+/// Result of reading a config file from disk
+struct LoadedConfig {
+    config: AppConfig,
+    errors: Vec<String>,
+    read_only: bool,
+    migrated_from: Option<u32>,
+}
+
+/// Manages the layered configuration:
+/// the user config (`~/.config/s3c/config.{toml,yaml,json}`) is overlaid by an optional
+/// project config (`.s3c.toml` etc. in the CWD or a parent directory).
+/// Project entries take precedence; only the user layer is ever written.
 pub struct ConfigManager {
     config_path: PathBuf,
+    config_format: ConfigFormat,
+    user_config: AppConfig,
+    project_config_path: Option<PathBuf>,
+    project_config: Option<AppConfig>,
+    /// Session-only buckets (profile, bucket), not persisted
+    session_buckets: Vec<(String, BucketConfig)>,
+    /// Merged view of user and project config
     pub app_config: AppConfig,
     pub aws_profiles: Vec<String>,
+    load_errors: Vec<String>,
+    read_only: bool,
 }
 
 impl ConfigManager {
@@ -59,24 +306,282 @@ impl ConfigManager {
         };
 
         fs::create_dir_all(&config_dir)?;
-        let config_path = config_dir.join("config.json");
 
-        let app_config = if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            serde_json::from_str(&content).unwrap_or_default()
+        let existing: Vec<PathBuf> = USER_CONFIG_FILES
+            .iter()
+            .map(|name| config_dir.join(name))
+            .filter(|path| path.exists())
+            .collect();
+        let config_path = existing
+            .first()
+            .cloned()
+            .unwrap_or_else(|| config_dir.join("config.json"));
+
+        let mut load_errors = Vec::new();
+        if existing.len() > 1 {
+            let ignored: Vec<String> = existing[1..]
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            load_errors.push(format!(
+                "Using {}, ignoring {}",
+                config_path.display(),
+                ignored.join(", ")
+            ));
+        }
+
+        let loaded = if config_path.exists() {
+            Self::load_config(&config_path, true)?
         } else {
-            AppConfig::default()
+            LoadedConfig {
+                config: AppConfig::default(),
+                errors: Vec::new(),
+                read_only: false,
+                migrated_from: None,
+            }
+        };
+        load_errors.extend(loaded.errors);
+
+        let project_config_path = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| Self::find_project_config(&cwd))
+            .filter(|path| *path != config_path);
+        let project_config = match &project_config_path {
+            Some(path) => {
+                let project = Self::load_config(path, false)?;
+                load_errors.extend(project.errors);
+                let mut config = project.config;
+                // Never run commands from a checked-in file without the user opting in
+                for profile in &mut config.profiles {
+                    if profile.setup_script.take().is_some() {
+                        load_errors.push(format!(
+                            "{}: setup_script of profile '{}' ignored (only allowed in the user config)",
+                            path.display(),
+                            profile.name
+                        ));
+                    }
+                }
+                Some(config)
+            }
+            None => None,
         };
 
         let aws_profiles = Self::load_aws_profiles()?;
 
-        Ok(Self {
+        let mut manager = Self {
+            config_format: ConfigFormat::from_path(&config_path),
             config_path,
-            app_config,
+            user_config: loaded.config,
+            project_config_path,
+            project_config,
+            session_buckets: Vec::new(),
+            app_config: AppConfig::default(),
             aws_profiles,
+            load_errors,
+            read_only: loaded.read_only,
+        };
+        manager.rebuild();
+
+        // Persist migrated configs right away (old file is kept as versioned backup)
+        if let Some(old_version) = loaded.migrated_from {
+            let backup = manager.backup_path(&format!("v{old_version}.bak"));
+            if let Err(e) = fs::copy(&manager.config_path, &backup)
+                .map_err(anyhow::Error::from)
+                .and_then(|_| manager.save())
+            {
+                manager
+                    .load_errors
+                    .push(format!("Failed to save migrated config: {e}"));
+            }
+        }
+
+        Ok(manager)
+    }
+
+    fn find_project_config(start: &Path) -> Option<PathBuf> {
+        start.ancestors().find_map(|dir| {
+            PROJECT_CONFIG_FILES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
         })
     }
 
+    /// Reads, migrates and validates a config file.
+    /// A broken user config never results in silently empty profiles being written back:
+    /// it is copied to a timestamped backup before anything else happens.
+    fn load_config(config_path: &Path, is_user_config: bool) -> Result<LoadedConfig> {
+        let content = fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let file_name = config_path.display().to_string();
+
+        let mut loaded = LoadedConfig {
+            config: AppConfig::default(),
+            errors: Vec::new(),
+            read_only: false,
+            migrated_from: None,
+        };
+
+        let mut value = match ConfigFormat::from_path(config_path).parse(&content) {
+            Ok(value) => value,
+            Err(e) => {
+                loaded
+                    .errors
+                    .push(format!("{file_name} is {e}. Changes will not be saved."));
+                loaded.read_only = true;
+                if is_user_config {
+                    Self::backup_invalid_config(config_path, &mut loaded.errors);
+                }
+                return Ok(loaded);
+            }
+        };
+
+        let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+
+        if version > CONFIG_VERSION {
+            // Written by a newer s3c: use what we understand, but never overwrite it
+            loaded.errors.push(format!(
+                "{file_name} has schema version {version}, this s3c supports up to {CONFIG_VERSION}. Changes will not be saved."
+            ));
+            loaded.read_only = true;
+        } else if version < CONFIG_VERSION {
+            for migration in &MIGRATIONS[version as usize..] {
+                if let Err(e) = migration(&mut value) {
+                    loaded.errors.push(format!(
+                        "Failed to migrate {file_name} from version {version}: {e}. Changes will not be saved."
+                    ));
+                    loaded.read_only = true;
+                    if is_user_config {
+                        Self::backup_invalid_config(config_path, &mut loaded.errors);
+                    }
+                    return Ok(loaded);
+                }
+            }
+            loaded.migrated_from = Some(version);
+        }
+
+        match serde_json::from_value::<AppConfig>(value) {
+            Ok(mut config) => {
+                loaded.errors.extend(
+                    config
+                        .validate()
+                        .into_iter()
+                        .map(|e| format!("{file_name}: {e}")),
+                );
+                for bucket in config.profiles.iter_mut().flat_map(|p| &mut p.buckets) {
+                    bucket.source = Some(config_path.to_path_buf());
+                }
+                loaded.config = config;
+            }
+            Err(e) => {
+                loaded.errors.push(format!(
+                    "Invalid {file_name}: {e}. Changes will not be saved."
+                ));
+                loaded.migrated_from = None;
+                loaded.read_only = true;
+                if is_user_config {
+                    Self::backup_invalid_config(config_path, &mut loaded.errors);
+                }
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    fn backup_invalid_config(config_path: &Path, errors: &mut Vec<String>) {
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let mut backup = config_path.as_os_str().to_owned();
+        backup.push(format!(".invalid-{timestamp}.bak"));
+        let backup = PathBuf::from(backup);
+
+        match fs::copy(config_path, &backup) {
+            Ok(_) => errors.push(format!(
+                "The original file was backed up to {}",
+                backup.display()
+            )),
+            Err(e) => errors.push(format!("Failed to back up invalid config: {e}")),
+        }
+    }
+
+    fn backup_path(&self, suffix: &str) -> PathBuf {
+        let mut path = self.config_path.as_os_str().to_owned();
+        path.push(format!(".{suffix}"));
+        PathBuf::from(path)
+    }
+
+    /// Rebuilds the merged view: project profiles/buckets override user entries with the same name
+    fn rebuild(&mut self) {
+        let mut merged = self.user_config.clone();
+
+        if let Some(project) = &self.project_config {
+            for project_profile in &project.profiles {
+                match merged
+                    .profiles
+                    .iter_mut()
+                    .find(|p| p.name == project_profile.name)
+                {
+                    Some(profile) => {
+                        if project_profile.description.is_some() {
+                            profile.description = project_profile.description.clone();
+                        }
+                        for bucket in &project_profile.buckets {
+                            match profile.buckets.iter_mut().find(|b| b.name == bucket.name) {
+                                Some(existing) => *existing = bucket.clone(),
+                                None => profile.buckets.push(bucket.clone()),
+                            }
+                        }
+                    }
+                    None => merged.profiles.push(project_profile.clone()),
+                }
+            }
+        }
+
+        for (profile_name, bucket) in &self.session_buckets {
+            match merged.profiles.iter_mut().find(|p| &p.name == profile_name) {
+                Some(profile) => {
+                    if !profile.buckets.iter().any(|b| b.name == bucket.name) {
+                        profile.buckets.push(bucket.clone());
+                    }
+                }
+                None => merged.profiles.push(ProfileConfig {
+                    name: profile_name.clone(),
+                    buckets: vec![bucket.clone()],
+                    setup_script: None,
+                    description: None,
+                }),
+            }
+        }
+
+        self.app_config = merged;
+    }
+
+    /// Errors collected while loading the config (parse, migration and validation)
+    pub fn load_errors(&self) -> &[String] {
+        &self.load_errors
+    }
+
+    /// Path of the project config overlay, if one was found
+    pub fn project_config_path(&self) -> Option<&Path> {
+        self.project_config_path.as_deref()
+    }
+
+    /// Returns true if the bucket comes from the project config and can't be edited in s3c
+    pub fn is_project_bucket(&self, bucket: &BucketConfig) -> bool {
+        bucket.source.is_some() && bucket.source.as_deref() == self.project_config_path()
+    }
+
+    fn ensure_not_project_bucket(&self, profile_name: &str, bucket: &str) -> Result<()> {
+        if let Some(existing) = self.get_bucket_config(profile_name, bucket) {
+            if self.is_project_bucket(&existing) {
+                anyhow::bail!(
+                    "Bucket '{bucket}' is defined in {}, edit that file instead",
+                    existing.source_label().unwrap_or_default()
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn load_aws_profiles() -> Result<Vec<String>> {
         let home = dirs::home_dir().context("Could not find home directory")?;
         let credentials_path = home.join(".aws").join("credentials");
@@ -100,10 +605,34 @@ impl ConfigManager {
         Ok(profiles)
     }
 
+    /// Writes the user config atomically (temp file + rename), keeping the previous file as `.bak`.
+    /// A file that could not be loaded (invalid, or from a newer s3c) is never overwritten.
     pub fn save(&self) -> Result<()> {
-        let json = serde_json::to_string_pretty(&self.app_config)?;
-        fs::write(&self.config_path, json)?;
-        Ok(())
+        if self.read_only {
+            anyhow::bail!(
+                "{} could not be loaded completely and is not overwritten",
+                self.config_path.display()
+            );
+        }
+
+        let mut config = self.user_config.clone();
+        config.version = CONFIG_VERSION;
+        let content = self.config_format.serialize(&config)?;
+
+        if self.config_path.exists() {
+            fs::copy(&self.config_path, self.backup_path("bak"))
+                .context("Failed to back up config before saving")?;
+        }
+
+        write_atomic(&self.config_path, content.as_bytes())
+    }
+
+    /// Directory of the user config (also holds session and queue state)
+    pub fn config_dir(&self) -> PathBuf {
+        self.config_path
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_default()
     }
 
     pub fn get_profile_config(&self, profile_name: &str) -> Option<&ProfileConfig> {
@@ -113,72 +642,145 @@ impl ConfigManager {
             .find(|p| p.name == profile_name)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn add_bucket_to_profile(
+    fn user_profile_mut(&mut self, profile_name: &str) -> &mut ProfileConfig {
+        let index = match self
+            .user_config
+            .profiles
+            .iter()
+            .position(|p| p.name == profile_name)
+        {
+            Some(index) => index,
+            None => {
+                self.user_config.profiles.push(ProfileConfig {
+                    name: profile_name.to_string(),
+                    buckets: Vec::new(),
+                    setup_script: None,
+                    description: None,
+                });
+                self.user_config.profiles.len() - 1
+            }
+        };
+        &mut self.user_config.profiles[index]
+    }
+
+    /// Adds or replaces a bucket in the user config and saves it
+    pub fn save_bucket_config(
         &mut self,
         profile_name: &str,
-        bucket: String,
-        role_chain: Vec<String>,
-        region: String,
-        description: Option<String>,
-        base_prefix: Option<String>,
-        endpoint_url: Option<String>,
-        path_style: Option<bool>,
+        mut bucket_config: BucketConfig,
     ) -> Result<()> {
-        let bucket_config = BucketConfig {
-            name: bucket.clone(),
-            role_chain,
-            region,
-            description,
-            base_prefix,
-            endpoint_url,
-            path_style,
-        };
+        let errors = bucket_config.validate();
+        if !errors.is_empty() {
+            anyhow::bail!(errors.join("\n"));
+        }
+        self.ensure_not_project_bucket(profile_name, &bucket_config.name)?;
 
-        if let Some(profile) = self
-            .app_config
-            .profiles
+        bucket_config.source = Some(self.config_path.clone());
+        // A saved entry replaces an ad-hoc session bucket of the same name
+        self.session_buckets
+            .retain(|(p, b)| !(p == profile_name && b.name == bucket_config.name));
+
+        let profile = self.user_profile_mut(profile_name);
+        // Replace existing bucket or add new one
+        if let Some(existing) = profile
+            .buckets
             .iter_mut()
-            .find(|p| p.name == profile_name)
+            .find(|b| b.name == bucket_config.name)
         {
-            // Replace existing bucket or add new one
-            if let Some(existing) = profile.buckets.iter_mut().find(|b| b.name == bucket) {
-                *existing = bucket_config;
-            } else {
-                profile.buckets.push(bucket_config);
-            }
+            *existing = bucket_config;
         } else {
-            self.app_config.profiles.push(ProfileConfig {
-                name: profile_name.to_string(),
-                buckets: vec![bucket_config],
-                setup_script: None,
-                description: None,
-            });
+            profile.buckets.push(bucket_config);
         }
+        self.rebuild();
         self.save()?;
         Ok(())
     }
 
+    /// Registers a bucket for this session only (e.g. an ad-hoc opened public bucket).
+    /// It shows up in the merged view but is never written to disk.
+    pub fn add_session_bucket(&mut self, profile_name: &str, bucket_config: BucketConfig) {
+        if self
+            .get_bucket_config(profile_name, &bucket_config.name)
+            .is_some()
+        {
+            return;
+        }
+        self.session_buckets
+            .push((profile_name.to_string(), bucket_config));
+        self.rebuild();
+    }
+
     pub fn remove_bucket_from_profile(&mut self, profile_name: &str, bucket: &str) -> Result<()> {
+        self.ensure_not_project_bucket(profile_name, bucket)?;
+
         if let Some(profile) = self
-            .app_config
+            .user_config
             .profiles
             .iter_mut()
             .find(|p| p.name == profile_name)
         {
             profile.buckets.retain(|b| b.name != bucket);
         }
+        self.rebuild();
         self.save()?;
         Ok(())
     }
 
+    /// Corrects the region of a bucket (e.g. after a redirect).
+    /// The merged view is always updated for this session; the user config is only
+    /// rewritten if `settings.persist_detected_region` is set and the bucket is not
+    /// defined by the project config. Returns true if the change was saved.
+    pub fn set_bucket_region(
+        &mut self,
+        profile_name: &str,
+        bucket: &str,
+        region: &str,
+    ) -> Result<bool> {
+        let is_project = self
+            .get_bucket_config(profile_name, bucket)
+            .is_some_and(|b| self.is_project_bucket(&b));
+
+        if let Some(merged) = self
+            .app_config
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == profile_name)
+            .and_then(|p| p.buckets.iter_mut().find(|b| b.name == bucket))
+        {
+            merged.region = region.to_string();
+        }
+        for (_, session_bucket) in self
+            .session_buckets
+            .iter_mut()
+            .filter(|(p, b)| p == profile_name && b.name == bucket)
+        {
+            session_bucket.region = region.to_string();
+        }
+
+        if is_project || !self.app_config.settings.persist_detected_region {
+            return Ok(false);
+        }
+
+        let Some(user_bucket) = self
+            .user_config
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == profile_name)
+            .and_then(|p| p.buckets.iter_mut().find(|b| b.name == bucket))
+        else {
+            return Ok(false);
+        };
+        user_bucket.region = region.to_string();
+        self.save()?;
+        Ok(true)
+    }
+
     pub fn get_buckets_for_profile(&self, profile_name: &str) -> Vec<BucketConfig> {
         self.get_profile_config(profile_name)
             .map(|p| p.buckets.clone())
             .unwrap_or_default()
     }
 
-    #[allow(dead_code)]
     pub fn get_bucket_config(&self, profile_name: &str, bucket_name: &str) -> Option<BucketConfig> {
         self.get_profile_config(profile_name)
             .and_then(|p| p.buckets.iter().find(|b| b.name == bucket_name).cloned())
@@ -190,21 +792,44 @@ impl ConfigManager {
         profile_name: &str,
         script_path: Option<String>,
     ) -> Result<()> {
-        if let Some(profile) = self
-            .app_config
-            .profiles
-            .iter_mut()
-            .find(|p| p.name == profile_name)
-        {
-            profile.setup_script = script_path;
-        } else {
-            self.app_config.profiles.push(ProfileConfig {
-                name: profile_name.to_string(),
-                buckets: Vec::new(),
-                setup_script: script_path,
-                description: None,
-            });
+        self.user_profile_mut(profile_name).setup_script = script_path;
+        self.rebuild();
+        self.save()?;
+        Ok(())
+    }
+
+    pub fn set_profile_details(
+        &mut self,
+        profile_name: &str,
+        description: Option<String>,
+        setup_script: Option<String>,
+    ) -> Result<()> {
+        if profile_name.trim().is_empty() {
+            anyhow::bail!("Profile name must not be empty");
         }
+
+        // A description from the project config would shadow the user's change
+        let project_description = self
+            .project_config
+            .as_ref()
+            .and_then(|c| c.profiles.iter().find(|p| p.name == profile_name))
+            .and_then(|p| p.description.clone());
+        if project_description.is_some() && project_description != description {
+            anyhow::bail!(
+                "The description of profile '{profile_name}' is defined in {}",
+                self.project_config_path
+                    .as_deref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default()
+            );
+        }
+
+        let profile = self.user_profile_mut(profile_name);
+        if project_description.is_none() {
+            profile.description = description;
+        }
+        profile.setup_script = setup_script;
+        self.rebuild();
         self.save()?;
         Ok(())
     }
@@ -215,3 +840,124 @@ impl ConfigManager {
         Ok(())
     }
 }
+
+/// Writes via temp file + rename, so a crash never leaves a half-written file
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    {
+        let mut file = fs::File::create(&tmp_path)
+            .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+        file.write_all(content)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bucket(value: serde_json::Value) -> BucketConfig {
+        serde_json::from_value(value).unwrap()
+    }
+
+    /// Loads `content` from a temp file with the given extension
+    fn load(name: &str, content: &str) -> LoadedConfig {
+        let path = std::env::temp_dir().join(format!("s3c-test-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        let loaded = ConfigManager::load_config(&path, false).unwrap();
+        fs::remove_file(&path).unwrap();
+        loaded
+    }
+
+    #[test]
+    fn every_version_has_a_migration() {
+        assert_eq!(MIGRATIONS.len(), CONFIG_VERSION as usize);
+    }
+
+    #[test]
+    fn migration_from_v0_sets_the_version() {
+        let mut value = json!({"profiles": []});
+        migrate_v0_to_v1(&mut value).unwrap();
+        assert_eq!(value["version"], 1);
+        assert!(migrate_v0_to_v1(&mut json!([])).is_err());
+    }
+
+    #[test]
+    fn unversioned_config_is_migrated() {
+        let loaded = load(
+            "v0.json",
+            r#"{"profiles": [{"name": "dev", "buckets": [{"name": "logs"}]}]}"#,
+        );
+        assert_eq!(loaded.migrated_from, Some(0));
+        assert!(!loaded.read_only);
+        assert!(loaded.errors.is_empty());
+        assert_eq!(loaded.config.version, CONFIG_VERSION);
+        assert_eq!(loaded.config.profiles[0].buckets[0].region, "eu-west-1");
+    }
+
+    #[test]
+    fn broken_configs_are_read_only() {
+        for (name, content) in [
+            ("syntax.json", r#"{"profiles": ["#),
+            (
+                "schema.json",
+                r#"{"version": 1, "profiles": [{"buckets": 3}]}"#,
+            ),
+            ("newer.json", r#"{"version": 99, "profiles": []}"#),
+            ("syntax.toml", "profiles = ["),
+        ] {
+            let loaded = load(name, content);
+            assert!(loaded.read_only, "{name}");
+            assert!(
+                loaded.errors[0].contains("Changes will not be saved"),
+                "{name}: {:?}",
+                loaded.errors
+            );
+        }
+    }
+
+    #[test]
+    fn bucket_validation() {
+        assert!(bucket(json!({"name": "logs", "base_prefix": "app/"}))
+            .validate()
+            .is_empty());
+
+        let errors = bucket(json!({
+            "name": "my logs",
+            "region": " ",
+            "endpoint_url": "localhost:9000",
+            "base_prefix": "app",
+            "anonymous": true,
+            "role_chain": ["role/admin"],
+        }))
+        .validate();
+        assert_eq!(errors.len(), 6, "{errors:?}");
+    }
+
+    #[test]
+    fn duplicate_profiles_and_buckets_are_reported() {
+        let config: AppConfig = serde_json::from_value(json!({
+            "profiles": [
+                {"name": "dev", "buckets": [{"name": "a"}, {"name": "a"}]},
+                {"name": "dev", "buckets": []},
+                {"name": "", "buckets": []},
+            ]
+        }))
+        .unwrap();
+        let errors = config.validate();
+        assert_eq!(
+            errors,
+            [
+                "Bucket 'a' is defined twice in profile 'dev'",
+                "Profile 'dev' is defined twice",
+                "Profile #3 has an empty name",
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::models::config::BucketConfig;
 use crate::operations::s3::S3Object;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -32,9 +33,13 @@ pub enum ItemData {
         #[allow(dead_code)]
         is_dir: bool,
     },
+    ArchiveEntry {
+        path: String, // Pfad im Archiv
+    },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FilterCriteria {
     pub name_pattern: Option<String>,
     pub show_files: bool,
@@ -51,7 +56,7 @@ impl Default for FilterCriteria {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SortCriteria {
     NameAsc,
     NameDesc,
@@ -231,6 +236,10 @@ impl PanelListModel {
         self.rebuild_view();
     }
 
+    pub fn get_filter(&self) -> &FilterCriteria {
+        &self.filter
+    }
+
     pub fn get_current_sort(&self) -> SortCriteria {
         self.sort
     }
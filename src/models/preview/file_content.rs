@@ -1,5 +1,12 @@
+use super::pretty::PrettyText;
+use super::records::{RecordInfo, RecordSet};
+use super::table::TableData;
+use crate::operations::preview::decompress::{CompressedStream, Compression};
+use std::ops::Range;
+use std::time::Instant;
+
 /// Model für Text-basierte Datei-Vorschau (CSV, JSON, TXT, etc.)
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct FileContentPreview {
     pub filename: String,
     pub content: String,
@@ -12,6 +19,78 @@ pub struct FileContentPreview {
     pub content_start_offset: i64, // Byte offset where current content starts in file
     pub chunk_load_count: u32,     // Number of chunks loaded (incremented on each load)
     pub viewport_width: u16,       // Width of viewport for calculating visual line wraps
+    pub viewport_height: u16,      // Sichtbare Zeilen (für den Follow-Modus)
+    pub search: Option<PreviewSearch>,
+    pub view_mode: ViewMode,
+    pub hex: HexData,
+    pub pretty: Option<PrettyText>, // Formatiertes JSON ('p'), ersetzt content in der Anzeige
+    pub table: Option<TableData>,   // CSV/TSV: Tabellen-Ansicht ('t')
+    /// Komprimierte Datei: Offsets und file_size beziehen sich auf den dekomprimierten
+    /// Text; file_size ist erst am Ende bekannt (bis dahin eins mehr als geladen)
+    pub compressed: Option<CompressedStream>,
+    /// Parquet/Avro/JSON Lines: content enthält die ersten Datensätze als CSV
+    pub records: Option<RecordInfo>,
+    pub follow: Option<FollowState>,
+}
+
+/// Follow-Modus (`f`): neue Daten am Dateiende werden regelmäßig angehängt
+#[derive(Debug, Clone)]
+pub struct FollowState {
+    pub paused: bool, // Beim Zurückscrollen angehalten, End setzt fort
+    pub last_poll: Instant,
+}
+
+/// Darstellung der Vorschau (F4 wechselt)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewMode {
+    Text,
+    Hex,
+    Table,
+}
+
+/// Bytes pro Zeile der Hex-Ansicht
+pub const HEX_BYTES_PER_ROW: usize = 16;
+
+/// Geladener Bereich für die Hex-Ansicht (unabhängig vom Text-Inhalt)
+#[derive(Debug, Clone, Default)]
+pub struct HexData {
+    pub bytes: Vec<u8>,
+    pub start_offset: i64, // Datei-Offset von bytes[0] (Vielfaches von HEX_BYTES_PER_ROW)
+    pub scroll_row: usize,
+}
+
+impl HexData {
+    pub fn row_count(&self) -> usize {
+        self.bytes.len().div_ceil(HEX_BYTES_PER_ROW)
+    }
+
+    /// Datei-Offset hinter den geladenen Bytes
+    pub fn end_offset(&self) -> i64 {
+        self.start_offset + self.bytes.len() as i64
+    }
+
+    /// Datei-Offset der obersten sichtbaren Zeile
+    pub fn top_offset(&self) -> i64 {
+        self.start_offset + (self.scroll_row * HEX_BYTES_PER_ROW) as i64
+    }
+
+    pub fn contains(&self, offset: i64) -> bool {
+        offset >= self.start_offset && offset < self.end_offset()
+    }
+
+    /// Scrollt so, dass die Zeile mit `offset` oben steht (offset muss geladen sein)
+    pub fn scroll_to(&mut self, offset: i64) {
+        self.scroll_row = (offset - self.start_offset) as usize / HEX_BYTES_PER_ROW;
+    }
+}
+
+/// Aktive Suche (`/` vorwärts, `?` rückwärts)
+#[derive(Debug, Clone)]
+pub struct PreviewSearch {
+    pub pattern: String,
+    pub regex: regex::Regex,
+    pub backward: bool,                // Richtung von `n` (N = umgekehrt)
+    pub current: Option<Range<usize>>, // Byte-Bereich des aktuellen Treffers in text()
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,6 +114,8 @@ pub enum PreviewSource {
 impl FileContentPreview {
     pub fn new(filename: String, content: String, file_size: i64, source: PreviewSource) -> Self {
         let byte_offset = content.len() as i64;
+        let table = TableData::delimiter_for(Compression::strip_extension(&filename))
+            .map(|d| TableData::new(d, &content, true, byte_offset >= file_size));
         Self {
             filename,
             content,
@@ -46,6 +127,174 @@ impl FileContentPreview {
             content_start_offset: 0,
             chunk_load_count: 1, // Initial load counts as 1
             viewport_width: 80,  // Default, will be updated by UI
+            viewport_height: 20,
+            search: None,
+            view_mode: if table.is_some() {
+                ViewMode::Table
+            } else {
+                ViewMode::Text
+            },
+            hex: HexData::default(),
+            pretty: None,
+            table,
+            compressed: None,
+            records: None,
+            follow: None,
+        }
+    }
+
+    /// Vorschau einer komprimierten Datei mit dem ersten dekomprimierten Text
+    pub fn new_compressed(
+        filename: String,
+        content: String,
+        stream: CompressedStream,
+        source: PreviewSource,
+    ) -> Self {
+        let loaded = content.len() as i64;
+        let file_size = if stream.finished { loaded } else { loaded + 1 };
+        let mut preview = Self::new(filename, content, file_size, source);
+        preview.compressed = Some(stream);
+        preview
+    }
+
+    /// Strukturierte Vorschau: die gelesenen Datensätze als (vollständig geladene) Tabelle
+    pub fn new_records(filename: String, records: RecordSet, source: PreviewSource) -> Self {
+        let columns = records.columns();
+        let content = records.to_csv(&columns);
+        let size = content.len() as i64;
+        let mut preview = Self::new(filename, String::new(), 0, source);
+        preview.table = Some(TableData::with_header(',', columns, &content));
+        preview.view_mode = ViewMode::Table;
+        preview.content = content;
+        preview.byte_offset = size;
+        preview.file_size = size;
+        preview.records = Some(records.info);
+        preview
+    }
+
+    /// Dateiname ohne Kompressions-Endung (bestimmt Syntax und Tabellen-Ansicht)
+    pub fn content_name(&self) -> &str {
+        Compression::strip_extension(&self.filename)
+    }
+
+    /// Vorschau einer Binärdatei: startet in der Hex-Ansicht
+    pub fn new_hex(
+        filename: String,
+        bytes: Vec<u8>,
+        file_size: i64,
+        source: PreviewSource,
+    ) -> Self {
+        let mut preview = Self::new(filename, String::new(), file_size, source);
+        preview.view_mode = ViewMode::Hex;
+        preview.hex.bytes = bytes;
+        preview.table = None;
+        preview
+    }
+
+    /// Angezeigter Text: content oder dessen formatierte Fassung
+    pub fn text(&self) -> &str {
+        self.pretty.as_ref().map_or(&self.content, |p| &p.text)
+    }
+
+    /// Position in text() zu einem Byte-Offset in content
+    pub fn text_pos(&self, raw: usize) -> usize {
+        self.pretty.as_ref().map_or(raw, |p| p.to_text(raw))
+    }
+
+    /// Byte-Offset in text() am Anfang der obersten sichtbaren Zeile (Text oder Tabelle)
+    pub fn top_byte(&self) -> usize {
+        match (&self.table, self.view_mode) {
+            (Some(table), ViewMode::Table) => table.top_byte(),
+            _ => self.byte_at_visual_line(self.scroll_offset),
+        }
+    }
+
+    /// Datei-Offset der obersten sichtbaren Textzeile
+    pub fn text_top_offset(&self) -> i64 {
+        let top = self.top_byte();
+        let raw = self.pretty.as_ref().map_or(top, |p| p.to_raw(top));
+        self.content_start_offset + raw as i64
+    }
+
+    /// Schaltet die JSON-Formatierung um (Position bleibt erhalten)
+    pub fn toggle_pretty(&mut self) {
+        let top = self.text_top_offset() - self.content_start_offset;
+        self.pretty = match self.pretty {
+            Some(_) => None,
+            None => Some(PrettyText::format_json(&self.content)),
+        };
+        self.reset_match();
+        self.scroll_offset = self.visual_line_at_byte(self.text_pos(top as usize));
+    }
+
+    /// Wechselt zwischen Tabelle und Text (oberste Zeile bleibt erhalten)
+    pub fn toggle_table(&mut self) {
+        let top = self.top_byte();
+        self.scroll_offset = self.visual_line_at_byte(top);
+        match &mut self.table {
+            Some(_) if self.view_mode == ViewMode::Table => self.view_mode = ViewMode::Text,
+            Some(table) => {
+                table.scroll_row = table.row_at_byte(top);
+                self.view_mode = ViewMode::Table;
+            }
+            None => {}
+        }
+    }
+
+    /// Zeigt den Text (bzw. bei CSV/TSV die Tabelle) ab Byte `pos` von text()
+    pub fn show_text_at_byte(&mut self, pos: usize) {
+        self.scroll_offset = self.visual_line_at_byte(pos);
+        self.view_mode = match &mut self.table {
+            Some(table) => {
+                table.scroll_row = table.row_at_byte(pos);
+                ViewMode::Table
+            }
+            None => ViewMode::Text,
+        };
+    }
+
+    fn refresh_pretty(&mut self) {
+        if self.pretty.is_some() {
+            self.pretty = Some(PrettyText::format_json(&self.content));
+        }
+    }
+
+    /// Hängt nachgeladenen Text an; `end` ist der Datei-Offset dahinter
+    pub fn append_content(&mut self, text: &str, end: i64) {
+        self.content.push_str(text);
+        self.byte_offset = end;
+        self.refresh_pretty();
+        let complete = end >= self.file_size;
+        if let Some(table) = &mut self.table {
+            table.extend(&self.content, complete);
+        }
+    }
+
+    /// Stellt nachgeladenen Text ab Datei-Offset `start` voran. Gibt zurück, um wie viele
+    /// Bytes sich text() verschoben hat (der aktuelle Treffer wird mitverschoben).
+    pub fn prepend_content(&mut self, text: &str, start: i64) -> usize {
+        let old_len = self.text().len();
+        self.content.insert_str(0, text);
+        self.content_start_offset = start;
+        self.refresh_pretty();
+        let shift = self.text().len().saturating_sub(old_len);
+        self.shift_match(shift);
+        if let Some(table) = &mut self.table {
+            table.prepend(&self.content, text.len(), start == 0);
+        }
+        shift
+    }
+
+    /// Ersetzt den geladenen Text durch den Bereich `start..end` der Datei
+    pub fn replace_content(&mut self, text: String, start: i64, end: i64) {
+        self.content = text;
+        self.content_start_offset = start;
+        self.byte_offset = end;
+        self.reset_match();
+        self.refresh_pretty();
+        let complete = end >= self.file_size;
+        if let Some(table) = &mut self.table {
+            table.reset(&self.content, start == 0, complete);
         }
     }
 
@@ -59,13 +308,13 @@ impl FileContentPreview {
     /// Berechnet die Gesamtzahl der visuellen Zeilen (inkl. Umbrüche)
     pub fn calculate_visual_line_count(&self) -> usize {
         if self.viewport_width == 0 {
-            return self.content.lines().count();
+            return self.text().lines().count();
         }
 
         let width = self.viewport_width as usize;
         let mut visual_lines = 0;
 
-        for line in self.content.lines() {
+        for line in self.text().lines() {
             if line.is_empty() {
                 visual_lines += 1;
             } else {
@@ -78,31 +327,121 @@ impl FileContentPreview {
         visual_lines.max(1) // Mindestens 1 Zeile
     }
 
-    /// Gibt visuelle Zeilen zurück (content mit Umbrüchen basierend auf viewport_width)
-    pub fn get_visual_lines(&self) -> Vec<String> {
+    /// Zeilen von text() mit ihrem Byte-Offset
+    pub fn lines_with_offsets(&self) -> impl Iterator<Item = (usize, &str)> {
+        let base = self.text().as_ptr() as usize;
+        self.text()
+            .lines()
+            .map(move |line| (line.as_ptr() as usize - base, line))
+    }
+
+    /// Anzahl visueller Zeilen einer Zeile (mindestens 1)
+    pub fn wrapped_height(&self, line: &str) -> usize {
         if self.viewport_width == 0 {
-            return self.content.lines().map(|s| s.to_string()).collect();
+            return 1;
         }
+        line.chars()
+            .count()
+            .div_ceil(self.viewport_width as usize)
+            .max(1)
+    }
 
-        let width = self.viewport_width as usize;
-        let mut visual_lines = Vec::new();
+    /// Scrollt so, dass die letzte Seite sichtbar ist (Text und Tabelle)
+    pub fn scroll_to_last_page(&mut self) {
+        let page = self.viewport_height as usize;
+        self.scroll_offset = self.calculate_visual_line_count().saturating_sub(page);
+        if let Some(table) = &mut self.table {
+            // Beim Zeichnen auf die letzte volle Seite begrenzt
+            table.scroll_row = table.rows.len();
+        }
+    }
 
-        for line in self.content.lines() {
-            if line.is_empty() {
-                visual_lines.push(String::new());
-            } else {
-                // Zeile in chunks der Breite width aufteilen
-                let chars: Vec<char> = line.chars().collect();
-                for chunk in chars.chunks(width) {
-                    visual_lines.push(chunk.iter().collect());
+    /// Visuelle Zeile, in der das Byte `byte` von text() steht
+    pub fn visual_line_at_byte(&self, byte: usize) -> usize {
+        let mut visual = 0;
+        for (offset, line) in self.lines_with_offsets() {
+            if byte <= offset + line.len() {
+                let column = line[..byte.saturating_sub(offset)].chars().count();
+                let width = (self.viewport_width as usize).max(1);
+                let wrap = if self.viewport_width == 0 {
+                    0
+                } else {
+                    column / width
+                };
+                return visual + wrap.min(self.wrapped_height(line) - 1);
+            }
+            visual += self.wrapped_height(line);
+        }
+        visual.saturating_sub(1)
+    }
+
+    /// Byte-Offset des Anfangs der (logischen) Zeile, die in visueller Zeile `visual` steht
+    pub fn byte_at_visual_line(&self, visual: usize) -> usize {
+        let mut current = 0;
+        for (offset, line) in self.lines_with_offsets() {
+            current += self.wrapped_height(line);
+            if current > visual {
+                return offset;
+            }
+        }
+        self.text().len()
+    }
+
+    /// Nächster Treffer der Suche im geladenen Inhalt, ab dem aktuellen Treffer
+    /// (oder der obersten sichtbaren Zeile)
+    pub fn find_match(&self, backward: bool) -> Option<Range<usize>> {
+        let search = self.search.as_ref()?;
+        let top = self.top_byte();
+
+        if backward {
+            let end = search.current.as_ref().map_or(top, |m| m.start);
+            search
+                .regex
+                .find_iter(&self.text()[..end])
+                .last()
+                .map(|m| m.range())
+        } else {
+            let start = match &search.current {
+                // Leere Treffer (z.B. `^`) nicht endlos wiederfinden
+                Some(m) if m.is_empty() => {
+                    m.end
+                        + self.text()[m.end..]
+                            .chars()
+                            .next()
+                            .map_or(1, char::len_utf8)
                 }
+                Some(m) => m.end,
+                None => top,
+            };
+            if start > self.text().len() {
+                return None;
             }
+            search.regex.find_at(self.text(), start).map(|m| m.range())
+        }
+    }
+
+    /// Macht `range` zum aktuellen Treffer und scrollt dorthin
+    pub fn select_match(&mut self, range: Range<usize>) {
+        self.scroll_offset = self.visual_line_at_byte(range.start);
+        if let Some(table) = &mut self.table {
+            table.scroll_row = table.row_at_byte(range.start);
+        }
+        if let Some(search) = self.search.as_mut() {
+            search.current = Some(range);
         }
+    }
 
-        if visual_lines.is_empty() {
-            visual_lines.push(String::new());
+    /// Verschiebt den aktuellen Treffer, nachdem `len` Bytes vorangestellt wurden
+    pub fn shift_match(&mut self, len: usize) {
+        if let Some(current) = self.search.as_mut().and_then(|s| s.current.as_mut()) {
+            *current = current.start + len..current.end + len;
         }
+    }
 
-        visual_lines
+    /// Vergisst den aktuellen Treffer (Inhalt wurde ersetzt)
+    pub fn reset_match(&mut self) {
+        if let Some(search) = self.search.as_mut() {
+            search.current = None;
+        }
     }
 }
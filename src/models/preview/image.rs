@@ -1,26 +1,61 @@
 use super::PreviewSource;
+use image::DynamicImage;
+
+/// Zoomstufen (1.0 = ganzes Bild sichtbar)
+const ZOOM_LEVELS: [f64; 8] = [1.0, 1.5, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0];
+
+/// Anteil des sichtbaren Ausschnitts, um den ein Pfeiltasten-Schritt verschiebt
+const PAN_STEP: f64 = 0.2;
 
 /// Model für Bild-Vorschau
 #[derive(Debug, Clone)]
 pub struct ImagePreview {
     pub filename: String,
     pub source: PreviewSource,
-    pub image_data: Vec<u8>,
-    pub dimensions: Option<(u32, u32)>,
+    pub image: DynamicImage, // Dekodiert, Ausrichtung angewendet, für die Anzeige verkleinert
+    pub dimensions: (u32, u32), // Originalgröße
+    pub info: ImageInfo,
+    pub zoom_level: usize,
+    pub center: (f64, f64), // Mittelpunkt des Ausschnitts relativ zur Bildgröße (0..1)
+    pub show_info: bool,
+}
+
+/// Format- und Metadaten eines Bildes
+#[derive(Debug, Clone, Default)]
+pub struct ImageInfo {
+    pub format: String,
+    pub color: String,
+    pub bits_per_pixel: u16,
+    pub file_size: u64,
+    pub exif: Vec<(String, String)>,
+}
+
+/// Bild über dem Größenlimit, wartet auf Bestätigung vor dem Laden
+#[derive(Debug, Clone)]
+pub struct PendingImage {
+    pub filename: String,
+    pub source: PreviewSource,
+    pub size: u64,
+    pub limit: u64,
 }
 
 impl ImagePreview {
     pub fn new(
         filename: String,
         source: PreviewSource,
-        image_data: Vec<u8>,
-        dimensions: Option<(u32, u32)>,
+        image: DynamicImage,
+        dimensions: (u32, u32),
+        info: ImageInfo,
     ) -> Self {
         Self {
             filename,
             source,
-            image_data,
+            image,
             dimensions,
+            info,
+            zoom_level: 0,
+            center: (0.5, 0.5),
+            show_info: true,
         }
     }
 
@@ -30,4 +65,51 @@ impl ImagePreview {
             PreviewSource::S3Object { bucket, .. } => format!("S3: {bucket}"),
         }
     }
+
+    pub fn zoom(&self) -> f64 {
+        ZOOM_LEVELS[self.zoom_level]
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom_level = (self.zoom_level + 1).min(ZOOM_LEVELS.len() - 1);
+        self.clamp_center();
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom_level = self.zoom_level.saturating_sub(1);
+        self.clamp_center();
+    }
+
+    pub fn reset_zoom(&mut self) {
+        self.zoom_level = 0;
+        self.center = (0.5, 0.5);
+    }
+
+    /// Verschiebt den Ausschnitt um Schritte (negativ = nach links/oben)
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        let step = PAN_STEP / self.zoom();
+        self.center.0 += f64::from(dx) * step;
+        self.center.1 += f64::from(dy) * step;
+        self.clamp_center();
+    }
+
+    /// Hält den Ausschnitt innerhalb des Bildes
+    fn clamp_center(&mut self) {
+        let half = 0.5 / self.zoom();
+        self.center.0 = self.center.0.clamp(half, 1.0 - half);
+        self.center.1 = self.center.1.clamp(half, 1.0 - half);
+    }
+
+    /// Sichtbarer Ausschnitt des Anzeigebildes in Pixeln (x, y, Breite, Höhe)
+    pub fn visible_region(&self) -> (u32, u32, u32, u32) {
+        let (width, height) = (self.image.width(), self.image.height());
+        let zoom = self.zoom();
+        let w = ((f64::from(width) / zoom).round() as u32).clamp(1, width.max(1));
+        let h = ((f64::from(height) / zoom).round() as u32).clamp(1, height.max(1));
+        let x = (self.center.0 * f64::from(width) - f64::from(w) / 2.0).round() as i64;
+        let y = (self.center.1 * f64::from(height) - f64::from(h) / 2.0).round() as i64;
+        let x = x.clamp(0, i64::from(width - w)) as u32;
+        let y = y.clamp(0, i64::from(height - h)) as u32;
+        (x, y, w, h)
+    }
 }
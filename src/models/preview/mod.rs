@@ -1,5 +1,13 @@
 pub mod file_content;
 pub mod image;
+pub mod pretty;
+pub mod records;
+pub mod table;
 
-pub use file_content::{FileContentPreview, PreviewMode, PreviewSource};
-pub use image::ImagePreview;
+pub use file_content::{
+    FileContentPreview, FollowState, HexData, PreviewMode, PreviewSearch, PreviewSource, ViewMode,
+    HEX_BYTES_PER_ROW,
+};
+pub use image::{ImageInfo, ImagePreview, PendingImage};
+pub use records::{RecordInfo, RecordSet, RECORD_PREVIEW_ROWS};
+pub use table::{TableData, MAX_COLUMN_WIDTH};
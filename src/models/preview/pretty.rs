@@ -0,0 +1,102 @@
+/// Eingerückte Darstellung von (minifiziertem) JSON.
+///
+/// Formatiert zeichenweise ohne zu parsen, funktioniert also auch mit angeschnittenen
+/// Chunks und JSON Lines.
+#[derive(Debug, Clone, Default)]
+pub struct PrettyText {
+    pub text: String,
+    // (Offset im formatierten Text, Offset im Rohtext) je Zeilenanfang, aufsteigend
+    line_map: Vec<(usize, usize)>,
+}
+
+const INDENT: &str = "  ";
+
+impl PrettyText {
+    pub fn format_json(raw: &str) -> Self {
+        let mut out = PrettyText {
+            text: String::with_capacity(raw.len() * 3 / 2),
+            line_map: vec![(0, 0)],
+        };
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut chars = raw.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            let next_raw = i + c.len_utf8();
+            if in_string {
+                out.text.push(c);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    in_string = false;
+                }
+                continue;
+            }
+
+            match c {
+                '"' => {
+                    out.text.push(c);
+                    in_string = true;
+                }
+                '{' | '[' => {
+                    out.text.push(c);
+                    depth += 1;
+                    // Leere Objekte/Arrays bleiben `{}` / `[]`
+                    let closes = chars
+                        .clone()
+                        .find(|(_, n)| !n.is_whitespace())
+                        .is_some_and(|(_, n)| n == '}' || n == ']');
+                    if !closes {
+                        out.newline(depth, next_raw);
+                    }
+                }
+                '}' | ']' => {
+                    depth = depth.saturating_sub(1);
+                    if !out.text.ends_with(['{', '[']) {
+                        out.newline(depth, i);
+                    }
+                    out.text.push(c);
+                }
+                ',' => {
+                    out.text.push(c);
+                    out.newline(depth, next_raw);
+                }
+                ':' => out.text.push_str(": "),
+                // Zeilenumbruch zwischen Werten auf oberster Ebene (JSON Lines) bleibt erhalten
+                '\n' if depth == 0 => out.newline(0, next_raw),
+                c if c.is_whitespace() => {}
+                c => out.text.push(c),
+            }
+        }
+        out
+    }
+
+    fn newline(&mut self, depth: usize, raw: usize) {
+        self.text.push('\n');
+        for _ in 0..depth {
+            self.text.push_str(INDENT);
+        }
+        self.line_map.push((self.text.len(), raw));
+    }
+
+    /// Ungefähre Position im Rohtext zu einer Position im formatierten Text
+    pub fn to_raw(&self, pos: usize) -> usize {
+        let index = self.line_map.partition_point(|(text, _)| *text <= pos) - 1;
+        let (text, raw) = self.line_map[index];
+        raw + (pos - text)
+    }
+
+    /// Ungefähre Position im formatierten Text zu einer Position im Rohtext
+    pub fn to_text(&self, raw: usize) -> usize {
+        let index = self.line_map.partition_point(|(_, r)| *r <= raw) - 1;
+        let (text, line_raw) = self.line_map[index];
+        let mut pos = (text + (raw - line_raw)).min(self.text.len());
+        while !self.text.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        pos
+    }
+}
@@ -0,0 +1,85 @@
+use serde_json::Value;
+
+/// Anzahl Datensätze, die eine strukturierte Vorschau höchstens zeigt
+pub const RECORD_PREVIEW_ROWS: usize = 1000;
+
+/// Strukturierte Vorschau (Parquet, Avro, JSON Lines): Schema und Kennzahlen der Datei
+#[derive(Debug, Clone)]
+pub struct RecordInfo {
+    pub format: &'static str,
+    pub summary: String,
+    pub fields: Vec<(String, String)>, // Schema: Feldname und Typ
+    pub total_rows: Option<u64>,       // Unbekannt, wenn nur der Anfang gelesen wurde
+    pub file_size: i64,
+}
+
+impl RecordInfo {
+    /// Typ eines Feldes laut Schema
+    pub fn field_type(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, t)| t.as_str())
+    }
+}
+
+/// Gelesene Datensätze als (Feld, Wert)-Paare in Schema-Reihenfolge
+#[derive(Debug, Clone)]
+pub struct RecordSet {
+    pub info: RecordInfo,
+    pub rows: Vec<Vec<(String, Value)>>,
+}
+
+impl RecordSet {
+    /// Spalten: erst die Felder des Schemas, dann weitere Schlüssel in Fundreihenfolge
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns: Vec<String> = self.info.fields.iter().map(|(n, _)| n.clone()).collect();
+        for (name, _) in self.rows.iter().flatten() {
+            if !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+        columns
+    }
+
+    /// Datensätze als CSV (erste Zeile: Spaltennamen)
+    pub fn to_csv(&self, columns: &[String]) -> String {
+        let mut csv = csv_line(columns.iter().map(String::as_str));
+        for row in &self.rows {
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|c| {
+                    row.iter()
+                        .find(|(name, _)| name == c)
+                        .map_or(String::new(), |(_, v)| cell_text(v))
+                })
+                .collect();
+            csv.push_str(&csv_line(cells.iter().map(String::as_str)));
+        }
+        csv
+    }
+}
+
+/// Zellinhalt: Texte ohne Anführungszeichen, verschachtelte Werte als JSON
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_line<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let mut line = fields
+        .map(|f| {
+            if f.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
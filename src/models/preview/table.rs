@@ -0,0 +1,300 @@
+use std::ops::Range;
+
+/// Breiteste Darstellung einer Spalte in Zeichen (längere Werte werden gekürzt)
+pub const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Tabellen-Ansicht für CSV/TSV.
+///
+/// Die Datensätze werden nur als Byte-Bereiche im geladenen Inhalt gehalten und beim
+/// Nachladen eines Chunks inkrementell ergänzt; die Spaltenbreiten gelten für alles, was
+/// bisher geladen wurde.
+#[derive(Debug, Clone)]
+pub struct TableData {
+    pub delimiter: char,
+    pub header: Option<Vec<String>>, // Erkannte Kopfzeile (bleibt beim Springen erhalten)
+    pub rows: Vec<Range<usize>>,     // Datensätze in content, ohne Zeilenende
+    parsed_until: usize,             // Ab hier ist content noch nicht zerlegt
+    pub widths: Vec<usize>,
+    pub scroll_row: usize,
+    pub first_column: usize, // Erste sichtbare Spalte (← →)
+    pub hidden: Vec<bool>,   // Über den Spaltenwähler ausgeblendet
+    pub picker_selected: usize,
+}
+
+impl TableData {
+    /// Trennzeichen für die Tabellen-Ansicht nach Dateiendung
+    pub fn delimiter_for(filename: &str) -> Option<char> {
+        let lower = filename.to_lowercase();
+        match lower.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("csv") => Some(','),
+            Some("tsv") | Some("tab") => Some('\t'),
+            _ => None,
+        }
+    }
+
+    /// Zerlegt `content`; `at_file_start`: content beginnt am Dateianfang,
+    /// `complete`: content reicht bis zum Dateiende
+    pub fn new(delimiter: char, content: &str, at_file_start: bool, complete: bool) -> Self {
+        let mut table = Self::empty(delimiter);
+        table.reset(content, at_file_start, complete);
+        table
+    }
+
+    /// Vollständiger Inhalt mit bekannten Spaltennamen (erste Zeile von content)
+    pub fn with_header(delimiter: char, header: Vec<String>, content: &str) -> Self {
+        let mut table = Self::empty(delimiter);
+        table.update_widths(&header);
+        table.header = Some(header);
+        table.reset(content, true, true);
+        table
+    }
+
+    fn empty(delimiter: char) -> Self {
+        TableData {
+            delimiter,
+            header: None,
+            rows: Vec::new(),
+            parsed_until: 0,
+            widths: Vec::new(),
+            scroll_row: 0,
+            first_column: 0,
+            hidden: Vec::new(),
+            picker_selected: 0,
+        }
+    }
+
+    /// Neu zerlegen, nachdem content ersetzt wurde (Kopfzeile, Breiten und ausgeblendete
+    /// Spalten bleiben)
+    pub fn reset(&mut self, content: &str, at_file_start: bool, complete: bool) {
+        self.rows.clear();
+        self.scroll_row = 0;
+        self.parsed_until = self.first_record_start(content, at_file_start);
+        self.extend(content, complete);
+    }
+
+    /// Zerlegt neu angehängten Text. Ein unvollständiger letzter Datensatz wird erst mit
+    /// dem nächsten Chunk übernommen (außer am Dateiende).
+    pub fn extend(&mut self, content: &str, complete: bool) {
+        let limit = if complete { usize::MAX } else { content.len() };
+        let (rows, next) = self.parse_records(content, self.parsed_until, limit);
+        self.rows.extend(rows);
+        self.parsed_until = next;
+    }
+
+    /// Übernimmt `len` vorangestellte Bytes; die Scrollposition bleibt auf derselben Zeile
+    pub fn prepend(&mut self, content: &str, len: usize, at_file_start: bool) {
+        let old_first = self.rows.first().map_or(self.parsed_until, |r| r.start) + len;
+        for row in &mut self.rows {
+            *row = row.start + len..row.end + len;
+        }
+        self.parsed_until += len;
+
+        let from = self.first_record_start(content, at_file_start);
+        let (mut rows, _) = self.parse_records(content, from, old_first);
+        self.scroll_row += rows.len();
+        rows.append(&mut self.rows);
+        self.rows = rows;
+    }
+
+    /// Beginn des ersten Datensatzes: nach einer angeschnittenen Zeile bzw. nach der
+    /// Kopfzeile (die beim ersten Mal erkannt wird)
+    fn first_record_start(&mut self, content: &str, at_file_start: bool) -> usize {
+        if !at_file_start {
+            return content.find('\n').map_or(content.len(), |i| i + 1);
+        }
+        let Some(end) = record_end(content, 0) else {
+            return 0;
+        };
+        let first = split_fields(&content[..end], self.delimiter);
+        if self.header.is_none() && looks_like_header(&first) {
+            self.update_widths(&first);
+            self.header = Some(first);
+        }
+        if self.header.is_some() {
+            next_record(content, end)
+        } else {
+            0
+        }
+    }
+
+    /// Datensätze ab `from`, die vor `limit` enden. Gibt sie mit dem Offset dahinter zurück.
+    fn parse_records(
+        &mut self,
+        content: &str,
+        mut from: usize,
+        limit: usize,
+    ) -> (Vec<Range<usize>>, usize) {
+        let mut rows = Vec::new();
+        while from < content.len() && from < limit {
+            let end = match record_end(content, from) {
+                Some(end) => end,
+                None if limit > content.len() => content.len(),
+                None => break,
+            };
+            let fields = split_fields(&content[from..end], self.delimiter);
+            self.update_widths(&fields);
+            rows.push(from..end);
+            from = next_record(content, end);
+        }
+        (rows, from)
+    }
+
+    fn update_widths(&mut self, fields: &[String]) {
+        if self.widths.len() < fields.len() {
+            self.widths.resize(fields.len(), 1);
+            self.hidden.resize(fields.len(), false);
+        }
+        for (width, field) in self.widths.iter_mut().zip(fields) {
+            *width = (*width).max(field.chars().count().min(MAX_COLUMN_WIDTH));
+        }
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.widths.len()
+    }
+
+    /// Spaltenname: aus der Kopfzeile, sonst die Spaltennummer
+    pub fn column_name(&self, column: usize) -> String {
+        self.header
+            .as_ref()
+            .and_then(|h| h.get(column))
+            .cloned()
+            .unwrap_or_else(|| format!("#{}", column + 1))
+    }
+
+    /// Felder eines Datensatzes
+    pub fn fields(&self, content: &str, row: usize) -> Vec<String> {
+        self.rows
+            .get(row)
+            .map(|r| split_fields(&content[r.clone()], self.delimiter))
+            .unwrap_or_default()
+    }
+
+    /// Zeile, die das Byte `byte` von content enthält (oder die nächste danach)
+    pub fn row_at_byte(&self, byte: usize) -> usize {
+        self.rows
+            .partition_point(|r| r.end < byte)
+            .min(self.rows.len().saturating_sub(1))
+    }
+
+    /// Byte-Offset in content, an dem die oberste sichtbare Zeile beginnt
+    pub fn top_byte(&self) -> usize {
+        self.rows
+            .get(self.scroll_row)
+            .map_or(self.parsed_until, |r| r.start)
+    }
+}
+
+/// Ende des Datensatzes ab `from` (Zeilenumbruch außerhalb von Anführungszeichen, ohne
+/// `\r`), None wenn der Text vorher endet
+fn record_end(content: &str, from: usize) -> Option<usize> {
+    let mut in_quotes = false;
+    for (i, b) in content.as_bytes()[from..].iter().enumerate() {
+        match b {
+            b'"' => in_quotes = !in_quotes,
+            b'\n' if !in_quotes => {
+                let end = from + i;
+                return Some(if content[..end].ends_with('\r') {
+                    end - 1
+                } else {
+                    end
+                });
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Beginn des Datensatzes nach dem, der bei `end` endet
+fn next_record(content: &str, end: usize) -> usize {
+    let rest = &content[end..];
+    end + rest.find('\n').map_or(rest.len(), |i| i + 1)
+}
+
+/// Zerlegt einen Datensatz in Felder (`"` umschließt Felder, `""` ist ein Anführungszeichen)
+pub fn split_fields(record: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = record.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            // Zeilenumbrüche in Feldern einzeilig darstellen
+            '\n' | '\r' => field.push(' '),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Erste Zeile ist eine Kopfzeile, wenn alle Felder gefüllt, eindeutig und keine Zahlen sind
+fn looks_like_header(fields: &[String]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    fields.iter().all(|f| {
+        let f = f.trim();
+        !f.is_empty() && f.parse::<f64>().is_err() && seen.insert(f)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn quoted_fields_keep_delimiters_and_quotes() {
+        assert_eq!(
+            split_fields(r#"a,"b,c","say ""hi""",,"x"#, ','),
+            fields(&["a", "b,c", r#"say "hi""#, "", "x"])
+        );
+        assert_eq!(split_fields("a\tb c", '\t'), fields(&["a", "b c"]));
+        assert_eq!(split_fields("\"two\nlines\"", ','), fields(&["two lines"]));
+    }
+
+    #[test]
+    fn header_detection() {
+        assert!(looks_like_header(&fields(&["id", "name", "price"])));
+        assert!(!looks_like_header(&fields(&["1", "Ada", "9.5"])));
+        assert!(!looks_like_header(&fields(&["id", "", "price"])));
+        assert!(!looks_like_header(&fields(&["id", "id"])));
+    }
+
+    #[test]
+    fn incomplete_record_waits_for_the_next_chunk() {
+        let content = "id,note\r\n1,\"multi\nline\"\n2,open";
+        let mut table = TableData::new(',', content, true, false);
+        assert_eq!(table.header, Some(fields(&["id", "note"])));
+        assert_eq!(table.rows.len(), 1);
+        assert_eq!(table.fields(content, 0), fields(&["1", "multi line"]));
+
+        table.extend(content, true);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.fields(content, 1), fields(&["2", "open"]));
+        assert_eq!(table.widths, [2, 10]);
+    }
+
+    #[test]
+    fn prepend_keeps_the_visible_row() {
+        let tail = "3,c\n4,d\n";
+        let mut table = TableData::new(',', tail, false, true);
+        // Ohne Dateianfang gilt die erste (ggf. angeschnittene) Zeile nicht
+        assert_eq!(table.rows.len(), 1);
+
+        let content = format!("1,a\n2,b\n{tail}");
+        table.prepend(&content, 8, true);
+        assert_eq!(table.rows.len(), 4);
+        assert_eq!(table.scroll_row, 3);
+        assert_eq!(table.fields(&content, 3), fields(&["4", "d"]));
+    }
+}
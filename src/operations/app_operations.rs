@@ -1,5 +1,5 @@
 use crate::app::{App, PanelType};
-use crate::models::list::{ItemData, ItemType};
+use crate::models::list::{ItemData, ItemType, PanelItem};
 use anyhow::Result;
 
 /// Extract S3 key from path by removing s3://bucket/ prefix
@@ -171,6 +171,46 @@ pub async fn view_file(app: &mut App) -> Result<()> {
                 app.show_info("No item selected");
             }
         }
+        PanelType::Archive { .. } => {
+            let item = app.get_active_panel().list_model.get_item(selected_index);
+
+            match item {
+                Some(PanelItem {
+                    item_type: ItemType::File,
+                    data: ItemData::ArchiveEntry { path },
+                    name,
+                    ..
+                }) => {
+                    let path = path.clone();
+                    let filename = name.clone();
+                    let Some(index) = &app.get_active_panel().archive else {
+                        return Ok(());
+                    };
+
+                    // Vorschau arbeitet auf einer entpackten Kopie im temporären Verzeichnis
+                    match index.extract_for_preview(&path).await {
+                        Ok(file) => {
+                            let source = PreviewSource::LocalFile {
+                                path: file.display().to_string(),
+                            };
+                            if is_image_file(&filename) {
+                                show_image_preview(app, source).await?;
+                            } else {
+                                show_file_content_preview(app, source).await?;
+                            }
+                        }
+                        Err(e) => app.show_error(&format!("Cannot extract '{filename}': {e}")),
+                    }
+                }
+                Some(PanelItem {
+                    item_type: ItemType::Directory,
+                    ..
+                }) => {
+                    app.show_info("Cannot preview directories");
+                }
+                _ => {}
+            }
+        }
         _ => {
             app.show_error("Preview only available for files");
         }
@@ -0,0 +1,585 @@
+//! Archive als virtuelles Verzeichnis (zip, tar, tar.gz).
+//!
+//! Zip wird über Range-Reads gelesen: erst das Ende (End of Central Directory), dann das
+//! Central Directory und beim Entpacken nur die Daten des Eintrags - auf S3 wird das Archiv
+//! also nicht komplett geladen. Tar hat kein Inhaltsverzeichnis und wird der Reihe nach
+//! gelesen; liegt es auf S3, wird es dafür einmal in ein temporäres Verzeichnis geladen.
+
+use crate::operations::preview::file_loader::load_local_file_bytes;
+use crate::operations::s3::S3Manager;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Gelesen wird in Stücken dieser Größe (Central Directory, Eintragsdaten)
+const READ_SIZE: u64 = 8 * 1024 * 1024;
+
+/// End of Central Directory: 22 Bytes plus bis zu 64 KB Kommentar
+const EOCD_SEARCH: u64 = 22 + 65535;
+
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let lower = filename.to_lowercase();
+        if lower.ends_with(".zip") || lower.ends_with(".jar") {
+            Some(ArchiveFormat::Zip)
+        } else if lower.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Wo die Archiv-Bytes liegen
+pub enum ArchiveData {
+    Local(PathBuf),
+    S3 {
+        manager: Box<S3Manager>,
+        key: String,
+    },
+}
+
+impl ArchiveData {
+    async fn read_range(&self, start: u64, len: u64) -> Result<Vec<u8>> {
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        match self {
+            ArchiveData::Local(path) => {
+                load_local_file_bytes(&path.display().to_string(), start as i64, len as i64).await
+            }
+            ArchiveData::S3 { manager, key } => {
+                manager
+                    .get_object_range(key, start as i64, (start + len - 1) as i64)
+                    .await
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    pub path: String, // Pfad im Archiv, ohne abschließenden '/'
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+    zip: Option<ZipLocation>,
+}
+
+/// Wo die (komprimierten) Daten eines Zip-Eintrags liegen
+#[derive(Debug, Clone, Copy)]
+struct ZipLocation {
+    header_offset: u64,
+    compressed_size: u64,
+    method: u16,
+    encrypted: bool,
+}
+
+/// Inhaltsverzeichnis eines geöffneten Archivs.
+/// Das temporäre Verzeichnis (Vorschau, Tar von S3) wird beim Schließen gelöscht.
+pub struct ArchiveIndex {
+    pub format: ArchiveFormat,
+    pub entries: Vec<ArchiveEntry>,
+    data: ArchiveData,
+    temp_dir: PathBuf,
+}
+
+impl Drop for ArchiveIndex {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.temp_dir);
+    }
+}
+
+impl ArchiveIndex {
+    /// Liest das Inhaltsverzeichnis
+    pub async fn open(format: ArchiveFormat, data: ArchiveData) -> Result<Self> {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "s3c-archive-{}-{}",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&temp_dir).context("Failed to create temp directory")?;
+        let mut index = ArchiveIndex {
+            format,
+            entries: Vec::new(),
+            data,
+            temp_dir,
+        };
+
+        match format {
+            ArchiveFormat::Zip => index.entries = read_zip_directory(&index.data).await?,
+            ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+                if let ArchiveData::S3 { manager, key } = &index.data {
+                    let path = index.temp_dir.join("archive");
+                    manager.download_file(key, &path).await?;
+                    index.data = ArchiveData::Local(path);
+                }
+                let ArchiveData::Local(path) = &index.data else {
+                    unreachable!()
+                };
+                index.entries = read_tar_entries(format, path)?;
+            }
+        }
+        Ok(index)
+    }
+
+    /// Anzahl der Dateien, die `extract(path, ..)` schreiben würde
+    pub fn file_count(&self, path: &str) -> usize {
+        let dir_prefix = format!("{path}/");
+        self.entries
+            .iter()
+            .filter(|e| !e.is_dir && (e.path == path || e.path.starts_with(&dir_prefix)))
+            .count()
+    }
+
+    /// Neues, leeres Verzeichnis im temporären Verzeichnis des Archivs
+    pub fn staging_dir(&self) -> PathBuf {
+        let n = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        self.temp_dir.join(format!("extract-{n}"))
+    }
+
+    /// Entpackt eine Datei für die Vorschau ins temporäre Verzeichnis
+    pub async fn extract_for_preview(&self, path: &str) -> Result<PathBuf> {
+        let mut files = self.extract(path, &self.staging_dir()).await?;
+        files.pop().context("Entry not found in archive")
+    }
+
+    /// Entpackt den Eintrag `path` (bei Ordnern alles darunter) nach `dest`. Die Pfade
+    /// unterhalb des Ordners, in dem `path` liegt, bleiben erhalten; vorhandene Dateien
+    /// werden nicht überschrieben. Gibt die geschriebenen Dateien zurück.
+    pub async fn extract(&self, path: &str, dest: &Path) -> Result<Vec<PathBuf>> {
+        let parent_len = path.rfind('/').map_or(0, |i| i + 1);
+        let dir_prefix = format!("{path}/");
+        let target = |entry_path: &str| -> Option<PathBuf> {
+            if entry_path != path && !entry_path.starts_with(&dir_prefix) {
+                return None;
+            }
+            Some(dest.join(&entry_path[parent_len..]))
+        };
+
+        let mut written = Vec::new();
+        match self.format {
+            ArchiveFormat::Zip => {
+                for entry in &self.entries {
+                    let Some(target) = target(&entry.path) else {
+                        continue;
+                    };
+                    if entry.is_dir {
+                        std::fs::create_dir_all(&target)?;
+                        continue;
+                    }
+                    if let Some(file) = create_new_file(&target)? {
+                        let result = self.extract_zip_entry(entry, file).await;
+                        if result.is_err() {
+                            let _ = std::fs::remove_file(&target);
+                        }
+                        result.with_context(|| format!("Failed to extract {}", entry.path))?;
+                        written.push(target);
+                    }
+                }
+            }
+            ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+                let ArchiveData::Local(archive_path) = &self.data else {
+                    bail!("Tar archive was not downloaded");
+                };
+                let mut archive = tar_archive(self.format, archive_path)?;
+                for entry in archive.entries()? {
+                    let mut entry = entry?;
+                    let Some(entry_path) = entry_path(&entry.path_bytes()) else {
+                        continue;
+                    };
+                    let Some(target) = target(&entry_path) else {
+                        continue;
+                    };
+                    let kind = entry.header().entry_type();
+                    if kind.is_dir() {
+                        std::fs::create_dir_all(&target)?;
+                    } else if kind.is_file() {
+                        if let Some(mut file) = create_new_file(&target)? {
+                            std::io::copy(&mut entry, &mut file)
+                                .with_context(|| format!("Failed to extract {entry_path}"))?;
+                            written.push(target);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(written)
+    }
+
+    /// Liest die Daten eines Zip-Eintrags stückweise und schreibt sie entpackt in `file`
+    async fn extract_zip_entry(&self, entry: &ArchiveEntry, file: std::fs::File) -> Result<()> {
+        let Some(zip) = entry.zip else {
+            bail!("Not a zip entry");
+        };
+        if zip.encrypted {
+            bail!("Encrypted entries are not supported");
+        }
+        let mut writer: Box<dyn Write> = match zip.method {
+            0 => Box::new(file),
+            8 => Box::new(flate2::write::DeflateDecoder::new(file)),
+            method => bail!("Unsupported compression method {method}"),
+        };
+
+        // Local Header: Name und Extra-Feld können vom Central Directory abweichen
+        let header = self.data.read_range(zip.header_offset, 30).await?;
+        if header.len() < 30 || le_u32(&header, 0) != 0x04034b50 {
+            bail!("Invalid local file header");
+        }
+        let start =
+            zip.header_offset + 30 + le_u16(&header, 26) as u64 + le_u16(&header, 28) as u64;
+
+        let mut offset = 0;
+        while offset < zip.compressed_size {
+            let len = READ_SIZE.min(zip.compressed_size - offset);
+            let bytes = self.data.read_range(start + offset, len).await?;
+            if bytes.is_empty() {
+                bail!("Archive is truncated");
+            }
+            writer.write_all(&bytes)?;
+            offset += bytes.len() as u64;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Legt die Datei samt Ordnern an; None wenn sie schon existiert
+fn create_new_file(path: &Path) -> Result<Option<std::fs::File>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match std::fs::File::create_new(path) {
+        Ok(file) => Ok(Some(file)),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Cannot create {}", path.display())),
+    }
+}
+
+/// Pfad eines Eintrags ohne führendes `/` und `./`; Einträge mit `..` werden
+/// ignoriert, damit beim Entpacken nichts außerhalb des Ziels landet
+fn entry_path(raw: &[u8]) -> Option<String> {
+    let path = String::from_utf8_lossy(raw).replace('\\', "/");
+    let parts: Vec<&str> = path
+        .split('/')
+        .filter(|p| !p.is_empty() && *p != ".")
+        .collect();
+    if parts.is_empty() || parts.contains(&"..") {
+        return None;
+    }
+    Some(parts.join("/"))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn le_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn le_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+async fn archive_size(data: &ArchiveData) -> Result<u64> {
+    match data {
+        ArchiveData::Local(path) => Ok(std::fs::metadata(path)?.len()),
+        ArchiveData::S3 { manager, key } => Ok(manager.get_object_size(key).await?.max(0) as u64),
+    }
+}
+
+/// Liest das Central Directory eines Zip-Archivs (auch Zip64)
+async fn read_zip_directory(data: &ArchiveData) -> Result<Vec<ArchiveEntry>> {
+    let size = archive_size(data).await?;
+    let tail_start = size.saturating_sub(EOCD_SEARCH);
+    let tail = data.read_range(tail_start, size - tail_start).await?;
+    let eocd = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| le_u32(&tail, i) == 0x06054b50)
+        .context("Not a zip archive (no end of central directory)")?;
+
+    let mut count = le_u16(&tail, eocd + 10) as u64;
+    let mut cd_size = le_u32(&tail, eocd + 12) as u64;
+    let mut cd_offset = le_u32(&tail, eocd + 16) as u64;
+
+    // Zip64: Locator direkt vor dem EOCD verweist auf den Zip64-EOCD
+    if eocd >= 20 && le_u32(&tail, eocd - 20) == 0x07064b50 {
+        let eocd64_offset = le_u64(&tail, eocd - 12);
+        let eocd64 = data.read_range(eocd64_offset, 56).await?;
+        if eocd64.len() < 56 || le_u32(&eocd64, 0) != 0x06064b50 {
+            bail!("Invalid zip64 end of central directory");
+        }
+        count = le_u64(&eocd64, 32);
+        cd_size = le_u64(&eocd64, 40);
+        cd_offset = le_u64(&eocd64, 48);
+    }
+
+    // Kaputte oder manipulierte Werte dürfen keine riesigen Allokationen auslösen
+    if cd_offset.checked_add(cd_size).is_none_or(|end| end > size) {
+        bail!("Zip central directory lies outside the archive");
+    }
+    // Jeder Eintrag belegt mindestens 46 Bytes
+    let count = count.min(cd_size / 46);
+
+    let mut directory = Vec::with_capacity(cd_size as usize);
+    while (directory.len() as u64) < cd_size {
+        let len = READ_SIZE.min(cd_size - directory.len() as u64);
+        let bytes = data
+            .read_range(cd_offset + directory.len() as u64, len)
+            .await?;
+        if bytes.is_empty() {
+            bail!("Zip central directory is truncated");
+        }
+        directory.extend(bytes);
+    }
+
+    let mut entries = Vec::with_capacity(count as usize);
+    let mut pos = 0;
+    while pos + 46 <= directory.len() && le_u32(&directory, pos) == 0x02014b50 {
+        let header = &directory[pos..];
+        let name_len = le_u16(header, 28) as usize;
+        let extra_len = le_u16(header, 30) as usize;
+        let comment_len = le_u16(header, 32) as usize;
+        if header.len() < 46 + name_len + extra_len {
+            bail!("Zip central directory is truncated");
+        }
+        let name = &header[46..46 + name_len];
+        let extra = &header[46 + name_len..46 + name_len + extra_len];
+
+        let mut size = le_u32(header, 24) as u64;
+        let mut compressed_size = le_u32(header, 20) as u64;
+        let mut header_offset = le_u32(header, 42) as u64;
+        zip64_sizes(extra, &mut size, &mut compressed_size, &mut header_offset);
+
+        if let Some(path) = entry_path(name) {
+            entries.push(ArchiveEntry {
+                is_dir: name.ends_with(b"/"),
+                path,
+                size,
+                modified: dos_datetime(le_u16(header, 14), le_u16(header, 12)),
+                zip: Some(ZipLocation {
+                    header_offset,
+                    compressed_size,
+                    method: le_u16(header, 10),
+                    encrypted: le_u16(header, 8) & 1 != 0,
+                }),
+            });
+        }
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(entries)
+}
+
+/// Zip64-Extra-Feld: enthält die Werte, die im Header als 0xFFFFFFFF stehen
+fn zip64_sizes(extra: &[u8], size: &mut u64, compressed_size: &mut u64, offset: &mut u64) {
+    let mut pos = 0;
+    while pos + 4 <= extra.len() {
+        let id = le_u16(extra, pos);
+        let len = le_u16(extra, pos + 2) as usize;
+        let field = &extra[pos + 4..(pos + 4 + len).min(extra.len())];
+        if id == 0x0001 {
+            let mut at = 0;
+            for value in [size, compressed_size, offset] {
+                if *value == 0xFFFFFFFF && at + 8 <= field.len() {
+                    *value = le_u64(field, at);
+                    at += 8;
+                }
+            }
+            return;
+        }
+        pos += 4 + len;
+    }
+}
+
+/// MS-DOS Datum/Uhrzeit (lokale Zeit) aus dem Zip-Header
+fn dos_datetime(date: u16, time: u16) -> Option<DateTime<Utc>> {
+    let naive = NaiveDate::from_ymd_opt(
+        1980 + (date >> 9) as i32,
+        ((date >> 5) & 0x0f) as u32,
+        (date & 0x1f) as u32,
+    )?
+    .and_hms_opt(
+        (time >> 11) as u32,
+        ((time >> 5) & 0x3f) as u32,
+        ((time & 0x1f) * 2) as u32,
+    )?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn tar_archive(format: ArchiveFormat, path: &Path) -> Result<tar::Archive<Box<dyn std::io::Read>>> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+    let reader: Box<dyn std::io::Read> = match format {
+        ArchiveFormat::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+        _ => Box::new(file),
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+/// Liest alle Header eines Tar-Archivs (nur Dateien und Ordner)
+fn read_tar_entries(format: ArchiveFormat, path: &Path) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = tar_archive(format, path)?;
+    let mut entries = Vec::new();
+    for entry in archive.entries().context("Not a tar archive")? {
+        let entry = entry.context("Invalid tar archive")?;
+        let header = entry.header();
+        let kind = header.entry_type();
+        if !kind.is_file() && !kind.is_dir() {
+            continue;
+        }
+        let Some(path) = entry_path(&entry.path_bytes()) else {
+            continue;
+        };
+        entries.push(ArchiveEntry {
+            path,
+            is_dir: kind.is_dir(),
+            size: entry.size(),
+            modified: header
+                .mtime()
+                .ok()
+                .and_then(|t| DateTime::from_timestamp(t as i64, 0)),
+            zip: None,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zip64_field(values: &[u64]) -> Vec<u8> {
+        let mut extra = vec![0x01, 0x00];
+        extra.extend(((values.len() * 8) as u16).to_le_bytes());
+        for value in values {
+            extra.extend(value.to_le_bytes());
+        }
+        extra
+    }
+
+    /// End of Central Directory mit den angegebenen Werten
+    fn eocd(count: u16, cd_size: u32, cd_offset: u32) -> Vec<u8> {
+        let mut eocd = 0x06054b50u32.to_le_bytes().to_vec();
+        eocd.extend([0; 6]);
+        eocd.extend(count.to_le_bytes());
+        eocd.extend(cd_size.to_le_bytes());
+        eocd.extend(cd_offset.to_le_bytes());
+        eocd.extend([0; 2]);
+        eocd
+    }
+
+    async fn read_directory(name: &str, bytes: &[u8]) -> Result<Vec<ArchiveEntry>> {
+        let path = std::env::temp_dir().join(format!("s3c-test-{}-{name}", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        let result = read_zip_directory(&ArchiveData::Local(path.clone())).await;
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn zip64_sizes_replaces_only_saturated_values() {
+        let extra = zip64_field(&[5_000_000_000, 6_000_000_000]);
+        let (mut size, mut compressed_size, mut offset) = (0xFFFFFFFF, 100, 0xFFFFFFFF);
+        zip64_sizes(&extra, &mut size, &mut compressed_size, &mut offset);
+        assert_eq!(
+            (size, compressed_size, offset),
+            (5_000_000_000, 100, 6_000_000_000)
+        );
+    }
+
+    #[test]
+    fn zip64_sizes_skips_other_extra_fields() {
+        let mut extra = vec![0x55, 0x54, 0x05, 0x00, 1, 2, 3, 4, 5];
+        extra.extend(zip64_field(&[7_000_000_000]));
+        let (mut size, mut compressed_size, mut offset) = (10, 0xFFFFFFFF, 20);
+        zip64_sizes(&extra, &mut size, &mut compressed_size, &mut offset);
+        assert_eq!((size, compressed_size, offset), (10, 7_000_000_000, 20));
+    }
+
+    #[test]
+    fn zip64_sizes_ignores_truncated_field() {
+        let mut extra = zip64_field(&[5_000_000_000]);
+        extra.truncate(8);
+        let (mut size, mut compressed_size, mut offset) = (0xFFFFFFFF, 1, 2);
+        zip64_sizes(&extra, &mut size, &mut compressed_size, &mut offset);
+        assert_eq!((size, compressed_size, offset), (0xFFFFFFFF, 1, 2));
+    }
+
+    #[test]
+    fn entry_path_normalizes_and_rejects_parent_dirs() {
+        assert_eq!(
+            entry_path(b"./dir//file.txt").as_deref(),
+            Some("dir/file.txt")
+        );
+        assert_eq!(entry_path(b"/abs\\win.txt").as_deref(), Some("abs/win.txt"));
+        assert_eq!(entry_path(b"dir/../../etc/passwd"), None);
+        assert_eq!(entry_path(b"./"), None);
+    }
+
+    #[tokio::test]
+    async fn zip_directory_with_one_entry() {
+        let name = b"docs/readme.txt";
+        let mut cd = 0x02014b50u32.to_le_bytes().to_vec();
+        cd.extend([0; 16]);
+        cd.extend(11u32.to_le_bytes()); // compressed size
+        cd.extend(42u32.to_le_bytes()); // size
+        cd.extend((name.len() as u16).to_le_bytes());
+        cd.extend([0; 12]);
+        cd.extend(0u32.to_le_bytes()); // local header offset
+        cd.extend(name);
+        let mut zip = cd.clone();
+        zip.extend(eocd(1, cd.len() as u32, 0));
+
+        let entries = read_directory("one.zip", &zip).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "docs/readme.txt");
+        assert_eq!(entries[0].size, 42);
+        assert!(!entries[0].is_dir);
+    }
+
+    #[tokio::test]
+    async fn zip_directory_outside_the_archive_is_rejected() {
+        let zip = eocd(1, 0x7FFF_FFFF, 0);
+        let err = read_directory("outside.zip", &zip).await.unwrap_err();
+        assert!(err.to_string().contains("outside the archive"));
+
+        let zip = eocd(1, 10, 0xFFFF_FFF0);
+        assert!(read_directory("offset.zip", &zip).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn zip64_entry_count_is_capped_by_directory_size() {
+        // Zip64-EOCD mit absurder Anzahl, aber leerem Central Directory
+        let mut zip = 0x06064b50u32.to_le_bytes().to_vec();
+        zip.extend(44u64.to_le_bytes());
+        zip.extend([0; 20]);
+        zip.extend(u64::MAX.to_le_bytes()); // count
+        zip.extend(0u64.to_le_bytes()); // cd size
+        zip.extend(0u64.to_le_bytes()); // cd offset
+        zip.extend(0x07064b50u32.to_le_bytes());
+        zip.extend([0; 4]);
+        zip.extend(0u64.to_le_bytes()); // offset of the zip64 EOCD
+        zip.extend([0; 4]);
+        zip.extend(eocd(0xFFFF, 0xFFFF_FFFF, 0xFFFF_FFFF));
+
+        let entries = read_directory("zip64.zip", &zip).await.unwrap();
+        assert!(entries.is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+//! Destination conflicts: what happens when the target of a copy already exists.
+
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    Overwrite,
+    Skip,
+    Rename,
+    OverwriteIfNewer,
+    OverwriteIfSizeDiffers,
+}
+
+impl ConflictResolution {
+    /// Order of the options in the conflict dialog
+    pub const ALL: [ConflictResolution; 5] = [
+        ConflictResolution::Overwrite,
+        ConflictResolution::Skip,
+        ConflictResolution::Rename,
+        ConflictResolution::OverwriteIfNewer,
+        ConflictResolution::OverwriteIfSizeDiffers,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ConflictResolution::Overwrite => "Overwrite",
+            ConflictResolution::Skip => "Skip",
+            ConflictResolution::Rename => "Rename (keep both, add suffix)",
+            ConflictResolution::OverwriteIfNewer => "Overwrite if source is newer",
+            ConflictResolution::OverwriteIfSizeDiffers => "Overwrite if size differs",
+        }
+    }
+
+    /// Whether the transfer runs (onto the existing destination, or renamed).
+    /// Unknown modification times count as "not newer".
+    pub fn transfers(self, source: &FileStat, destination: &FileStat) -> bool {
+        match self {
+            ConflictResolution::Overwrite | ConflictResolution::Rename => true,
+            ConflictResolution::Skip => false,
+            ConflictResolution::OverwriteIfNewer => match (source.modified, destination.modified) {
+                (Some(source), Some(destination)) => source > destination,
+                _ => false,
+            },
+            ConflictResolution::OverwriteIfSizeDiffers => source.size != destination.size,
+        }
+    }
+}
+
+/// Size and modification time of a transfer source or an existing destination
+#[derive(Debug, Clone, Default)]
+pub struct FileStat {
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl FileStat {
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        Self {
+            size: metadata.len(),
+            modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+        }
+    }
+}
+
+/// `dir/report.csv` -> `dir/report (1).csv` (works for local paths, keys and s3:// URLs)
+pub fn with_suffix(path: &str, n: usize) -> String {
+    let name_start = path.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let (dir, name) = path.split_at(name_start);
+    // A leading dot is part of the name (".env" has no extension)
+    match name.rfind('.').filter(|&dot| dot > 0) {
+        Some(dot) => format!("{dir}{} ({n}){}", &name[..dot], &name[dot..]),
+        None => format!("{dir}{name} ({n})"),
+    }
+}
@@ -0,0 +1,452 @@
+//! Connection test for a bucket configuration.
+//! Runs every step of establishing a connection separately so a broken config
+//! shows *which* part is wrong instead of a generic list failure.
+
+use aws_config::SdkConfig;
+use aws_credential_types::provider::ProvideCredentials;
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::primitives::ByteStream;
+use tokio::sync::mpsc;
+
+use crate::models::config::BucketConfig;
+use crate::operations::s3::S3Manager;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepStatus {
+    Running,
+    Passed(String),
+    Failed {
+        code: String,
+        message: String,
+        hint: Option<String>,
+    },
+    Skipped(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticStep {
+    pub name: String,
+    pub status: StepStatus,
+}
+
+/// Progress message from the test task: step `index` has a new status
+#[derive(Debug, Clone)]
+pub struct DiagnosticUpdate {
+    pub index: usize,
+    pub step: DiagnosticStep,
+}
+
+/// Details extracted from an SDK error
+struct ErrorDetails {
+    code: String,
+    message: String,
+    status: Option<u16>,
+    bucket_region: Option<String>,
+}
+
+/// Sends step updates to the UI
+struct Reporter {
+    tx: mpsc::UnboundedSender<DiagnosticUpdate>,
+    next_index: usize,
+}
+
+impl Reporter {
+    fn start(&mut self, name: &str) -> usize {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.send(index, name, StepStatus::Running);
+        index
+    }
+
+    fn send(&self, index: usize, name: &str, status: StepStatus) {
+        let _ = self.tx.send(DiagnosticUpdate {
+            index,
+            step: DiagnosticStep {
+                name: name.to_string(),
+                status,
+            },
+        });
+    }
+
+    fn skip(&mut self, name: &str, reason: &str) {
+        let index = self.start(name);
+        self.send(index, name, StepStatus::Skipped(reason.to_string()));
+    }
+}
+
+/// Starts the connection test in the background; updates arrive on the returned channel
+pub fn start_connection_test(
+    profile: String,
+    bucket: BucketConfig,
+) -> (
+    mpsc::UnboundedReceiver<DiagnosticUpdate>,
+    tokio::task::JoinHandle<()>,
+) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let handle = tokio::spawn(async move {
+        let mut reporter = Reporter { tx, next_index: 0 };
+        run_connection_test(&mut reporter, &profile, &bucket).await;
+    });
+    (rx, handle)
+}
+
+async fn run_connection_test(reporter: &mut Reporter, profile: &str, bucket: &BucketConfig) {
+    let region = bucket.region.as_str();
+    let endpoint_url = bucket.endpoint_url.as_deref();
+
+    // 1. Profile + credentials (anonymous buckets skip everything up to the S3 steps)
+    if bucket.is_anonymous() {
+        let config = S3Manager::load_anonymous_config(region).await;
+        reporter.skip("Load profile", "anonymous access, requests are not signed");
+        skip_identity_steps(reporter, bucket, "anonymous access");
+        run_s3_steps(reporter, &config, bucket).await;
+        return;
+    }
+
+    let name = format!("Load profile '{profile}'");
+    let index = reporter.start(&name);
+    let mut config = S3Manager::load_profile_config(profile, region).await;
+    let credentials = match config.credentials_provider() {
+        Some(provider) => provider
+            .provide_credentials()
+            .await
+            .map_err(|e| error_chain(&e)),
+        None => Err("no credentials provider configured".to_string()),
+    };
+    match credentials {
+        Ok(creds) => reporter.send(
+            index,
+            &name,
+            StepStatus::Passed(format!(
+                "credentials resolved (access key {})",
+                mask_key(creds.access_key_id())
+            )),
+        ),
+        Err(message) => {
+            reporter.send(
+                index,
+                &name,
+                StepStatus::Failed {
+                    code: "CredentialsError".to_string(),
+                    message,
+                    hint: Some(format!(
+                        "check [{profile}] in ~/.aws/credentials or ~/.aws/config (SSO: aws sso login --profile {profile})"
+                    )),
+                },
+            );
+            skip_remaining(reporter, bucket, "no credentials");
+            return;
+        }
+    }
+
+    // 2. Role chain
+    for (role_index, role) in bucket.role_chain.iter().enumerate() {
+        let name = format!(
+            "AssumeRole {}/{}: {role}",
+            role_index + 1,
+            bucket.role_chain.len()
+        );
+        let index = reporter.start(&name);
+        match S3Manager::assume_role(&config, role, role_index, region).await {
+            Ok(assumed) => {
+                config = assumed;
+                reporter.send(index, &name, StepStatus::Passed("role assumed".to_string()));
+            }
+            Err(e) => {
+                let details = error_details(&e);
+                let hint = sts_hint(&details.code);
+                reporter.send(index, &name, failed(details, hint));
+                for role in &bucket.role_chain[role_index + 1..] {
+                    reporter.skip(&format!("AssumeRole: {role}"), "previous role failed");
+                }
+                reporter.skip("GetCallerIdentity", "role chain failed");
+                skip_s3_steps(reporter, "role chain failed");
+                return;
+            }
+        }
+    }
+
+    // 3. Identity
+    let name = "GetCallerIdentity";
+    if endpoint_url.is_some() {
+        reporter.skip(name, "custom endpoint (no AWS STS)");
+    } else {
+        let index = reporter.start(name);
+        let sts_client = aws_sdk_sts::Client::new(&config);
+        match sts_client.get_caller_identity().send().await {
+            Ok(identity) => reporter.send(
+                index,
+                name,
+                StepStatus::Passed(identity.arn().unwrap_or("-").to_string()),
+            ),
+            Err(e) => {
+                let details = error_details(&e);
+                let hint = sts_hint(&details.code);
+                reporter.send(index, name, failed(details, hint));
+            }
+        }
+    }
+
+    run_s3_steps(reporter, &config, bucket).await;
+}
+
+/// Steps 4-7: bucket access with the final credentials
+async fn run_s3_steps(reporter: &mut Reporter, config: &SdkConfig, bucket: &BucketConfig) {
+    let region = bucket.region.as_str();
+    let prefix = bucket.base_prefix.clone().unwrap_or_default();
+    let client = S3Manager::build_client(config, bucket.endpoint_url.as_deref(), bucket.path_style);
+
+    // 4. HeadBucket
+    let name = format!("HeadBucket '{}'", bucket.name);
+    let index = reporter.start(&name);
+    match client.head_bucket().bucket(&bucket.name).send().await {
+        Ok(output) => match output.bucket_region() {
+            Some(actual) if actual != region => reporter.send(
+                index,
+                &name,
+                StepStatus::Failed {
+                    code: "RegionMismatch".to_string(),
+                    message: format!("configured region is {region}"),
+                    hint: Some(format!("region mismatch: bucket is in {actual}")),
+                },
+            ),
+            _ => reporter.send(
+                index,
+                &name,
+                StepStatus::Passed("bucket reachable".to_string()),
+            ),
+        },
+        Err(e) => {
+            let details = error_details(&e);
+            let hint = s3_hint(&details, bucket, "s3:ListBucket");
+            reporter.send(index, &name, failed(details, hint));
+        }
+    }
+
+    // 5. ListObjectsV2
+    let name = if prefix.is_empty() {
+        "ListObjectsV2".to_string()
+    } else {
+        format!("ListObjectsV2 '{prefix}'")
+    };
+    let index = reporter.start(&name);
+    match client
+        .list_objects_v2()
+        .bucket(&bucket.name)
+        .prefix(&prefix)
+        .max_keys(1)
+        .send()
+        .await
+    {
+        Ok(output) => {
+            let detail = if output.contents().is_empty() && output.common_prefixes().is_empty() {
+                "listing allowed, no objects found".to_string()
+            } else {
+                "listing allowed".to_string()
+            };
+            reporter.send(index, &name, StepStatus::Passed(detail));
+        }
+        Err(e) => {
+            let details = error_details(&e);
+            let hint = s3_hint(&details, bucket, "s3:ListBucket");
+            reporter.send(index, &name, failed(details, hint));
+        }
+    }
+
+    // 6. Write probe
+    let probe_key = format!(
+        "{prefix}.s3c-connection-test-{}",
+        chrono::Utc::now().timestamp()
+    );
+    let name = format!("PutObject '{probe_key}'");
+    let index = reporter.start(&name);
+    let put_result = client
+        .put_object()
+        .bucket(&bucket.name)
+        .key(&probe_key)
+        .body(ByteStream::from_static(b"s3c connection test\n"))
+        .send()
+        .await;
+    match put_result {
+        Ok(_) => reporter.send(
+            index,
+            &name,
+            StepStatus::Passed("write allowed".to_string()),
+        ),
+        Err(e) => {
+            let details = error_details(&e);
+            let hint = s3_hint(&details, bucket, "s3:PutObject");
+            reporter.send(index, &name, failed(details, hint));
+            reporter.skip("DeleteObject (probe)", "nothing was written");
+            return;
+        }
+    }
+
+    // 7. Remove the probe again
+    let name = "DeleteObject (probe)";
+    let index = reporter.start(name);
+    match client
+        .delete_object()
+        .bucket(&bucket.name)
+        .key(&probe_key)
+        .send()
+        .await
+    {
+        Ok(_) => reporter.send(
+            index,
+            name,
+            StepStatus::Passed("delete allowed".to_string()),
+        ),
+        Err(e) => {
+            let details = error_details(&e);
+            let mut hint = s3_hint(&details, bucket, "s3:DeleteObject");
+            hint.get_or_insert_with(|| format!("remove '{probe_key}' manually"));
+            reporter.send(index, name, failed(details, hint));
+        }
+    }
+}
+
+fn skip_remaining(reporter: &mut Reporter, bucket: &BucketConfig, reason: &str) {
+    skip_identity_steps(reporter, bucket, reason);
+    skip_s3_steps(reporter, reason);
+}
+
+fn skip_identity_steps(reporter: &mut Reporter, bucket: &BucketConfig, reason: &str) {
+    for role in &bucket.role_chain {
+        reporter.skip(&format!("AssumeRole: {role}"), reason);
+    }
+    reporter.skip("GetCallerIdentity", reason);
+}
+
+fn skip_s3_steps(reporter: &mut Reporter, reason: &str) {
+    for name in [
+        "HeadBucket",
+        "ListObjectsV2",
+        "PutObject (probe)",
+        "DeleteObject (probe)",
+    ] {
+        reporter.skip(name, reason);
+    }
+}
+
+fn failed(details: ErrorDetails, hint: Option<String>) -> StepStatus {
+    StepStatus::Failed {
+        code: details.code,
+        message: details.message,
+        hint,
+    }
+}
+
+fn mask_key(key: &str) -> String {
+    if key.len() <= 8 {
+        return "****".to_string();
+    }
+    format!("{}…{}", &key[..4], &key[key.len() - 4..])
+}
+
+/// Error message including all sources, without the SDK's debug output
+fn error_chain(err: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+    message
+}
+
+fn error_details<E>(err: &SdkError<E, HttpResponse>) -> ErrorDetails
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+{
+    let status = err.raw_response().map(|r| r.status().as_u16());
+    let code = err
+        .code()
+        .map(str::to_string)
+        .or_else(|| match err {
+            SdkError::DispatchFailure(_) => Some("DispatchFailure".to_string()),
+            SdkError::TimeoutError(_) => Some("Timeout".to_string()),
+            SdkError::ConstructionFailure(_) => Some("ConstructionFailure".to_string()),
+            _ => None,
+        })
+        .or_else(|| status.map(|s| format!("HTTP {s}")))
+        .unwrap_or_else(|| "Unknown".to_string());
+    let message = err
+        .message()
+        .map(str::to_string)
+        .unwrap_or_else(|| error_chain(err));
+    let bucket_region = err
+        .raw_response()
+        .and_then(|r| r.headers().get("x-amz-bucket-region"))
+        .map(str::to_string);
+
+    ErrorDetails {
+        code,
+        message,
+        status,
+        bucket_region,
+    }
+}
+
+fn sts_hint(code: &str) -> Option<String> {
+    let hint = match code {
+        "AccessDenied" => {
+            "not allowed to assume this role: check the role's trust policy and sts:AssumeRole permission"
+        }
+        "InvalidClientTokenId" | "SignatureDoesNotMatch" => {
+            "credentials of the profile are invalid"
+        }
+        "ExpiredToken" | "ExpiredTokenException" => {
+            "session expired: refresh credentials (e.g. aws sso login)"
+        }
+        "DispatchFailure" | "Timeout" => "STS not reachable: check network/proxy",
+        _ => return None,
+    };
+    Some(hint.to_string())
+}
+
+fn s3_hint(details: &ErrorDetails, bucket: &BucketConfig, action: &str) -> Option<String> {
+    if let Some(actual) = &details.bucket_region {
+        if actual != &bucket.region {
+            return Some(format!("region mismatch: bucket is in {actual}"));
+        }
+    }
+
+    let hint = match (details.code.as_str(), details.status) {
+        ("PermanentRedirect" | "AuthorizationHeaderMalformed", _) | (_, Some(301)) => {
+            "region mismatch: check the bucket's region".to_string()
+        }
+        ("NoSuchBucket" | "NotFound", _) | (_, Some(404)) => {
+            if bucket.endpoint_url.is_some() {
+                "bucket not found: check bucket name and endpoint URL".to_string()
+            } else {
+                "bucket not found: check the bucket name".to_string()
+            }
+        }
+        ("AccessDenied" | "Forbidden", _) | (_, Some(403)) => {
+            format!("access denied: identity lacks {action} (check IAM and bucket policy)")
+        }
+        ("InvalidAccessKeyId", _) => {
+            "access key unknown to this endpoint: wrong credentials or endpoint URL".to_string()
+        }
+        ("SignatureDoesNotMatch", _) => {
+            "signature mismatch: wrong secret key or region/endpoint".to_string()
+        }
+        ("ExpiredToken" | "TokenRefreshRequired", _) => {
+            "session expired: refresh credentials (e.g. aws sso login)".to_string()
+        }
+        ("DispatchFailure", _) => {
+            if bucket.endpoint_url.is_some() && bucket.path_style != Some(true) {
+                "endpoint unreachable: many S3-compatible services need 'Force Path Style'"
+                    .to_string()
+            } else {
+                "endpoint unreachable: check endpoint URL and network".to_string()
+            }
+        }
+        ("Timeout", _) => "request timed out: check endpoint URL and network".to_string(),
+        _ => return None,
+    };
+    Some(hint)
+}
@@ -0,0 +1,94 @@
+//! Opening files in an external program ($EDITOR or $PAGER).
+//!
+//! S3 objects are downloaded to a temp directory first. Whether the editor changed
+//! the file is decided by comparing modification time, size and a content hash.
+
+use anyhow::{Context, Result};
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
+
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// State of a file before it is opened in the editor
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+}
+
+impl FileFingerprint {
+    pub fn read(path: &Path) -> Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let n = file.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            buffer[..n].hash(&mut hasher);
+        }
+        Ok(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            hash: hasher.finish(),
+        })
+    }
+
+    /// Changed if size or content differ. A new modification time with the same
+    /// content (saved without edits) doesn't count.
+    pub fn changed(&self, path: &Path) -> Result<bool> {
+        let now = Self::read(path)?;
+        Ok(now.len != self.len || (now.modified != self.modified && now.hash != self.hash))
+    }
+}
+
+/// Command for the editor ($VISUAL, $EDITOR) or pager ($PAGER) with the file as argument.
+/// The variable may contain arguments (e.g. `code --wait`).
+pub fn external_command(edit: bool, path: &Path) -> Command {
+    let configured = if edit {
+        std::env::var("VISUAL")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .or_else(|| std::env::var("EDITOR").ok())
+    } else {
+        std::env::var("PAGER").ok()
+    }
+    .filter(|v| !v.trim().is_empty());
+
+    #[cfg(target_os = "windows")]
+    let fallback = if edit { "notepad" } else { "more" };
+    #[cfg(not(target_os = "windows"))]
+    let fallback = if edit { "vi" } else { "less" };
+
+    let program = configured.unwrap_or_else(|| fallback.to_string());
+    let mut parts = program.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or(fallback));
+    command.args(parts).arg(path);
+    command
+}
+
+/// Path for the temp copy of an S3 object (own directory, original name, so the
+/// editor recognizes the file type by its extension)
+pub fn temp_copy_path(filename: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!(
+        "s3c-edit-{}-{}",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&dir).context("Failed to create temp directory")?;
+    Ok(dir.join(filename))
+}
+
+/// Removes the temp copy together with its directory
+pub fn remove_temp_copy(path: &Path) {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
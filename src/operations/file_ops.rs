@@ -1,5 +1,7 @@
-use crate::app::{App, InputMode, PanelType, Screen};
+use crate::app::{App, InputMode, PanelType, Screen, TransferConflictState};
 use crate::models::list::{ItemData, ItemType, PanelItem};
+use crate::operations::conflict::{with_suffix, ConflictResolution, FileStat};
+use crate::operations::s3::S3Manager;
 use crate::operations::{FileOperation, OperationStatus, OperationType};
 use anyhow::Result;
 use std::path::PathBuf;
@@ -17,6 +19,10 @@ impl App {
         let dest_type = dest_panel.panel_type.clone();
         let source_selected = source_panel.selected_index;
 
+        if let PanelType::Archive { .. } = source_type {
+            return self.extract_to_other_panel().await;
+        }
+
         match (&source_type, &dest_type) {
             // S3 → Local: Download file
             (
@@ -40,6 +46,10 @@ impl App {
                     let local_path = path.join(filename);
                     let key = s3_obj.key.clone();
                     let file_size = s3_obj.size as u64;
+                    let source = FileStat {
+                        size: file_size,
+                        modified: s3_obj.last_modified,
+                    };
 
                     // Queue-First: Always add to queue as Pending
                     // Queue processing will start the transfer automatically
@@ -54,10 +64,15 @@ impl App {
                         bucket: Some(bucket.clone()),
                         dest_profile: None,
                         dest_bucket: None,
+                        upload_id: None,
+                        etag: None,
+                        retries: 0,
+                        stats: Default::default(),
+                        priority: Default::default(),
                     };
 
-                    // Add to queue - queue processing handles the rest
-                    self.file_operation_queue.push(operation);
+                    // Add to queue (asks first if the file exists) - queue processing handles the rest
+                    self.enqueue_transfer(operation, source).await?;
                 }
             }
 
@@ -126,6 +141,10 @@ impl App {
                 {
                     let source_key = &s3_obj.key;
                     let file_size = s3_obj.size.max(0) as u64;
+                    let source = FileStat {
+                        size: file_size,
+                        modified: s3_obj.last_modified,
+                    };
 
                     // Build destination key
                     let dest_key = if dest_prefix.is_empty() {
@@ -155,9 +174,14 @@ impl App {
                         bucket: Some(source_bucket.clone()),
                         dest_profile: Some(dest_profile.clone()),
                         dest_bucket: Some(dest_bucket.clone()),
+                        upload_id: None,
+                        etag: None,
+                        retries: 0,
+                        stats: Default::default(),
+                        priority: Default::default(),
                     };
 
-                    self.file_operation_queue.push(operation);
+                    self.enqueue_transfer(operation, source).await?;
                     // Don't show success message yet - will show when copy completes
                 }
             }
@@ -178,9 +202,11 @@ impl App {
                         },
                     name,
                     size,
+                    modified: item_modified,
                     ..
                 }) = item
                 {
+                    let item_modified = *item_modified;
                     let source_file_path = source_file_path.clone();
                     let name = name.clone();
                     let dest_file_path = dest_path.join(&name);
@@ -192,50 +218,28 @@ impl App {
                         return Ok(());
                     }
 
-                    // Create file operation and add to queue
-                    self.file_operation_queue.push(FileOperation {
+                    let source = FileStat {
+                        size: file_size,
+                        modified: item_modified,
+                    };
+                    let operation = FileOperation {
                         operation_type: OperationType::Copy,
                         source: source_file_path.display().to_string(),
                         destination: dest_file_path.display().to_string(),
                         total_size: file_size,
                         transferred: 0,
-                        status: OperationStatus::InProgress,
+                        status: OperationStatus::Pending,
                         profile: None, // Local copy doesn't need S3 credentials
                         bucket: None,
                         dest_profile: None,
                         dest_bucket: None,
-                    });
-                    let queue_index = self.file_operation_queue.len() - 1;
-
-                    let result = self
-                        .copy_local_file_with_progress(&source_file_path, &dest_file_path)
-                        .await;
-
-                    match result {
-                        Ok(_) => {
-                            if let Some(op) = self.file_operation_queue.get_mut(queue_index) {
-                                op.status = OperationStatus::Completed;
-                            }
-                            self.show_success(&format!("Copied: {name}"));
-                            crate::app::navigation::reload_local_files(self).await?;
-                        }
-                        Err(e) => {
-                            if let Some(op) = self.file_operation_queue.get_mut(queue_index) {
-                                op.status = OperationStatus::Failed(format!("{e}"));
-                            }
-                            let error_msg = format!("{e}");
-                            if error_msg.contains("Permission denied")
-                                || error_msg.contains("permission denied")
-                            {
-                                self.show_error(&format!(
-                                    "Permission denied: Cannot write to '{}'",
-                                    dest_file_path.display()
-                                ));
-                            } else {
-                                self.show_error(&format!("Copy failed: {e}"));
-                            }
-                        }
-                    }
+                        upload_id: None,
+                        etag: None,
+                        retries: 0,
+                        stats: Default::default(),
+                        priority: Default::default(),
+                    };
+                    self.enqueue_transfer(operation, source).await?;
                 }
             }
 
@@ -247,6 +251,270 @@ impl App {
         Ok(())
     }
 
+    /// Extracts the selected archive entry (a folder with everything below it) into the
+    /// other panel. Existing files are kept; S3 destinations are uploaded directly.
+    async fn extract_to_other_panel(&mut self) -> Result<()> {
+        let (source_panel, dest_panel) = match self.active_panel {
+            crate::app::ActivePanel::Left => (&self.left_panel, &self.right_panel),
+            crate::app::ActivePanel::Right => (&self.right_panel, &self.left_panel),
+        };
+        let Some(index) = &source_panel.archive else {
+            return Ok(());
+        };
+        let Some(PanelItem {
+            data: ItemData::ArchiveEntry { path },
+            name,
+            ..
+        }) = source_panel
+            .list_model
+            .get_item(source_panel.selected_index)
+        else {
+            return Ok(());
+        };
+        let name = name.clone();
+        let total = index.file_count(path);
+
+        let result = match &dest_panel.panel_type {
+            PanelType::LocalFilesystem { path: dest_dir } => index
+                .extract(path, dest_dir)
+                .await
+                .map(|files| (files.len(), dest_dir.display().to_string())),
+            PanelType::S3Browser { bucket, prefix, .. } => {
+                let Some(s3_manager) = dest_panel.s3_manager.clone() else {
+                    return Ok(());
+                };
+                let destination = format!("s3://{bucket}/{prefix}");
+                extract_to_s3(index, path, &s3_manager, prefix)
+                    .await
+                    .map(|uploaded| (uploaded, destination))
+            }
+            _ => {
+                self.show_error("Extract needs a local or S3 folder in the other panel");
+                return Ok(());
+            }
+        };
+
+        match result {
+            Ok((extracted, destination)) => {
+                crate::app::navigation::reload_local_files(self).await?;
+                crate::app::navigation::reload_s3_browser(self).await?;
+                let skipped = total.saturating_sub(extracted);
+                let mut message = format!("Extracted {extracted} file(s) to {destination}");
+                if skipped > 0 {
+                    message.push_str(&format!(" ({skipped} skipped, already exist)"));
+                }
+                self.show_success(&message);
+            }
+            Err(e) => self.show_error(&format!("Failed to extract '{name}': {e}")),
+        }
+        Ok(())
+    }
+
+    /// Adds a transfer to the queue - unless its destination exists, then the
+    /// conflict dialog decides
+    pub(crate) async fn enqueue_transfer(
+        &mut self,
+        operation: FileOperation,
+        source: FileStat,
+    ) -> Result<()> {
+        // Destination can't be checked (e.g. no HEAD permission): transfer as before
+        let Ok(Some(destination)) = self.destination_stat(&operation).await else {
+            return self.start_transfer(operation).await;
+        };
+
+        self.transfer_conflict = TransferConflictState {
+            operation: Some(operation),
+            source,
+            destination,
+            selected: 0,
+        };
+        // From the upload path prompt: the dialog replaces it
+        if self.screen != Screen::Input {
+            self.prev_screen = Some(self.screen.clone());
+        }
+        self.screen = Screen::TransferConflict;
+        Ok(())
+    }
+
+    /// Applies the answer of the conflict dialog to the transfer waiting in it
+    pub(crate) async fn confirm_transfer_conflict(&mut self) -> Result<()> {
+        let state = std::mem::take(&mut self.transfer_conflict);
+        let Some(operation) = state.operation else {
+            return Ok(());
+        };
+        let resolution = ConflictResolution::ALL[state.selected];
+        self.resolve_conflict(operation, resolution, &state.source, &state.destination)
+            .await
+    }
+
+    /// Esc in the conflict dialog: skips the transfer
+    pub(crate) fn skip_transfer_conflict(&mut self) {
+        let state = std::mem::take(&mut self.transfer_conflict);
+        if let Some(operation) = state.operation {
+            self.show_info(&format!("Skipped (exists): {}", operation.destination));
+        }
+    }
+
+    async fn resolve_conflict(
+        &mut self,
+        mut operation: FileOperation,
+        resolution: ConflictResolution,
+        source: &FileStat,
+        destination: &FileStat,
+    ) -> Result<()> {
+        if !resolution.transfers(source, destination) {
+            self.show_info(&format!("Skipped (exists): {}", operation.destination));
+            return Ok(());
+        }
+
+        if resolution == ConflictResolution::Rename {
+            match self.free_destination(&operation).await {
+                Some(destination) => operation.destination = destination,
+                None => {
+                    self.show_error(&format!("No free name found for {}", operation.destination));
+                    return Ok(());
+                }
+            }
+        }
+        self.start_transfer(operation).await
+    }
+
+    /// First `name (n).ext` that doesn't exist yet
+    async fn free_destination(&self, operation: &FileOperation) -> Option<String> {
+        let mut candidate = operation.clone();
+        for n in 1..=999 {
+            candidate.destination = with_suffix(&operation.destination, n);
+            if let Ok(None) = self.destination_stat(&candidate).await {
+                return Some(candidate.destination);
+            }
+        }
+        None
+    }
+
+    /// Size and date of the existing destination, `None` if it doesn't exist
+    async fn destination_stat(&self, operation: &FileOperation) -> Result<Option<FileStat>> {
+        let (profile, bucket, key) = match operation.operation_type {
+            OperationType::Download | OperationType::Copy => {
+                return match std::fs::metadata(&operation.destination) {
+                    Ok(metadata) => Ok(Some(FileStat::from_metadata(&metadata))),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(e.into()),
+                };
+            }
+            OperationType::Upload => (
+                operation.profile.as_deref(),
+                operation.bucket.as_deref(),
+                operation.destination.as_str(),
+            ),
+            OperationType::S3Copy => {
+                let bucket = operation.dest_bucket.as_deref().unwrap_or_default();
+                (
+                    operation.dest_profile.as_deref(),
+                    Some(bucket),
+                    operation
+                        .destination
+                        .strip_prefix(&format!("s3://{bucket}/"))
+                        .unwrap_or(&operation.destination),
+                )
+            }
+            OperationType::Rename => return Ok(None),
+        };
+        let (Some(profile), Some(bucket)) = (profile, bucket) else {
+            return Ok(None);
+        };
+
+        let object = self
+            .s3_manager_for(profile, bucket)
+            .await?
+            .stat_object(key)
+            .await?;
+        Ok(object.map(|object| FileStat {
+            size: object.size.max(0) as u64,
+            modified: object.last_modified,
+        }))
+    }
+
+    /// Connection of a panel showing this bucket, otherwise a new one
+    async fn s3_manager_for(&self, profile: &str, bucket: &str) -> Result<S3Manager> {
+        for panel in [&self.left_panel, &self.right_panel] {
+            if let (
+                PanelType::S3Browser {
+                    profile: p,
+                    bucket: b,
+                    ..
+                },
+                Some(manager),
+            ) = (&panel.panel_type, &panel.s3_manager)
+            {
+                if p == profile && b == bucket {
+                    return Ok(manager.clone());
+                }
+            }
+        }
+        let config = self
+            .config_manager
+            .get_bucket_config(profile, bucket)
+            .ok_or_else(|| anyhow::anyhow!("Bucket config not found"))?;
+        S3Manager::from_bucket_config(profile, &config).await
+    }
+
+    /// Queues S3 transfers, local copies run right away
+    async fn start_transfer(&mut self, operation: FileOperation) -> Result<()> {
+        if operation.operation_type == OperationType::Copy {
+            self.copy_locally(operation).await
+        } else {
+            self.file_operation_queue.push(operation);
+            Ok(())
+        }
+    }
+
+    async fn copy_locally(&mut self, mut operation: FileOperation) -> Result<()> {
+        let source_file_path = PathBuf::from(&operation.source);
+        let dest_file_path = PathBuf::from(&operation.destination);
+        let name = dest_file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        operation.status = OperationStatus::InProgress;
+        operation.mark_started();
+        self.file_operation_queue.push(operation);
+        let queue_index = self.file_operation_queue.len() - 1;
+
+        let result = self
+            .copy_local_file_with_progress(&source_file_path, &dest_file_path)
+            .await;
+
+        match result {
+            Ok(_) => {
+                if let Some(op) = self.file_operation_queue.get_mut(queue_index) {
+                    op.status = OperationStatus::Completed;
+                    op.mark_finished();
+                }
+                self.show_success(&format!("Copied: {name}"));
+                crate::app::navigation::reload_local_files(self).await?;
+            }
+            Err(e) => {
+                if let Some(op) = self.file_operation_queue.get_mut(queue_index) {
+                    op.status = OperationStatus::Failed(format!("{e}"));
+                    op.mark_finished();
+                }
+                let error_msg = format!("{e}");
+                if error_msg.contains("Permission denied")
+                    || error_msg.contains("permission denied")
+                {
+                    self.show_error(&format!(
+                        "Permission denied: Cannot write to '{}'",
+                        dest_file_path.display()
+                    ));
+                } else {
+                    self.show_error(&format!("Copy failed: {e}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Helper function to copy local files with progress tracking
     pub(crate) async fn copy_local_file_with_progress(
         &mut self,
@@ -280,3 +548,33 @@ impl App {
         Ok(())
     }
 }
+
+/// Extracts into a staging directory and uploads the files below `prefix`
+/// (existing keys are left alone). Returns the number of uploaded files.
+async fn extract_to_s3(
+    index: &crate::operations::archive::ArchiveIndex,
+    path: &str,
+    s3_manager: &S3Manager,
+    prefix: &str,
+) -> Result<usize> {
+    let staging = index.staging_dir();
+    let result = async {
+        let mut uploaded = 0;
+        for file in index.extract(path, &staging).await? {
+            let relative = file
+                .strip_prefix(&staging)?
+                .to_string_lossy()
+                .replace('\\', "/");
+            let key = format!("{prefix}{relative}");
+            if s3_manager.stat_object(&key).await?.is_some() {
+                continue;
+            }
+            s3_manager.upload_file(&file, &key).await?;
+            uploaded += 1;
+        }
+        Ok(uploaded)
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
@@ -1,9 +1,14 @@
 pub mod app_operations;
+pub mod archive;
+pub mod conflict;
+pub mod diagnostics;
+pub mod external;
 pub mod file_ops;
 pub mod preview;
 pub mod queue;
 pub mod run;
 pub mod s3;
+pub mod throttle;
 
 pub use app_operations::{confirm_delete, create_folder, rename_file, view_file};
 pub use queue::{FileOperation, OperationStatus, OperationType};
@@ -11,4 +16,7 @@ pub use run::run_app;
 
 // Export for library usage
 #[allow(unused_imports)]
-pub use run::{process_background_list_task, process_background_tasks, process_setup_script};
+pub use run::{
+    process_background_list_task, process_background_tasks, process_setup_script,
+    process_start_locations,
+};
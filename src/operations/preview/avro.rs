@@ -0,0 +1,706 @@
+//! Minimaler Leser für Avro Object Container Files: Header mit JSON-Schema, dann Blöcke
+//! mit Datensätzen (Codecs null, deflate, snappy, zstandard, bzip2, xz).
+
+use super::records::PrefixParser;
+use crate::models::preview::{RecordInfo, RecordSet, RECORD_PREVIEW_ROWS};
+use anyhow::{anyhow, bail, Result};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::Read;
+
+const MAGIC: &[u8] = b"Obj\x01";
+
+/// Avro-Schema (benannte Typen werden über ihren Namen nachgeschlagen)
+#[derive(Debug, Clone)]
+enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Date,            // int: Tage seit 1970-01-01
+    TimestampMillis, // long
+    TimestampMicros, // long
+    Record(Vec<(String, Schema)>),
+    Enum(Vec<String>),
+    Array(Box<Schema>),
+    Map(Box<Schema>),
+    Union(Vec<Schema>),
+    Fixed(usize),
+    Named(String),
+}
+
+/// Zerlegt den Dateianfang: Header, dann alle vollständigen Blöcke (bis
+/// RECORD_PREVIEW_ROWS Datensätze)
+#[derive(Default)]
+pub struct AvroRecords {
+    header: Option<Header>,
+    pos: usize, // Ende des letzten vollständig gelesenen Blocks
+    rows: Vec<Vec<(String, Value)>>,
+    truncated: bool, // nicht alle Datensätze eines Blocks übernommen
+}
+
+impl PrefixParser for AvroRecords {
+    fn parse(&mut self, bytes: &[u8], complete: bool) -> Result<()> {
+        if bytes.len() >= MAGIC.len() && !bytes.starts_with(MAGIC) {
+            bail!("Not an Avro object container file");
+        }
+        let mut input = Input {
+            bytes,
+            pos: self.pos,
+        };
+        let header = match &mut self.header {
+            Some(header) => header,
+            None => match read_header(&mut input) {
+                Ok(header) => self.header.insert(header),
+                // Mehr lesen (der Aufrufer liest weiter, solange die Datensätze nicht reichen)
+                Err(_) if !complete => return Ok(()),
+                Err(e) => return Err(e),
+            },
+        };
+
+        while self.rows.len() < RECORD_PREVIEW_ROWS && input.pos < bytes.len() {
+            // Ein angeschnittener Block am Ende wird im nächsten Schritt erneut gelesen
+            let start = input.pos;
+            let Ok(block) = read_block(&mut input, header) else {
+                input.pos = start;
+                break;
+            };
+            let mut data = Input {
+                bytes: &block.data,
+                pos: 0,
+            };
+            for _ in 0..block.count {
+                if self.rows.len() >= RECORD_PREVIEW_ROWS {
+                    self.truncated = true;
+                    break;
+                }
+                let value = header.decode(&header.schema, &mut data)?;
+                self.rows.push(match value {
+                    Value::Object(_) if matches!(header.schema, Schema::Record(_)) => {
+                        record_fields(&header.schema, value)
+                    }
+                    other => vec![("value".to_string(), other)],
+                });
+            }
+        }
+        self.pos = input.pos;
+        Ok(())
+    }
+
+    fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn finish(self, bytes: &[u8], complete: bool) -> RecordSet {
+        let Some(header) = self.header else {
+            return RecordSet {
+                info: RecordInfo {
+                    format: "AVRO",
+                    summary: format!("Header not complete in the first {} KB", bytes.len() / 1024),
+                    fields: Vec::new(),
+                    total_rows: None,
+                    file_size: 0,
+                },
+                rows: Vec::new(),
+            };
+        };
+        let whole_file = complete && !self.truncated && self.pos >= bytes.len();
+
+        let fields = match &header.schema {
+            Schema::Record(fields) => fields
+                .iter()
+                .map(|(name, schema)| (name.clone(), type_name(schema)))
+                .collect(),
+            other => vec![("value".to_string(), type_name(other))],
+        };
+        let read = if whole_file {
+            "whole file read".to_string()
+        } else {
+            format!("first {} KB read", self.pos / 1024)
+        };
+        RecordSet {
+            info: RecordInfo {
+                format: "AVRO",
+                summary: format!(
+                    "Record {} | Codec: {} | {} records ({read})",
+                    header.name,
+                    header.codec,
+                    self.rows.len()
+                ),
+                fields,
+                total_rows: whole_file.then_some(self.rows.len() as u64),
+                file_size: 0, // setzt load_records
+            },
+            rows: self.rows,
+        }
+    }
+}
+
+/// Felder eines Datensatzes in Schema-Reihenfolge
+fn record_fields(schema: &Schema, value: Value) -> Vec<(String, Value)> {
+    let (Schema::Record(fields), Value::Object(mut map)) = (schema, value) else {
+        return Vec::new();
+    };
+    fields
+        .iter()
+        .map(|(name, _)| (name.clone(), map.remove(name).unwrap_or(Value::Null)))
+        .collect()
+}
+
+struct Header {
+    schema: Schema,
+    named: HashMap<String, Schema>,
+    name: String,
+    codec: String,
+    sync: Vec<u8>,
+}
+
+struct Block {
+    count: usize,
+    data: Vec<u8>,
+}
+
+fn read_header(input: &mut Input) -> Result<Header> {
+    input.take(MAGIC.len())?;
+    let mut metadata = HashMap::new();
+    loop {
+        let count = input.long()?;
+        if count == 0 {
+            break;
+        }
+        if count < 0 {
+            input.long()?; // Blockgröße in Bytes
+        }
+        for _ in 0..count.unsigned_abs() {
+            let key = input.string()?;
+            let value = input.bytes()?.to_vec();
+            metadata.insert(key, value);
+        }
+    }
+    let sync = input.take(16)?.to_vec();
+
+    let schema_json = metadata
+        .get("avro.schema")
+        .ok_or_else(|| anyhow!("Avro header has no schema"))?;
+    let schema_json: Value = serde_json::from_slice(schema_json)?;
+    let mut named = HashMap::new();
+    let schema = parse_schema(&schema_json, &mut named)?;
+    let codec = metadata
+        .get("avro.codec")
+        .map(|c| String::from_utf8_lossy(c).to_string())
+        .unwrap_or_else(|| "null".to_string());
+    let name = schema_json
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("-")
+        .to_string();
+    Ok(Header {
+        schema,
+        named,
+        name,
+        codec,
+        sync,
+    })
+}
+
+fn read_block(input: &mut Input, header: &Header) -> Result<Block> {
+    let count = input.long()?;
+    let size = input.long()?;
+    if count < 0 || size < 0 {
+        bail!("Invalid Avro block");
+    }
+    let raw = input.take(size as usize)?;
+    if input.take(16)? != header.sync.as_slice() {
+        bail!("Avro sync marker mismatch");
+    }
+    let data = match header.codec.as_str() {
+        "null" => raw.to_vec(),
+        "deflate" => read_all(flate2::read::DeflateDecoder::new(raw))?,
+        // Snappy-Blöcke enden mit einer CRC32 der unkomprimierten Daten
+        "snappy" => snap::raw::Decoder::new()
+            .decompress_vec(&raw[..raw.len().saturating_sub(4)])
+            .map_err(|e| anyhow!("Snappy: {e}"))?,
+        "zstandard" => zstd::decode_all(raw)?,
+        "bzip2" => read_all(bzip2::read::BzDecoder::new(raw))?,
+        "xz" => read_all(liblzma::read::XzDecoder::new(raw))?,
+        other => bail!("Unsupported Avro codec '{other}'"),
+    };
+    Ok(Block {
+        count: count as usize,
+        data,
+    })
+}
+
+fn read_all(mut reader: impl Read) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+fn parse_schema(json: &Value, named: &mut HashMap<String, Schema>) -> Result<Schema> {
+    match json {
+        Value::String(name) => Ok(primitive(name).unwrap_or_else(|| Schema::Named(name.clone()))),
+        Value::Array(branches) => Ok(Schema::Union(
+            branches
+                .iter()
+                .map(|b| parse_schema(b, named))
+                .collect::<Result<_>>()?,
+        )),
+        Value::Object(object) => {
+            let kind = object
+                .get("type")
+                .ok_or_else(|| anyhow!("Avro schema without type"))?;
+            let Some(kind) = kind.as_str() else {
+                return parse_schema(kind, named);
+            };
+            let schema = match kind {
+                "record" | "error" => {
+                    let mut fields = Vec::new();
+                    for field in object
+                        .get("fields")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                    {
+                        let name = field.get("name").and_then(Value::as_str).unwrap_or("");
+                        let schema =
+                            parse_schema(field.get("type").unwrap_or(&Value::Null), named)?;
+                        fields.push((name.to_string(), schema));
+                    }
+                    Schema::Record(fields)
+                }
+                "enum" => Schema::Enum(
+                    object
+                        .get("symbols")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|s| s.as_str().map(str::to_string))
+                        .collect(),
+                ),
+                "array" => Schema::Array(Box::new(parse_schema(
+                    object.get("items").unwrap_or(&Value::Null),
+                    named,
+                )?)),
+                "map" => Schema::Map(Box::new(parse_schema(
+                    object.get("values").unwrap_or(&Value::Null),
+                    named,
+                )?)),
+                "fixed" => {
+                    Schema::Fixed(object.get("size").and_then(Value::as_u64).unwrap_or(0) as usize)
+                }
+                other => {
+                    let logical = object.get("logicalType").and_then(Value::as_str);
+                    match (other, logical) {
+                        ("int", Some("date")) => Schema::Date,
+                        ("long", Some("timestamp-millis")) => Schema::TimestampMillis,
+                        ("long", Some("timestamp-micros")) => Schema::TimestampMicros,
+                        _ => primitive(other).unwrap_or_else(|| Schema::Named(other.to_string())),
+                    }
+                }
+            };
+            // Benannte Typen: unter vollem und kurzem Namen ablegen
+            if let Some(name) = object.get("name").and_then(Value::as_str) {
+                named.insert(name.to_string(), schema.clone());
+                if let Some(namespace) = object.get("namespace").and_then(Value::as_str) {
+                    named.insert(format!("{namespace}.{name}"), schema.clone());
+                }
+                if let Some((_, short)) = name.rsplit_once('.') {
+                    named.insert(short.to_string(), schema.clone());
+                }
+            }
+            Ok(schema)
+        }
+        _ => bail!("Invalid Avro schema"),
+    }
+}
+
+fn primitive(name: &str) -> Option<Schema> {
+    Some(match name {
+        "null" => Schema::Null,
+        "boolean" => Schema::Boolean,
+        "int" => Schema::Int,
+        "long" => Schema::Long,
+        "float" => Schema::Float,
+        "double" => Schema::Double,
+        "bytes" => Schema::Bytes,
+        "string" => Schema::String,
+        _ => return None,
+    })
+}
+
+/// Typ für die Schema-Anzeige
+fn type_name(schema: &Schema) -> String {
+    match schema {
+        Schema::Null => "null".to_string(),
+        Schema::Boolean => "boolean".to_string(),
+        Schema::Int => "int".to_string(),
+        Schema::Long => "long".to_string(),
+        Schema::Float => "float".to_string(),
+        Schema::Double => "double".to_string(),
+        Schema::Bytes => "bytes".to_string(),
+        Schema::String => "string".to_string(),
+        Schema::Date => "int date".to_string(),
+        Schema::TimestampMillis => "long timestamp-millis".to_string(),
+        Schema::TimestampMicros => "long timestamp-micros".to_string(),
+        Schema::Record(fields) => format!("record ({} fields)", fields.len()),
+        Schema::Enum(symbols) => format!("enum ({} symbols)", symbols.len()),
+        Schema::Array(items) => format!("array<{}>", type_name(items)),
+        Schema::Map(values) => format!("map<{}>", type_name(values)),
+        Schema::Union(branches) => branches
+            .iter()
+            .map(type_name)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        Schema::Fixed(size) => format!("fixed ({size} bytes)"),
+        Schema::Named(name) => name.clone(),
+    }
+}
+
+impl Header {
+    /// Dekodiert einen Wert im Binärformat
+    fn decode(&self, schema: &Schema, input: &mut Input) -> Result<Value> {
+        Ok(match schema {
+            Schema::Null => Value::Null,
+            Schema::Boolean => Value::Bool(input.take(1)?[0] != 0),
+            Schema::Int | Schema::Long => Value::from(input.long()?),
+            Schema::Float => {
+                let b = input.take(4)?;
+                float_value(f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64)
+            }
+            Schema::Double => {
+                let mut b = [0; 8];
+                b.copy_from_slice(input.take(8)?);
+                float_value(f64::from_le_bytes(b))
+            }
+            Schema::Bytes => Value::String(bytes_text(input.bytes()?)),
+            Schema::String => Value::String(input.string()?),
+            Schema::Date => {
+                let days = input.long()?;
+                chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
+                    .and_then(|d| d.checked_add_signed(chrono::Duration::days(days)))
+                    .map_or(Value::from(days), |d| Value::String(d.to_string()))
+            }
+            Schema::TimestampMillis => timestamp(input.long()?, 1_000),
+            Schema::TimestampMicros => timestamp(input.long()?, 1_000_000),
+            Schema::Record(fields) => {
+                let mut map = Map::new();
+                for (name, field) in fields {
+                    map.insert(name.clone(), self.decode(field, input)?);
+                }
+                Value::Object(map)
+            }
+            Schema::Enum(symbols) => {
+                let index = input.long()?;
+                symbols
+                    .get(index as usize)
+                    .map_or(Value::from(index), |s| Value::String(s.clone()))
+            }
+            Schema::Array(items) => {
+                let mut values = Vec::new();
+                let empty_items = self.encodes_empty(items);
+                while let Some(count) = input.block_count()? {
+                    // Jeder Eintrag belegt mindestens ein Byte (außer null u.ä.)
+                    if !empty_items && count > input.remaining() {
+                        bail!("Invalid Avro array block count {count}");
+                    }
+                    for _ in 0..count {
+                        values.push(self.decode(items, input)?);
+                    }
+                }
+                Value::Array(values)
+            }
+            Schema::Map(values) => {
+                let mut map = Map::new();
+                while let Some(count) = input.block_count()? {
+                    // Jeder Schlüssel belegt mindestens ein Byte
+                    if count > input.remaining() {
+                        bail!("Invalid Avro map block count {count}");
+                    }
+                    for _ in 0..count {
+                        let key = input.string()?;
+                        map.insert(key, self.decode(values, input)?);
+                    }
+                }
+                Value::Object(map)
+            }
+            Schema::Union(branches) => {
+                let index = input.long()?;
+                let branch = branches
+                    .get(index as usize)
+                    .ok_or_else(|| anyhow!("Invalid Avro union index {index}"))?;
+                self.decode(branch, input)?
+            }
+            Schema::Fixed(size) => Value::String(bytes_text(input.take(*size)?)),
+            Schema::Named(name) => {
+                let schema = self
+                    .named
+                    .get(name)
+                    .ok_or_else(|| anyhow!("Unknown Avro type '{name}'"))?;
+                self.decode(schema, input)?
+            }
+        })
+    }
+
+    /// Werte dieses Typs belegen 0 Bytes (null, fixed(0), Records aus solchen Feldern)
+    fn encodes_empty(&self, schema: &Schema) -> bool {
+        match schema {
+            Schema::Null | Schema::Fixed(0) => true,
+            Schema::Record(fields) => fields.iter().all(|(_, field)| self.encodes_empty(field)),
+            Schema::Named(name) => self
+                .named
+                .get(name)
+                .is_some_and(|schema| self.encodes_empty(schema)),
+            _ => false,
+        }
+    }
+}
+
+fn float_value(value: f64) -> Value {
+    serde_json::Number::from_f64(value)
+        .map_or_else(|| Value::String(value.to_string()), Value::Number)
+}
+
+fn timestamp(value: i64, per_second: i64) -> Value {
+    let nanos = (value.rem_euclid(per_second) * (1_000_000_000 / per_second)) as u32;
+    chrono::DateTime::from_timestamp(value.div_euclid(per_second), nanos)
+        .map_or(Value::from(value), |t| {
+            Value::String(t.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+        })
+}
+
+/// Bytes als Text, wenn es UTF-8 ist, sonst hexadezimal
+fn bytes_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+    }
+}
+
+/// Lesezeiger im Binärformat
+struct Input<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Input<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("Unexpected end of Avro data"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn remaining(&self) -> u64 {
+        self.bytes.len().saturating_sub(self.pos) as u64
+    }
+
+    /// int und long: ZigZag-kodiertes Varint
+    fn long(&mut self) -> Result<i64> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        bail!("Invalid Avro varint")
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.long()?;
+        if len < 0 {
+            bail!("Invalid Avro length");
+        }
+        self.take(len as usize)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        Ok(String::from_utf8_lossy(self.bytes()?).to_string())
+    }
+
+    /// Anzahl Einträge des nächsten Array-/Map-Blocks, None am Ende
+    fn block_count(&mut self) -> Result<Option<u64>> {
+        let count = self.long()?;
+        if count < 0 {
+            self.long()?; // Blockgröße in Bytes
+        }
+        Ok((count != 0).then_some(count.unsigned_abs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYNC: [u8; 16] = [7; 16];
+
+    fn long(value: i64) -> Vec<u8> {
+        let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+        let mut bytes = Vec::new();
+        loop {
+            let byte = (zigzag & 0x7f) as u8;
+            zigzag >>= 7;
+            if zigzag == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn string(text: &str) -> Vec<u8> {
+        let mut bytes = long(text.len() as i64);
+        bytes.extend(text.as_bytes());
+        bytes
+    }
+
+    fn container(schema: &str, blocks: &[(i64, Vec<u8>)]) -> Vec<u8> {
+        let mut file = MAGIC.to_vec();
+        file.extend(long(1));
+        file.extend(string("avro.schema"));
+        file.extend(string(schema));
+        file.extend(long(0));
+        file.extend(SYNC);
+        for (count, data) in blocks {
+            file.extend(long(*count));
+            file.extend(long(data.len() as i64));
+            file.extend(data);
+            file.extend(SYNC);
+        }
+        file
+    }
+
+    const PERSON: &str = r#"{"type":"record","name":"Person","fields":[
+        {"name":"name","type":"string"},{"name":"age","type":"int"}]}"#;
+
+    fn person(name: &str, age: i64) -> Vec<u8> {
+        let mut bytes = string(name);
+        bytes.extend(long(age));
+        bytes
+    }
+
+    #[test]
+    fn varint_zigzag_decoding() {
+        for value in [
+            0,
+            -1,
+            1,
+            -64,
+            63,
+            64,
+            300,
+            i32::MAX as i64,
+            i64::MIN,
+            i64::MAX,
+        ] {
+            let bytes = long(value);
+            assert_eq!(
+                Input {
+                    bytes: &bytes,
+                    pos: 0
+                }
+                .long()
+                .unwrap(),
+                value
+            );
+        }
+        assert_eq!(long(-1), [0x01]);
+        assert_eq!(long(64), [0x80, 0x01]);
+    }
+
+    #[test]
+    fn varint_rejects_overlong_and_truncated_input() {
+        let overlong = [0xff; 11];
+        assert!(Input {
+            bytes: &overlong,
+            pos: 0
+        }
+        .long()
+        .is_err());
+        let truncated = [0x80, 0x80];
+        assert!(Input {
+            bytes: &truncated,
+            pos: 0
+        }
+        .long()
+        .is_err());
+    }
+
+    #[test]
+    fn records_from_complete_file() {
+        let mut block = person("Ada", 36);
+        block.extend(person("Alan", 41));
+        let file = container(PERSON, &[(2, block)]);
+
+        let mut records = AvroRecords::default();
+        records.parse(&file, true).unwrap();
+        let set = records.finish(&file, true);
+        assert_eq!(set.info.total_rows, Some(2));
+        assert_eq!(
+            set.rows[1],
+            vec![
+                ("name".to_string(), Value::from("Alan")),
+                ("age".to_string(), Value::from(41)),
+            ]
+        );
+    }
+
+    #[test]
+    fn records_continue_after_a_split_block() {
+        let file = container(PERSON, &[(1, person("Ada", 36)), (1, person("Alan", 41))]);
+        let split = file.len() - 5;
+
+        let mut records = AvroRecords::default();
+        records.parse(&file[..split], false).unwrap();
+        assert_eq!(records.rows(), 1);
+        records.parse(&file, true).unwrap();
+        let set = records.finish(&file, true);
+        assert_eq!(set.rows.len(), 2);
+        assert_eq!(set.info.total_rows, Some(2));
+    }
+
+    #[test]
+    fn incomplete_header_waits_for_more_bytes() {
+        let file = container(PERSON, &[]);
+        let mut records = AvroRecords::default();
+        records.parse(&file[..10], false).unwrap();
+        assert_eq!(records.rows(), 0);
+        assert!(records.parse(&file[..10], true).is_err());
+    }
+
+    #[test]
+    fn array_block_count_beyond_the_data_is_rejected() {
+        let schema = r#"{"type":"array","items":"long"}"#;
+        let mut block = long(i64::MAX);
+        block.extend(long(1));
+        let file = container(schema, &[(1, block)]);
+        let mut records = AvroRecords::default();
+        let err = records.parse(&file, true).unwrap_err();
+        assert!(err.to_string().contains("block count"));
+    }
+
+    #[test]
+    fn array_of_nulls_may_have_more_items_than_bytes() {
+        let schema = r#"{"type":"array","items":"null"}"#;
+        let mut block = long(3);
+        block.extend(long(0));
+        let file = container(schema, &[(1, block)]);
+        let mut records = AvroRecords::default();
+        records.parse(&file, true).unwrap();
+        let set = records.finish(&file, true);
+        assert_eq!(
+            set.rows[0][0].1,
+            Value::Array(vec![Value::Null, Value::Null, Value::Null])
+        );
+    }
+}
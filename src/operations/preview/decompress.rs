@@ -0,0 +1,200 @@
+//! Transparente Dekompression für die Datei-Vorschau (gzip, zstd, bzip2, xz).
+//!
+//! Die komprimierten Bytes werden wie sonst in Chunks gelesen und in einen Decoder
+//! geschrieben; angezeigt wird der dekomprimierte Text. Springen (End, Offset) geht nicht,
+//! nur vorwärts lesen.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+
+/// Stück, das auf einmal in den Decoder geht. Klein, damit ein stark komprimierter
+/// Chunk nicht auf einmal zu sehr viel Text aufgeht.
+const FEED_SIZE: usize = 4 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    /// Erkennung an den Magic Bytes, sonst an der Dateiendung
+    pub fn detect(filename: &str, head: &[u8]) -> Option<Self> {
+        if head.starts_with(&[0x1f, 0x8b]) {
+            Some(Compression::Gzip)
+        } else if head.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Compression::Zstd)
+        } else if head.starts_with(b"BZh") {
+            Some(Compression::Bzip2)
+        } else if head.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Compression::Xz)
+        } else {
+            Self::from_extension(filename)
+        }
+    }
+
+    fn from_extension(filename: &str) -> Option<Self> {
+        let lower = filename.to_lowercase();
+        match lower.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("gz") | Some("gzip") => Some(Compression::Gzip),
+            Some("zst") | Some("zstd") => Some(Compression::Zstd),
+            Some("bz2") => Some(Compression::Bzip2),
+            Some("xz") => Some(Compression::Xz),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Bzip2 => "bzip2",
+            Compression::Xz => "xz",
+        }
+    }
+
+    /// Dateiname ohne Kompressions-Endung (`app.log.gz` → `app.log`), für Syntax und
+    /// Tabellen-Erkennung
+    pub fn strip_extension(filename: &str) -> &str {
+        match filename.rsplit_once('.') {
+            Some((stem, _)) if Self::from_extension(filename).is_some() => stem,
+            _ => filename,
+        }
+    }
+}
+
+enum Decoder {
+    Gzip(flate2::write::MultiGzDecoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+    Bzip2(bzip2::write::BzDecoder<Vec<u8>>),
+    Xz(liblzma::write::XzDecoder<Vec<u8>>),
+}
+
+impl Decoder {
+    fn new(compression: Compression) -> Result<Self> {
+        Ok(match compression {
+            Compression::Gzip => Decoder::Gzip(flate2::write::MultiGzDecoder::new(Vec::new())),
+            Compression::Zstd => Decoder::Zstd(zstd::stream::write::Decoder::new(Vec::new())?),
+            Compression::Bzip2 => Decoder::Bzip2(bzip2::write::BzDecoder::new(Vec::new())),
+            Compression::Xz => {
+                Decoder::Xz(liblzma::write::XzDecoder::new_multi_decoder(Vec::new()))
+            }
+        })
+    }
+
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Decoder::Gzip(d) => d,
+            Decoder::Zstd(d) => d,
+            Decoder::Bzip2(d) => d,
+            Decoder::Xz(d) => d,
+        }
+    }
+
+    fn output(&mut self) -> &mut Vec<u8> {
+        match self {
+            Decoder::Gzip(d) => d.get_mut(),
+            Decoder::Zstd(d) => d.get_mut(),
+            Decoder::Bzip2(d) => d.get_mut(),
+            Decoder::Xz(d) => d.get_mut(),
+        }
+    }
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        match self {
+            Decoder::Gzip(d) => d.try_finish(),
+            Decoder::Zstd(d) => d.flush(),
+            Decoder::Bzip2(d) => d.try_finish(),
+            Decoder::Xz(d) => d.try_finish(),
+        }
+    }
+}
+
+/// Fortschritt beim Dekomprimieren einer Vorschau-Datei
+pub struct CompressedStream {
+    pub compression: Compression,
+    pub compressed_size: i64,
+    pub compressed_offset: i64, // So weit ist die komprimierte Datei gelesen
+    input: Vec<u8>,             // Gelesen, aber noch nicht dekodiert
+    decoder: Decoder,
+    utf8_tail: Vec<u8>, // Angeschnittenes Zeichen am Ende der bisherigen Ausgabe
+    pub finished: bool,
+}
+
+impl std::fmt::Debug for CompressedStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressedStream")
+            .field("compression", &self.compression)
+            .field("compressed_size", &self.compressed_size)
+            .field("compressed_offset", &self.compressed_offset)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl CompressedStream {
+    pub fn new(compression: Compression, compressed_size: i64) -> Result<Self> {
+        Ok(CompressedStream {
+            compression,
+            compressed_size,
+            compressed_offset: 0,
+            input: Vec::new(),
+            decoder: Decoder::new(compression)?,
+            utf8_tail: Vec::new(),
+            finished: false,
+        })
+    }
+
+    /// Alles Gelesene ist dekodiert, aber die Datei geht noch weiter
+    pub fn needs_input(&self) -> bool {
+        self.input.is_empty() && self.compressed_offset < self.compressed_size
+    }
+
+    /// Nimmt die nächsten komprimierten Bytes (ab compressed_offset) auf
+    pub fn push_input(&mut self, bytes: &[u8]) {
+        self.compressed_offset += bytes.len() as i64;
+        self.input.extend_from_slice(bytes);
+    }
+
+    /// Dekodiert gelesene Bytes, bis mindestens `max_output` Bytes herauskommen oder die
+    /// Eingabe aufgebraucht ist
+    pub fn decode(&mut self, max_output: usize) -> Result<Vec<u8>> {
+        let error = || format!("Invalid {} data", self.compression.label());
+        let mut fed = 0;
+        while fed < self.input.len() && self.decoder.output().len() < max_output {
+            let end = (fed + FEED_SIZE).min(self.input.len());
+            let writer = self.decoder.writer();
+            writer
+                .write_all(&self.input[fed..end])
+                .with_context(error)?;
+            writer.flush().with_context(error)?;
+            fed = end;
+        }
+        self.input.drain(..fed);
+
+        if self.input.is_empty() && self.compressed_offset >= self.compressed_size {
+            self.decoder.finish().with_context(error)?;
+            self.finished = true;
+        }
+        Ok(std::mem::take(self.decoder.output()))
+    }
+
+    /// Dekodierte Bytes als Text; ein an der Grenze angeschnittenes Zeichen wird mit der
+    /// nächsten Ausgabe zusammengesetzt
+    pub fn decode_text(&mut self, bytes: Vec<u8>) -> Result<String> {
+        let mut bytes = std::mem::take(&mut self.utf8_tail)
+            .into_iter()
+            .chain(bytes)
+            .collect::<Vec<_>>();
+        let not_text = || anyhow::anyhow!("Decompressed data is not valid UTF-8 text");
+        let valid = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(e) if !self.finished && e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => return Err(not_text()),
+        };
+        self.utf8_tail = bytes.split_off(valid);
+        String::from_utf8(bytes).map_err(|_| not_text())
+    }
+}
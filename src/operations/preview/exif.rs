@@ -0,0 +1,359 @@
+//! Minimaler EXIF-Leser (TIFF-Struktur): liest die für die Bildvorschau
+//! interessanten Tags aus IFD0, dem Exif- und dem GPS-IFD
+
+/// Tags, die angezeigt werden: (IFD, Tag, Bezeichnung)
+const TAGS: &[(Ifd, u16, &str)] = &[
+    (Ifd::Main, 0x010F, "Make"),
+    (Ifd::Main, 0x0110, "Model"),
+    (Ifd::Exif, 0xA434, "Lens"),
+    (Ifd::Exif, 0x9003, "Taken"),
+    (Ifd::Main, 0x0132, "Modified"),
+    (Ifd::Exif, 0x829A, "Exposure"),
+    (Ifd::Exif, 0x829D, "Aperture"),
+    (Ifd::Exif, 0x8827, "ISO"),
+    (Ifd::Exif, 0x920A, "Focal length"),
+    (Ifd::Main, 0x0112, "Orientation"),
+    (Ifd::Main, 0x0131, "Software"),
+];
+
+const EXIF_IFD_POINTER: u16 = 0x8769;
+const GPS_IFD_POINTER: u16 = 0x8825;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Ifd {
+    Main,
+    Exif,
+}
+
+struct Tiff<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+/// Eintrag eines IFD: Typ, Anzahl und Position der Werte
+struct Entry {
+    kind: u16,
+    count: u32,
+    offset: usize,
+}
+
+/// Liest die bekannten EXIF-Tags als (Bezeichnung, Wert); leer bei ungültigen Daten
+pub fn parse_exif(data: &[u8]) -> Vec<(String, String)> {
+    let data = data.strip_prefix(b"Exif\0\0").unwrap_or(data);
+    let little_endian = match data.get(..4) {
+        Some([0x49, 0x49, 42, 0]) => true,
+        Some([0x4D, 0x4D, 0, 42]) => false,
+        _ => return Vec::new(),
+    };
+    let tiff = Tiff {
+        data,
+        little_endian,
+    };
+    let Some(main) = tiff.u32(4).and_then(|offset| tiff.ifd(offset as usize)) else {
+        return Vec::new();
+    };
+    let exif = find(&main, EXIF_IFD_POINTER)
+        .and_then(|e| tiff.u32(e.offset))
+        .and_then(|offset| tiff.ifd(offset as usize))
+        .unwrap_or_default();
+
+    let mut tags = Vec::new();
+    for (ifd, tag, label) in TAGS {
+        let entries = if *ifd == Ifd::Main { &main } else { &exif };
+        if let Some(value) = find(entries, *tag).and_then(|e| tiff.format(*tag, e)) {
+            tags.push((label.to_string(), value));
+        }
+    }
+
+    let gps = find(&main, GPS_IFD_POINTER)
+        .and_then(|e| tiff.u32(e.offset))
+        .and_then(|offset| tiff.ifd(offset as usize));
+    if let Some(position) = gps.and_then(|gps| tiff.gps_position(&gps)) {
+        tags.push(("GPS".to_string(), position));
+    }
+    tags
+}
+
+fn find(entries: &[(u16, Entry)], tag: u16) -> Option<&Entry> {
+    entries.iter().find(|(t, _)| *t == tag).map(|(_, e)| e)
+}
+
+impl Tiff<'_> {
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    /// Einträge eines IFD; Werte über 4 Bytes stehen an der angegebenen Position
+    fn ifd(&self, offset: usize) -> Option<Vec<(u16, Entry)>> {
+        let count = self.u16(offset)? as usize;
+        let mut entries = Vec::with_capacity(count);
+        for i in 0..count {
+            let pos = offset + 2 + i * 12;
+            let tag = self.u16(pos)?;
+            let kind = self.u16(pos + 2)?;
+            let count = self.u32(pos + 4)?;
+            let size = match kind {
+                1 | 2 | 7 => 1,
+                3 => 2,
+                4 | 9 => 4,
+                5 | 10 => 8,
+                _ => continue,
+            } * count as usize;
+            let offset = if size <= 4 {
+                pos + 8
+            } else {
+                self.u32(pos + 8)? as usize
+            };
+            entries.push((
+                tag,
+                Entry {
+                    kind,
+                    count,
+                    offset,
+                },
+            ));
+        }
+        Some(entries)
+    }
+
+    fn rational(&self, offset: usize) -> Option<(u32, u32)> {
+        Some((self.u32(offset)?, self.u32(offset + 4)?))
+    }
+
+    fn number(&self, entry: &Entry) -> Option<u32> {
+        match entry.kind {
+            3 => self.u16(entry.offset).map(u32::from),
+            4 => self.u32(entry.offset),
+            _ => None,
+        }
+    }
+
+    fn format(&self, tag: u16, entry: &Entry) -> Option<String> {
+        let value = match (tag, entry.kind) {
+            (_, 2) => {
+                let bytes = self
+                    .data
+                    .get(entry.offset..entry.offset + entry.count as usize)?;
+                String::from_utf8_lossy(bytes)
+                    .trim_end_matches('\0')
+                    .trim()
+                    .to_string()
+            }
+            (0x829A, 5) => match self.rational(entry.offset)? {
+                (_, 0) => return None,
+                (n, d) if n > 0 && n < d => format!("1/{} s", (d as f64 / n as f64).round()),
+                (n, d) => format!("{} s", n as f64 / d as f64),
+            },
+            (0x829D, 5) => match self.rational(entry.offset)? {
+                (_, 0) => return None,
+                (n, d) => format!("f/{:.1}", n as f64 / d as f64),
+            },
+            (0x920A, 5) => match self.rational(entry.offset)? {
+                (_, 0) => return None,
+                (n, d) => format!("{} mm", (n as f64 / d as f64 * 10.0).round() / 10.0),
+            },
+            (0x0112, _) => orientation_name(self.number(entry)?).to_string(),
+            (_, 3 | 4) => self.number(entry)?.to_string(),
+            _ => return None,
+        };
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Position aus dem GPS-IFD in Dezimalgrad
+    fn gps_position(&self, gps: &[(u16, Entry)]) -> Option<String> {
+        let coordinate = |ref_tag: u16, tag: u16| -> Option<f64> {
+            let entry = find(gps, tag).filter(|e| e.kind == 5 && e.count == 3)?;
+            let mut degrees = 0.0;
+            for (i, divisor) in [1.0, 60.0, 3600.0].iter().enumerate() {
+                let (n, d) = self.rational(entry.offset + i * 8)?;
+                if d != 0 {
+                    degrees += n as f64 / d as f64 / divisor;
+                }
+            }
+            let hemisphere = find(gps, ref_tag).and_then(|e| self.data.get(e.offset))?;
+            Some(if matches!(hemisphere, b'S' | b'W') {
+                -degrees
+            } else {
+                degrees
+            })
+        };
+        let latitude = coordinate(1, 2)?;
+        let longitude = coordinate(3, 4)?;
+        Some(format!("{latitude:.5}, {longitude:.5}"))
+    }
+}
+
+fn orientation_name(value: u32) -> &'static str {
+    match value {
+        1 => "normal",
+        2 => "mirrored",
+        3 => "rotated 180°",
+        4 => "mirrored vertically",
+        5 => "mirrored, rotated 90° CCW",
+        6 => "rotated 90° CW",
+        7 => "mirrored, rotated 90° CW",
+        8 => "rotated 90° CCW",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// TIFF-Struktur (little endian); Werte über 4 Bytes folgen direkt
+    /// hinter ihrem IFD
+    struct Builder {
+        data: Vec<u8>,
+    }
+
+    /// IFD-Eintrag: Tag, Typ, Anzahl, Wert (bis 4 Bytes) bzw. Daten dahinter
+    type Field = (u16, u16, u32, Vec<u8>);
+
+    impl Builder {
+        fn new() -> Self {
+            Builder {
+                data: vec![0x49, 0x49, 42, 0, 8, 0, 0, 0],
+            }
+        }
+
+        /// Hängt ein IFD an; gibt seine Position zurück
+        fn ifd(&mut self, fields: &[Field]) -> u32 {
+            let start = self.data.len();
+            let values_start = start + 2 + fields.len() * 12 + 4;
+            let mut values: Vec<u8> = Vec::new();
+            self.data.extend((fields.len() as u16).to_le_bytes());
+            for (tag, kind, count, value) in fields {
+                self.data.extend(tag.to_le_bytes());
+                self.data.extend(kind.to_le_bytes());
+                self.data.extend(count.to_le_bytes());
+                if value.len() <= 4 {
+                    let mut inline = value.clone();
+                    inline.resize(4, 0);
+                    self.data.extend(inline);
+                } else {
+                    self.data
+                        .extend(((values_start + values.len()) as u32).to_le_bytes());
+                    values.extend(value);
+                }
+            }
+            self.data.extend(0u32.to_le_bytes());
+            self.data.extend(values);
+            start as u32
+        }
+    }
+
+    fn ascii(tag: u16, text: &str) -> Field {
+        let mut value = text.as_bytes().to_vec();
+        value.push(0);
+        (tag, 2, value.len() as u32, value)
+    }
+
+    fn rational(tag: u16, n: u32, d: u32) -> Field {
+        let mut value = n.to_le_bytes().to_vec();
+        value.extend(d.to_le_bytes());
+        (tag, 5, 1, value)
+    }
+
+    fn long(tag: u16, value: u32) -> Field {
+        (tag, 4, 1, value.to_le_bytes().to_vec())
+    }
+
+    fn short(tag: u16, value: u16) -> Field {
+        (tag, 3, 1, value.to_le_bytes().to_vec())
+    }
+
+    fn rationals(tag: u16, values: &[(u32, u32)]) -> Field {
+        let value = values
+            .iter()
+            .flat_map(|(n, d)| n.to_le_bytes().into_iter().chain(d.to_le_bytes()))
+            .collect();
+        (tag, 5, values.len() as u32, value)
+    }
+
+    fn value<'a>(tags: &'a [(String, String)], label: &str) -> Option<&'a str> {
+        tags.iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn reads_main_exif_and_gps_tags() {
+        // Erst die Unter-IFDs, dann IFD0 mit den Verweisen; der Header zeigt auf IFD0
+        let mut tiff = Builder::new();
+        let exif = tiff.ifd(&[
+            rational(0x829A, 1, 250),
+            rational(0x829D, 28, 10),
+            short(0x8827, 400),
+            rational(0x920A, 350, 10),
+        ]);
+        let gps = tiff.ifd(&[
+            (1, 2, 2, b"N\0".to_vec()),
+            rationals(2, &[(52, 1), (30, 1), (0, 1)]),
+            (3, 2, 2, b"W\0".to_vec()),
+            rationals(4, &[(13, 1), (15, 1), (36, 1)]),
+        ]);
+        let main = tiff.ifd(&[
+            ascii(0x010F, "Canon"),
+            ascii(0x0110, "EOS R5"),
+            short(0x0112, 6),
+            long(EXIF_IFD_POINTER, exif),
+            long(GPS_IFD_POINTER, gps),
+        ]);
+        tiff.data[4..8].copy_from_slice(&main.to_le_bytes());
+
+        let mut data = b"Exif\0\0".to_vec();
+        data.extend(&tiff.data);
+        let tags = parse_exif(&data);
+        assert_eq!(value(&tags, "Make"), Some("Canon"));
+        assert_eq!(value(&tags, "Model"), Some("EOS R5"));
+        assert_eq!(value(&tags, "Orientation"), Some("rotated 90° CW"));
+        assert_eq!(value(&tags, "Exposure"), Some("1/250 s"));
+        assert_eq!(value(&tags, "Aperture"), Some("f/2.8"));
+        assert_eq!(value(&tags, "ISO"), Some("400"));
+        assert_eq!(value(&tags, "Focal length"), Some("35 mm"));
+        assert_eq!(value(&tags, "GPS"), Some("52.50000, -13.26000"));
+    }
+
+    #[test]
+    fn reads_big_endian_data() {
+        let mut data = vec![0x4D, 0x4D, 0, 42, 0, 0, 0, 8];
+        data.extend(1u16.to_be_bytes());
+        data.extend(0x0112u16.to_be_bytes());
+        data.extend(3u16.to_be_bytes());
+        data.extend(1u32.to_be_bytes());
+        data.extend([0, 3, 0, 0]);
+        data.extend(0u32.to_be_bytes());
+        assert_eq!(
+            parse_exif(&data),
+            [("Orientation".to_string(), "rotated 180°".to_string())]
+        );
+    }
+
+    #[test]
+    fn invalid_data_gives_no_tags() {
+        assert!(parse_exif(b"").is_empty());
+        assert!(parse_exif(b"JFIF\0\0\0\0").is_empty());
+        // IFD-Offset hinter dem Ende, Einträge über das Ende hinaus
+        assert!(parse_exif(&[0x49, 0x49, 42, 0, 0xff, 0, 0, 0]).is_empty());
+        assert!(parse_exif(&[0x49, 0x49, 42, 0, 8, 0, 0, 0, 50, 0]).is_empty());
+        // Zähler und Offset eines Wertes außerhalb der Daten
+        let mut tiff = Builder::new();
+        tiff.ifd(&[(0x010F, 2, u32::MAX, vec![0; 8])]);
+        assert!(parse_exif(&tiff.data).is_empty());
+    }
+}
@@ -1,8 +1,13 @@
+use super::decompress::{CompressedStream, Compression};
+use super::records::{self, RangeSource, RecordFormat};
 use crate::models::preview::{FileContentPreview, PreviewSource};
 use anyhow::Result;
 use std::path::Path;
 use tokio::fs::File;
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Größe eines nachgeladenen Chunks
+pub const CHUNK_SIZE: i64 = 100 * 1024;
 
 /// Lädt Text-Datei-Inhalt von Local (S3 requires S3Manager, use load_s3_file_content)
 pub async fn load_file_content(source: PreviewSource) -> Result<FileContentPreview> {
@@ -22,28 +27,19 @@ async fn load_local_file(path: &str) -> Result<FileContentPreview> {
         .unwrap_or("unknown")
         .to_string();
 
-    let mut file = File::open(path).await?;
-    let metadata = file.metadata().await?;
-    let file_size = metadata.len() as i64;
+    let file_size = tokio::fs::metadata(path).await?.len() as i64;
+    let source = PreviewSource::LocalFile {
+        path: path.to_string(),
+    };
+    if let Some(format) = RecordFormat::from_filename(&filename) {
+        let reader = RangeSource::Local(path);
+        return records::load_records(format, reader, filename, file_size, source).await;
+    }
 
     // Load first 100KB chunk (lazy loading like S3)
-    let chunk_size = 100 * 1024;
-    let load_size = (chunk_size as i64).min(file_size) as usize;
-
-    let mut buffer = vec![0u8; load_size];
-    let bytes_read = file.read(&mut buffer).await?;
-
-    let content = String::from_utf8(buffer[..bytes_read].to_vec())
-        .map_err(|_| anyhow::anyhow!("File is not valid UTF-8 text"))?;
-
-    Ok(FileContentPreview::new(
-        filename,
-        content,
-        file_size,
-        PreviewSource::LocalFile {
-            path: path.to_string(),
-        },
-    ))
+    let bytes = load_local_file_bytes(path, 0, CHUNK_SIZE.min(file_size)).await?;
+
+    preview_from_first_chunk(filename, bytes, file_size, source)
 }
 
 async fn load_s3_object(
@@ -53,27 +49,77 @@ async fn load_s3_object(
 ) -> Result<FileContentPreview> {
     let filename = extract_filename(key);
     let file_size = s3_manager.get_object_size(key).await?;
+    let source = PreviewSource::S3Object {
+        key: key.to_string(),
+        bucket: bucket.to_string(),
+    };
+    if let Some(format) = RecordFormat::from_filename(&filename) {
+        let reader = RangeSource::S3(s3_manager, key);
+        return records::load_records(format, reader, filename, file_size, source).await;
+    }
 
-    let chunk_size = 100 * 1024;
-    let load_size = if file_size < chunk_size {
-        file_size
+    let bytes = if file_size == 0 {
+        Vec::new()
     } else {
-        chunk_size
+        let load_size = CHUNK_SIZE.min(file_size);
+        s3_manager.get_object_range(key, 0, load_size - 1).await?
     };
 
-    let bytes = s3_manager.get_object_range(key, 0, load_size - 1).await?;
-    let content =
-        String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("File is not valid UTF-8 text"))?;
-
-    Ok(FileContentPreview::new(
-        filename,
-        content,
-        file_size,
-        PreviewSource::S3Object {
-            key: key.to_string(),
-            bucket: bucket.to_string(),
-        },
-    ))
+    preview_from_first_chunk(filename, bytes, file_size, source)
+}
+
+/// Text-Vorschau, oder Hex-Ansicht wenn der erste Chunk kein Text ist
+fn preview_from_first_chunk(
+    filename: String,
+    bytes: Vec<u8>,
+    file_size: i64,
+    source: PreviewSource,
+) -> Result<FileContentPreview> {
+    if let Some(compression) = Compression::detect(&filename, &bytes) {
+        return compressed_preview(filename, compression, bytes, file_size, source);
+    }
+
+    let at_eof = bytes.len() as i64 >= file_size;
+    // NUL-Bytes kommen in Text praktisch nicht vor (auch wenn sie gültiges UTF-8 sind)
+    let text = if bytes.contains(&0) {
+        None
+    } else {
+        decode_text_chunk(&bytes, false, !at_eof).ok()
+    };
+
+    Ok(match text {
+        Some(chunk) => FileContentPreview::new(filename, chunk.text, file_size, source),
+        None => FileContentPreview::new_hex(filename, bytes, file_size, source),
+    })
+}
+
+/// Vorschau einer komprimierten Datei: dekomprimiert den ersten Chunk (weitere beim
+/// Scrollen). Ist der Inhalt kein Text, zeigt die Hex-Ansicht den ersten Teil.
+fn compressed_preview(
+    filename: String,
+    compression: Compression,
+    bytes: Vec<u8>,
+    file_size: i64,
+    source: PreviewSource,
+) -> Result<FileContentPreview> {
+    let mut stream = CompressedStream::new(compression, file_size)?;
+    stream.push_input(&bytes);
+    let decoded = stream.decode(CHUNK_SIZE as usize)?;
+
+    let text = if decoded.contains(&0) {
+        None
+    } else {
+        stream.decode_text(decoded.clone()).ok()
+    };
+    Ok(match text {
+        Some(text) => FileContentPreview::new_compressed(filename, text, stream, source),
+        None => {
+            let size = decoded.len() as i64;
+            let mut preview = FileContentPreview::new_hex(filename, decoded, size, source);
+            preview.compressed = Some(stream);
+            preview
+        }
+    })
 }
 
 /// Lädt S3-Datei mit S3Manager (für öffentliche API)
@@ -85,90 +131,63 @@ pub async fn load_s3_file_content(
     load_s3_object(key, bucket, s3_manager).await
 }
 
-/// Lädt nächsten Chunk für lokale Datei
-pub async fn load_more_local_file_content(
-    path: &str,
-    byte_offset: i64,
-    file_size: i64,
-) -> Result<String> {
-    if byte_offset >= file_size {
-        return Ok(String::new());
+/// Liest bis zu `len` Bytes ab `start` aus einer lokalen Datei
+pub async fn load_local_file_bytes(path: &str, start: i64, len: i64) -> Result<Vec<u8>> {
+    if len <= 0 {
+        return Ok(Vec::new());
     }
 
     let mut file = File::open(path).await?;
-    let chunk_size = 100 * 1024;
-    let bytes_to_read = ((file_size - byte_offset) as usize).min(chunk_size);
-
-    // Seek to offset
-    use tokio::io::AsyncSeekExt;
-    file.seek(std::io::SeekFrom::Start(byte_offset as u64))
-        .await?;
+    file.seek(std::io::SeekFrom::Start(start as u64)).await?;
 
-    let mut buffer = vec![0u8; bytes_to_read];
-    let bytes_read = file.read(&mut buffer).await?;
+    let mut buffer = Vec::with_capacity(len as usize);
+    file.take(len as u64).read_to_end(&mut buffer).await?;
 
-    let content = String::from_utf8(buffer[..bytes_read].to_vec())
-        .map_err(|_| anyhow::anyhow!("File chunk is not valid UTF-8 text"))?;
-
-    Ok(content)
+    Ok(buffer)
 }
 
-/// Lädt einen bestimmten Bereich einer lokalen Datei
-pub async fn load_local_file_range(
-    path: &str,
-    start_offset: i64,
-    bytes_to_read: i64,
-) -> Result<String> {
-    if bytes_to_read == 0 {
-        return Ok(String::new());
-    }
-
-    let mut file = File::open(path).await?;
-
-    // Seek to start offset
-    use tokio::io::AsyncSeekExt;
-    file.seek(std::io::SeekFrom::Start(start_offset as u64))
-        .await?;
-
-    let mut buffer = vec![0u8; bytes_to_read as usize];
-    let bytes_read = file.read(&mut buffer).await?;
-
-    let content = String::from_utf8(buffer[..bytes_read].to_vec())
-        .map_err(|_| anyhow::anyhow!("File range is not valid UTF-8 text"))?;
-
-    Ok(content)
+/// Als UTF-8 dekodierter Chunk; `start..end` sind die Bytes des Chunks, die im Text stecken
+#[derive(Debug)]
+pub struct TextChunk {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
 }
 
-/// Lädt die letzten 100KB einer lokalen Datei (für "END" Taste)
-pub async fn load_local_file_tail(path: &str, file_size: i64) -> Result<String> {
-    if file_size == 0 {
-        return Ok(String::new());
-    }
+/// Fehler von `decode_text_chunk`: die Bytes sind kein gültiges UTF-8
+#[derive(Debug)]
+pub struct NotText;
 
-    let mut file = File::open(path).await?;
-    let chunk_size = 100 * 1024;
+impl std::fmt::Display for NotText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "File is not valid UTF-8 text")
+    }
+}
 
-    // Calculate start position (last 100KB or from beginning)
-    let start_offset = if file_size > chunk_size {
-        file_size - chunk_size
+impl std::error::Error for NotText {}
+
+/// Dekodiert einen Chunk als UTF-8. An den Chunk-Grenzen angeschnittene Zeichen sind kein
+/// Fehler: führende Folge-Bytes (`trim_start`) und ein unvollständiges Zeichen am Ende
+/// (`trim_end`) werden weggelassen und beim nächsten Chunk mitgeladen.
+pub fn decode_text_chunk(bytes: &[u8], trim_start: bool, trim_end: bool) -> Result<TextChunk> {
+    let start = if trim_start {
+        bytes
+            .iter()
+            .take(3)
+            .take_while(|b| (**b & 0xC0) == 0x80)
+            .count()
     } else {
         0
     };
 
-    let bytes_to_read = (file_size - start_offset) as usize;
-
-    // Seek to start offset
-    use tokio::io::AsyncSeekExt;
-    file.seek(std::io::SeekFrom::Start(start_offset as u64))
-        .await?;
-
-    let mut buffer = vec![0u8; bytes_to_read];
-    let bytes_read = file.read(&mut buffer).await?;
-
-    let content = String::from_utf8(buffer[..bytes_read].to_vec())
-        .map_err(|_| anyhow::anyhow!("File tail is not valid UTF-8 text"))?;
+    let end = match std::str::from_utf8(&bytes[start..]) {
+        Ok(_) => bytes.len(),
+        Err(e) if trim_end && e.error_len().is_none() => start + e.valid_up_to(),
+        Err(_) => return Err(NotText.into()),
+    };
 
-    Ok(content)
+    let text = String::from_utf8(bytes[start..end].to_vec()).map_err(|_| NotText)?;
+    Ok(TextChunk { text, start, end })
 }
 
 /// Extrahiert Dateinamen aus Pfad oder Key
@@ -179,3 +198,38 @@ pub fn extract_filename(path_or_key: &str) -> String {
         .unwrap_or("unknown")
         .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_chunk_drops_characters_cut_at_both_ends() {
+        // "äöü" mit abgeschnittenem ersten und letzten Zeichen
+        let bytes = "äöü".as_bytes();
+        let chunk = decode_text_chunk(&bytes[1..5], true, true).unwrap();
+        assert_eq!(chunk.text, "ö");
+        assert_eq!((chunk.start, chunk.end), (1, 3));
+    }
+
+    #[test]
+    fn text_chunk_keeps_whole_characters() {
+        let chunk = decode_text_chunk("a€b".as_bytes(), true, true).unwrap();
+        assert_eq!(chunk.text, "a€b");
+        assert_eq!((chunk.start, chunk.end), (0, 5));
+    }
+
+    #[test]
+    fn cut_character_is_an_error_without_trimming() {
+        let bytes = &"€".as_bytes()[..2];
+        let err = decode_text_chunk(bytes, false, false).unwrap_err();
+        assert!(err.is::<NotText>());
+        assert!(decode_text_chunk(&"€".as_bytes()[1..], false, true).is_err());
+    }
+
+    #[test]
+    fn invalid_bytes_inside_the_chunk_are_not_text() {
+        let err = decode_text_chunk(b"abc\xffdef", true, true).unwrap_err();
+        assert!(err.is::<NotText>());
+    }
+}
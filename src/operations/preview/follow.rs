@@ -0,0 +1,68 @@
+//! Follow-Modus der Datei-Vorschau: Größenabfrage und Nachladen im Hintergrund
+
+use super::file_loader::{decode_text_chunk, CHUNK_SIZE};
+use super::search::ChunkReader;
+use anyhow::Result;
+
+/// Wächst die Datei zwischen zwei Abfragen um mehr, wird nur das neue Ende geladen
+const FOLLOW_MAX_APPEND: i64 = 10 * CHUNK_SIZE;
+
+/// Ergebnis einer Follow-Abfrage
+#[derive(Debug)]
+pub enum FollowLoad {
+    Unchanged,
+    /// Neuer Text schließt an den geladenen Inhalt an
+    Append {
+        text: String,
+        end: i64,
+        size: i64,
+    },
+    /// Datei ist geschrumpft (neu geschrieben) oder stark gewachsen: ihr Ende ersetzt
+    /// den geladenen Inhalt
+    Tail {
+        text: String,
+        start: i64,
+        end: i64,
+        size: i64,
+        shrunk: bool,
+    },
+}
+
+/// Fragt die Größe ab und lädt, was seit `byte_offset` (Ende des geladenen Inhalts)
+/// dazugekommen ist
+pub async fn load_follow(
+    reader: ChunkReader,
+    byte_offset: i64,
+    file_size: i64,
+) -> Result<FollowLoad> {
+    let size = reader.size().await?;
+    if size == file_size && byte_offset >= file_size {
+        return Ok(FollowLoad::Unchanged);
+    }
+
+    if size < file_size || size - byte_offset > FOLLOW_MAX_APPEND {
+        let start = size.saturating_sub(CHUNK_SIZE).max(0);
+        let (text, start, end) = read_text(&reader, start, size).await?;
+        return Ok(FollowLoad::Tail {
+            text,
+            start,
+            end,
+            size,
+            shrunk: size < file_size,
+        });
+    }
+
+    let (text, _, end) = read_text(&reader, byte_offset, size).await?;
+    Ok(FollowLoad::Append { text, end, size })
+}
+
+/// Text `start..end`; ein am Ende angeschnittenes Zeichen wird beim nächsten Mal mitgeladen
+async fn read_text(reader: &ChunkReader, start: i64, end: i64) -> Result<(String, i64, i64)> {
+    let bytes = reader.read(start, end).await?;
+    let chunk = decode_text_chunk(&bytes, start > 0, true)?;
+    Ok((
+        chunk.text,
+        start + chunk.start as i64,
+        start + chunk.end as i64,
+    ))
+}
@@ -1,7 +1,14 @@
-use crate::models::preview::{ImagePreview, PreviewSource};
-use anyhow::Result;
-use image::GenericImageView;
+use crate::models::preview::{ImageInfo, ImagePreview, PreviewSource};
+use anyhow::{anyhow, Result};
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, RgbaImage};
+use resvg::{tiny_skia, usvg};
+use std::io::Cursor;
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Größte Kantenlänge des Anzeigebildes; größere Bilder werden beim Laden verkleinert
+const MAX_DISPLAY_EDGE: u32 = 2048;
 
 /// Lädt Bild von Local (S3 requires S3Manager, use load_s3_image)
 pub async fn load_image(source: PreviewSource) -> Result<ImagePreview> {
@@ -10,9 +17,9 @@ pub async fn load_image(source: PreviewSource) -> Result<ImagePreview> {
             let path_clone = path.clone();
             load_local_image(&path, PreviewSource::LocalFile { path: path_clone }).await
         }
-        PreviewSource::S3Object { .. } => Err(anyhow::anyhow!(
-            "S3 images require S3Manager, use load_s3_image"
-        )),
+        PreviewSource::S3Object { .. } => {
+            Err(anyhow!("S3 images require S3Manager, use load_s3_image"))
+        }
     }
 }
 
@@ -30,26 +37,11 @@ pub async fn load_s3_image(
     // Download entire image (images need to be complete to decode)
     let bytes = s3_manager.get_object_range(key, 0, file_size - 1).await?;
 
-    // Load image from memory
-    let img = image::load_from_memory(&bytes)?;
-    let dimensions = img.dimensions();
-
-    // Encode to PNG for ratatui-image
-    let mut image_data = Vec::new();
-    img.write_to(
-        &mut std::io::Cursor::new(&mut image_data),
-        image::ImageFormat::Png,
-    )?;
-
-    Ok(ImagePreview::new(
-        filename,
-        PreviewSource::S3Object {
-            key: key.to_string(),
-            bucket: bucket.to_string(),
-        },
-        image_data,
-        Some(dimensions),
-    ))
+    let source = PreviewSource::S3Object {
+        key: key.to_string(),
+        bucket: bucket.to_string(),
+    };
+    tokio::task::spawn_blocking(move || decode_image(filename, source, bytes)).await?
 }
 
 async fn load_local_image(path: &str, source: PreviewSource) -> Result<ImagePreview> {
@@ -60,30 +52,193 @@ async fn load_local_image(path: &str, source: PreviewSource) -> Result<ImagePrev
         .unwrap_or("unknown")
         .to_string();
 
-    // Load image with image crate
-    let img = image::open(path)?;
-    let dimensions = img.dimensions();
+    let bytes = tokio::fs::read(path).await?;
+    tokio::task::spawn_blocking(move || decode_image(filename, source, bytes)).await?
+}
+
+/// Größe der Bilddatei vor dem Laden (für das Größenlimit)
+pub async fn image_file_size(
+    source: &PreviewSource,
+    s3_manager: Option<&crate::operations::s3::S3Manager>,
+) -> Result<u64> {
+    match source {
+        PreviewSource::LocalFile { path } => Ok(tokio::fs::metadata(path).await?.len()),
+        PreviewSource::S3Object { key, .. } => match s3_manager {
+            Some(s3_manager) => Ok(s3_manager.get_object_size(key).await?.max(0) as u64),
+            None => Err(anyhow!("No S3 connection available")),
+        },
+    }
+}
+
+/// Dekodiert das Bild, liest Format, Farbtiefe und EXIF und wendet die EXIF-Ausrichtung an
+fn decode_image(filename: String, source: PreviewSource, bytes: Vec<u8>) -> Result<ImagePreview> {
+    if is_svg_file(&filename) {
+        return rasterize_svg(filename, source, bytes);
+    }
+    let reader = ImageReader::new(Cursor::new(bytes.as_slice())).with_guessed_format()?;
+    let format = reader
+        .format()
+        .ok_or_else(|| anyhow!("Unknown image format"))?;
+    let mut decoder = reader.into_decoder()?;
+
+    // TIFF-Dateien sind selbst eine TIFF-Struktur mit den Tags in IFD0
+    let exif = match format {
+        ImageFormat::Tiff => super::exif::parse_exif(&bytes),
+        _ => decoder
+            .exif_metadata()
+            .ok()
+            .flatten()
+            .map(|data| super::exif::parse_exif(&data))
+            .unwrap_or_default(),
+    };
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let color = decoder.original_color_type();
+
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    let dimensions = (image.width(), image.height());
+    if dimensions.0 > MAX_DISPLAY_EDGE || dimensions.1 > MAX_DISPLAY_EDGE {
+        image = image.resize(
+            MAX_DISPLAY_EDGE,
+            MAX_DISPLAY_EDGE,
+            image::imageops::FilterType::Triangle,
+        );
+    }
+
+    let info = ImageInfo {
+        format: format_name(format),
+        color: format!("{color:?}"),
+        bits_per_pixel: color.bits_per_pixel(),
+        file_size: bytes.len() as u64,
+        exif,
+    };
+    Ok(ImagePreview::new(filename, source, image, dimensions, info))
+}
+
+/// Rastert ein SVG (auch gzip-komprimiert) so, dass die längere Kante MAX_DISPLAY_EDGE
+/// Pixel hat - Vektorgrafiken bleiben dadurch auch gezoomt scharf
+fn rasterize_svg(filename: String, source: PreviewSource, bytes: Vec<u8>) -> Result<ImagePreview> {
+    let options = usvg::Options {
+        fontdb: system_fonts(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_data(&bytes, &options)?;
+    let size = tree.size();
+    let scale = MAX_DISPLAY_EDGE as f32 / size.width().max(size.height());
+    let width = ((size.width() * scale).round() as u32).max(1);
+    let height = ((size.height() * scale).round() as u32).max(1);
 
-    // Encode to PNG bytes for ratatui-image
-    let mut image_data = Vec::new();
-    img.write_to(
-        &mut std::io::Cursor::new(&mut image_data),
-        image::ImageFormat::Png,
-    )?;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| anyhow!("Invalid SVG size {width}x{height}"))?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    // tiny-skia arbeitet mit vormultipliziertem Alpha
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    let image = RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| anyhow!("Failed to rasterize SVG"))?;
 
+    let dimensions = (
+        size.width().round().max(1.0) as u32,
+        size.height().round().max(1.0) as u32,
+    );
+    let info = ImageInfo {
+        format: "SVG".to_string(),
+        color: "Rgba8 (rasterized)".to_string(),
+        bits_per_pixel: 32,
+        file_size: bytes.len() as u64,
+        exif: Vec::new(),
+    };
     Ok(ImagePreview::new(
         filename,
         source,
-        image_data,
-        Some(dimensions),
+        DynamicImage::ImageRgba8(image),
+        dimensions,
+        info,
     ))
 }
 
+/// Systemschriften für Text in SVGs (einmal geladen)
+fn system_fonts() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fonts = usvg::fontdb::Database::new();
+            fonts.load_system_fonts();
+            Arc::new(fonts)
+        })
+        .clone()
+}
+
+fn is_svg_file(filename: &str) -> bool {
+    let ext = filename.split('.').next_back().unwrap_or("").to_lowercase();
+    matches!(ext.as_str(), "svg" | "svgz")
+}
+
+fn format_name(format: ImageFormat) -> String {
+    match format {
+        ImageFormat::Jpeg => "JPEG".to_string(),
+        ImageFormat::WebP => "WebP".to_string(),
+        other => format!("{other:?}").to_uppercase(),
+    }
+}
+
 /// Prüft ob Datei ein Bild ist
 pub fn is_image_file(filename: &str) -> bool {
     let ext = filename.split('.').next_back().unwrap_or("").to_lowercase();
     matches!(
         ext.as_str(),
-        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp"
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "tif" | "tiff" | "svg" | "svgz"
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+
+    const SVG: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20">
+        <rect width="20" height="20" fill="red"/>
+    </svg>"#;
+
+    fn source() -> PreviewSource {
+        PreviewSource::LocalFile {
+            path: "test.svg".to_string(),
+        }
+    }
+
+    #[test]
+    fn svg_is_rasterized_to_display_size() {
+        let preview = decode_image("test.svg".to_string(), source(), SVG.to_vec()).unwrap();
+        assert_eq!(preview.dimensions, (40, 20));
+        assert_eq!(preview.info.format, "SVG");
+        assert_eq!(
+            preview.image.dimensions(),
+            (MAX_DISPLAY_EDGE, MAX_DISPLAY_EDGE / 2)
+        );
+        // Linke Hälfte rot und deckend, rechte Hälfte transparent
+        assert_eq!(preview.image.get_pixel(10, 10).0, [255, 0, 0, 255]);
+        assert_eq!(preview.image.get_pixel(MAX_DISPLAY_EDGE - 10, 10).0[3], 0);
+    }
+
+    #[test]
+    fn invalid_svg_is_an_error() {
+        assert!(decode_image("bad.svg".to_string(), source(), b"<svg".to_vec()).is_err());
+    }
+
+    #[test]
+    fn svg_extensions_are_images() {
+        assert!(is_image_file("logo.SVG"));
+        assert!(is_image_file("logo.svgz"));
+        assert!(!is_image_file("movie.avif"));
+    }
+}
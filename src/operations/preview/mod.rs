@@ -1,5 +1,11 @@
+pub mod avro;
+pub mod decompress;
+pub mod exif;
 pub mod file_loader;
+pub mod follow;
 pub mod image_loader;
+pub mod records;
+pub mod search;
 
 pub use file_loader::load_file_content;
 pub use image_loader::load_image;
@@ -0,0 +1,431 @@
+use super::avro;
+use crate::models::preview::{
+    FileContentPreview, PreviewSource, RecordInfo, RecordSet, RECORD_PREVIEW_ROWS,
+};
+use crate::operations::s3::S3Manager;
+use anyhow::{bail, Result};
+use bytes::{Buf, Bytes};
+use parquet::basic::{ConvertedType, Repetition};
+use parquet::file::metadata::ParquetMetaDataReader;
+use parquet::file::reader::{ChunkReader, FileReader, Length, SerializedFileReader};
+use parquet::schema::types::Type;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Höchstens so viele Bytes werden für die Datensätze gelesen (Parquet: je Datei,
+/// Avro/JSON Lines: ab Dateianfang)
+const READ_LIMIT: i64 = 16 * 1024 * 1024;
+
+/// Avro und JSON Lines werden in Schritten dieser Größe gelesen
+const READ_STEP: i64 = 1024 * 1024;
+
+/// Dateiformate mit strukturierter Vorschau
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordFormat {
+    Parquet,
+    Avro,
+    JsonLines,
+}
+
+impl RecordFormat {
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let lower = filename.to_lowercase();
+        match lower.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("parquet") | Some("parq") => Some(Self::Parquet),
+            Some("avro") => Some(Self::Avro),
+            Some("jsonl") | Some("ndjson") => Some(Self::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+/// Liest Byte-Bereiche aus einer lokalen Datei oder einem S3-Objekt
+pub enum RangeSource<'a> {
+    Local(&'a str),
+    S3(&'a S3Manager, &'a str),
+}
+
+impl RangeSource<'_> {
+    async fn read(&self, start: i64, len: i64) -> Result<Vec<u8>> {
+        if len <= 0 {
+            return Ok(Vec::new());
+        }
+        match self {
+            RangeSource::Local(path) => {
+                super::file_loader::load_local_file_bytes(path, start, len).await
+            }
+            RangeSource::S3(manager, key) => {
+                manager.get_object_range(key, start, start + len - 1).await
+            }
+        }
+    }
+}
+
+/// Schema, Kennzahlen und die ersten Datensätze einer Parquet-, Avro- oder JSON-Lines-Datei
+pub async fn load_records(
+    format: RecordFormat,
+    reader: RangeSource<'_>,
+    filename: String,
+    file_size: i64,
+    source: PreviewSource,
+) -> Result<FileContentPreview> {
+    let mut records = match format {
+        RecordFormat::Parquet => load_parquet(&reader, file_size).await?,
+        RecordFormat::Avro => read_prefix(&reader, file_size, avro::AvroRecords::default()).await?,
+        RecordFormat::JsonLines => read_prefix(&reader, file_size, JsonLines::default()).await?,
+    };
+    records.info.file_size = file_size;
+    Ok(FileContentPreview::new_records(filename, records, source))
+}
+
+/// Zerlegt den Dateianfang schrittweise (Avro, JSON Lines); die Position bleibt
+/// zwischen den Leseschritten erhalten, jeder Schritt verarbeitet nur die neuen Bytes
+pub trait PrefixParser {
+    /// Verarbeitet die Bytes ab der zuletzt erreichten Position. `complete`: die Bytes
+    /// reichen bis zum Dateiende.
+    fn parse(&mut self, bytes: &[u8], complete: bool) -> Result<()>;
+
+    /// Anzahl bisher gelesener Datensätze
+    fn rows(&self) -> usize;
+
+    fn finish(self, bytes: &[u8], complete: bool) -> RecordSet;
+}
+
+/// Liest den Dateianfang schrittweise, bis genug Datensätze zerlegt sind (oder die
+/// Datei bzw. READ_LIMIT erreicht ist)
+async fn read_prefix(
+    reader: &RangeSource<'_>,
+    file_size: i64,
+    mut parser: impl PrefixParser,
+) -> Result<RecordSet> {
+    let mut bytes = Vec::new();
+    loop {
+        let start = bytes.len() as i64;
+        let len = READ_STEP.min(file_size - start);
+        let chunk = reader.read(start, len).await?;
+        let complete = chunk.is_empty() || start + chunk.len() as i64 >= file_size;
+        bytes.extend(chunk);
+        parser.parse(&bytes, complete)?;
+        if complete || parser.rows() >= RECORD_PREVIEW_ROWS || bytes.len() as i64 >= READ_LIMIT {
+            return Ok(parser.finish(&bytes, complete));
+        }
+    }
+}
+
+/// JSON Lines: ein JSON-Wert je Zeile; Objekte werden auf Spalten verteilt
+#[derive(Default)]
+struct JsonLines {
+    pos: usize, // Ende der letzten vollständig gelesenen Zeile
+    rows: Vec<Vec<(String, Value)>>,
+    invalid: usize,
+    truncated: bool, // nicht alle gelesenen Zeilen übernommen
+}
+
+impl PrefixParser for JsonLines {
+    fn parse(&mut self, bytes: &[u8], complete: bool) -> Result<()> {
+        // Die letzte Zeile ist ohne Dateiende ggf. angeschnitten
+        let end = if complete {
+            bytes.len()
+        } else {
+            bytes[self.pos..]
+                .iter()
+                .rposition(|b| *b == b'\n')
+                .map_or(self.pos, |i| self.pos + i + 1)
+        };
+        let text = String::from_utf8_lossy(&bytes[self.pos..end]);
+        self.pos = end;
+
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            if self.rows.len() >= RECORD_PREVIEW_ROWS {
+                self.truncated = true;
+                break;
+            }
+            let row = match serde_json::from_str::<OrderedObject>(line) {
+                Ok(object) => object.0,
+                Err(_) => {
+                    let value = serde_json::from_str(line).unwrap_or_else(|_| {
+                        self.invalid += 1;
+                        Value::String(line.to_string())
+                    });
+                    vec![("value".to_string(), value)]
+                }
+            };
+            self.rows.push(row);
+        }
+        Ok(())
+    }
+
+    fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn finish(self, _bytes: &[u8], complete: bool) -> RecordSet {
+        let whole_file = complete && !self.truncated;
+        let read = if whole_file {
+            "whole file read".to_string()
+        } else {
+            format!("first {} KB read", self.pos / 1024)
+        };
+        let mut summary = format!("{} records ({read})", self.rows.len());
+        if self.invalid > 0 {
+            summary.push_str(&format!(" | {} line(s) not valid JSON", self.invalid));
+        }
+        let total_rows = whole_file.then_some(self.rows.len() as u64);
+        RecordSet {
+            info: RecordInfo {
+                format: "JSON LINES",
+                summary,
+                fields: Vec::new(),
+                total_rows,
+                file_size: 0, // setzt load_records
+            },
+            rows: self.rows,
+        }
+    }
+}
+
+/// JSON-Objekt mit Schlüsseln in Datei-Reihenfolge
+struct OrderedObject(Vec<(String, Value)>);
+
+impl<'de> serde::Deserialize<'de> for OrderedObject {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = OrderedObject;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<OrderedObject, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    fields.push(entry);
+                }
+                Ok(OrderedObject(fields))
+            }
+        }
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+/// Parquet: Footer per Range-Request, dann nur die Spalten-Chunks der ersten Row Groups
+/// (je Chunk höchstens ein Anteil von READ_LIMIT - das reicht für die ersten Pages)
+async fn load_parquet(reader: &RangeSource<'_>, file_size: i64) -> Result<RecordSet> {
+    if file_size < 12 {
+        bail!("File is too small to be a Parquet file");
+    }
+    let tail = reader.read(file_size - 8, 8).await?;
+    match &tail[4..] {
+        b"PAR1" => {}
+        b"PARE" => bail!("Encrypted Parquet files are not supported"),
+        _ => bail!("Not a Parquet file (footer magic missing)"),
+    }
+    let metadata_len = u32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]) as i64;
+    let metadata_start = file_size - 8 - metadata_len;
+    if metadata_start < 4 {
+        bail!("Invalid Parquet footer");
+    }
+    let mut footer = reader.read(metadata_start, metadata_len).await?;
+    let metadata = ParquetMetaDataReader::decode_metadata(&footer)?;
+    footer.extend(tail);
+
+    let mut file = SparseFile {
+        len: file_size as u64,
+        ranges: vec![(metadata_start as u64, Bytes::from(footer))],
+    };
+    let mut rows = 0;
+    let mut budget = READ_LIMIT as u64;
+    for group in metadata.row_groups() {
+        if rows >= RECORD_PREVIEW_ROWS as i64 || budget == 0 {
+            break;
+        }
+        let ranges: Vec<(u64, u64)> = group.columns().iter().map(|c| c.byte_range()).collect();
+        let total: u64 = ranges.iter().map(|(_, len)| len).sum();
+        let start = ranges.iter().map(|(start, _)| *start).min().unwrap_or(0);
+        let end = ranges.iter().map(|(s, len)| s + len).max().unwrap_or(0);
+        if end - start <= budget {
+            // Chunks liegen hintereinander: ein Request für die ganze Row Group
+            file.fetch(reader, start, end - start).await?;
+            budget -= end - start;
+        } else {
+            let share = (budget / ranges.len().max(1) as u64).max(64 * 1024);
+            for (start, len) in ranges {
+                file.fetch(reader, start, len.min(share)).await?;
+            }
+            budget = budget.saturating_sub(total.min(share * group.num_columns() as u64));
+        }
+        rows += group.num_rows();
+    }
+
+    let file_metadata = metadata.file_metadata();
+    let codecs: BTreeSet<String> = metadata
+        .row_groups()
+        .iter()
+        .flat_map(|g| g.columns())
+        .map(|c| c.compression().to_string())
+        .collect();
+    let mut summary = format!(
+        "{} rows in {} row groups | {} columns | Compression: {}",
+        file_metadata.num_rows(),
+        metadata.num_row_groups(),
+        file_metadata.schema_descr().num_columns(),
+        codecs.into_iter().collect::<Vec<_>>().join(", ")
+    );
+    if let Some(created_by) = file_metadata.created_by() {
+        summary.push_str(&format!(" | {created_by}"));
+    }
+    let fields = file_metadata
+        .schema_descr()
+        .root_schema()
+        .get_fields()
+        .iter()
+        .map(|f| (f.name().to_string(), parquet_type(f)))
+        .collect();
+    let total_rows = Some(file_metadata.num_rows() as u64);
+
+    // Ein nicht geladener Bereich beendet die Datensätze (Zeilen davor bleiben)
+    let file_reader = SerializedFileReader::new(file)?;
+    let mut records = Vec::new();
+    for row in file_reader.get_row_iter(None)?.take(RECORD_PREVIEW_ROWS) {
+        let Ok(row) = row else {
+            break;
+        };
+        records.push(
+            row.get_column_iter()
+                .map(|(name, field)| (name.clone(), field.to_json_value()))
+                .collect(),
+        );
+    }
+
+    Ok(RecordSet {
+        info: RecordInfo {
+            format: "PARQUET",
+            summary,
+            fields,
+            total_rows,
+            file_size,
+        },
+        rows: records,
+    })
+}
+
+/// Typ eines Parquet-Feldes, z.B. "optional BYTE_ARRAY UTF8" oder "optional group LIST"
+fn parquet_type(field: &Type) -> String {
+    let info = field.get_basic_info();
+    let repetition = if info.has_repetition() {
+        match info.repetition() {
+            Repetition::REQUIRED => "",
+            Repetition::OPTIONAL => "optional ",
+            Repetition::REPEATED => "repeated ",
+        }
+    } else {
+        ""
+    };
+    let base = if field.is_primitive() {
+        field.get_physical_type().to_string()
+    } else {
+        format!("group ({} fields)", field.get_fields().len())
+    };
+    match info.converted_type() {
+        ConvertedType::NONE => format!("{repetition}{base}"),
+        converted => format!("{repetition}{base} {converted}"),
+    }
+}
+
+/// Parquet-Datei, von der nur einzelne Bereiche im Speicher liegen
+struct SparseFile {
+    len: u64,
+    ranges: Vec<(u64, Bytes)>, // Datei-Offset und Inhalt
+}
+
+impl SparseFile {
+    async fn fetch(&mut self, reader: &RangeSource<'_>, start: u64, len: u64) -> Result<()> {
+        if len > 0 {
+            let bytes = reader.read(start as i64, len as i64).await?;
+            self.ranges.push((start, Bytes::from(bytes)));
+        }
+        Ok(())
+    }
+
+    /// Geladene Bytes ab `start` bis zum Ende des Bereichs, der `start` enthält
+    fn slice_from(&self, start: u64) -> parquet::errors::Result<Bytes> {
+        self.ranges
+            .iter()
+            .find(|(offset, bytes)| start >= *offset && start < offset + bytes.len() as u64)
+            .map(|(offset, bytes)| bytes.slice((start - offset) as usize..))
+            .ok_or_else(|| {
+                parquet::errors::ParquetError::EOF(format!("Offset {start} was not loaded"))
+            })
+    }
+}
+
+impl Length for SparseFile {
+    fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+impl ChunkReader for SparseFile {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+        Ok(self.slice_from(start)?.reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        let bytes = self.slice_from(start)?;
+        if bytes.len() < length {
+            return Err(parquet::errors::ParquetError::EOF(format!(
+                "Range at {start} was only partly loaded"
+            )));
+        }
+        Ok(bytes.slice(..length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_lines_keep_a_split_line_for_the_next_step() {
+        let text = b"{\"b\":1,\"a\":2}\n[1,2]\nnot json\n{\"a\":3}";
+        let mut parser = JsonLines::default();
+        parser.parse(&text[..18], false).unwrap();
+        assert_eq!(parser.rows(), 1);
+        parser.parse(text, true).unwrap();
+
+        let set = parser.finish(text, true);
+        assert_eq!(set.info.total_rows, Some(4));
+        assert!(set.info.summary.contains("1 line(s) not valid JSON"));
+        assert_eq!(
+            set.rows[0],
+            vec![
+                ("b".to_string(), Value::from(1)),
+                ("a".to_string(), Value::from(2)),
+            ]
+        );
+        assert_eq!(
+            set.rows[1],
+            vec![("value".to_string(), serde_json::json!([1, 2]))]
+        );
+        assert_eq!(
+            set.rows[2],
+            vec![("value".to_string(), Value::from("not json"))]
+        );
+    }
+
+    #[test]
+    fn json_lines_stop_at_the_preview_limit() {
+        let text = "{}\n".repeat(RECORD_PREVIEW_ROWS + 5);
+        let mut parser = JsonLines::default();
+        parser.parse(text.as_bytes(), true).unwrap();
+        let set = parser.finish(text.as_bytes(), true);
+        assert_eq!(set.rows.len(), RECORD_PREVIEW_ROWS);
+        assert_eq!(set.info.total_rows, None);
+    }
+}
@@ -0,0 +1,201 @@
+//! Suche in der Datei-Vorschau über noch nicht geladene Chunks hinweg
+
+use crate::operations::s3::S3Manager;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+pub const SEARCH_CHUNK_SIZE: i64 = super::file_loader::CHUNK_SIZE;
+/// So viel lädt eine Suche (`/`, `?`, `n`) höchstens nach, bevor sie anhält
+pub const SEARCH_LOAD_LIMIT: i64 = 64 * 1024 * 1024;
+
+/// Quelle der nachzuladenden Chunks
+#[derive(Clone)]
+pub enum ChunkReader {
+    Local(String),
+    S3 {
+        manager: Box<S3Manager>,
+        key: String,
+    },
+}
+
+impl ChunkReader {
+    /// Liest die Bytes `start..end` (end exklusiv)
+    pub(crate) async fn read(&self, start: i64, end: i64) -> Result<Vec<u8>> {
+        match self {
+            ChunkReader::Local(path) => {
+                let mut file = tokio::fs::File::open(path).await?;
+                file.seek(std::io::SeekFrom::Start(start as u64)).await?;
+                let mut buffer = vec![0u8; (end - start) as usize];
+                file.read_exact(&mut buffer)
+                    .await
+                    .context("File changed while reading")?;
+                Ok(buffer)
+            }
+            ChunkReader::S3 { manager, key } => manager.get_object_range(key, start, end - 1).await,
+        }
+    }
+
+    /// Aktuelle Größe der Datei bzw. des S3-Objekts
+    pub(crate) async fn size(&self) -> Result<i64> {
+        match self {
+            ChunkReader::Local(path) => Ok(tokio::fs::metadata(path).await?.len() as i64),
+            ChunkReader::S3 { manager, key } => manager.get_object_size(key).await,
+        }
+    }
+}
+
+/// Ergebnis einer Such-Nachladung
+#[derive(Debug)]
+pub struct SearchLoad {
+    pub text: String, // Nachgeladener Text
+    pub start: i64,   // Byte-Bereich von text in der Datei
+    pub end: i64,
+    /// text schließt direkt an den geladenen Inhalt an (anhängen bzw. voranstellen).
+    /// Sonst wurde übersprungen und text ist nur das Fenster um die Fundstelle.
+    pub contiguous: bool,
+    pub limit_reached: bool, // Angehalten, obwohl die Datei noch weitergeht
+}
+
+/// Lädt ab `from` Chunks nach (vorwärts bis file_size, rückwärts bis 0), bis `regex`
+/// trifft. `carry` ist die angeschnittene Zeile des geladenen Inhalts (letzte bzw. erste),
+/// damit auch Treffer über die Chunk-Grenze hinweg gefunden werden. Behalten werden nur
+/// die letzten Chunks, damit große Dateien nicht komplett im Speicher landen.
+pub async fn load_until_match(
+    reader: ChunkReader,
+    regex: Regex,
+    from: i64,
+    file_size: i64,
+    backward: bool,
+    carry: String,
+    progress: Arc<AtomicU64>,
+) -> Result<SearchLoad> {
+    if backward {
+        load_backward(reader, regex, from, carry, progress).await
+    } else {
+        load_forward(reader, regex, from, file_size, carry, progress).await
+    }
+}
+
+async fn load_forward(
+    reader: ChunkReader,
+    regex: Regex,
+    from: i64,
+    file_size: i64,
+    carry: String,
+    progress: Arc<AtomicU64>,
+) -> Result<SearchLoad> {
+    let window = 2 * SEARCH_CHUNK_SIZE as usize;
+    let carry_len = carry.len();
+    let mut text = carry;
+    let mut dropped = 0; // Vorne verworfene Bytes von text
+    let mut pending: Vec<u8> = Vec::new(); // Unvollständiges UTF-8-Zeichen am Chunk-Ende
+    let mut pos = from;
+    let mut found = false;
+
+    while pos < file_size && pos - from < SEARCH_LOAD_LIMIT && !found {
+        let end = (pos + SEARCH_CHUNK_SIZE).min(file_size);
+        pending.extend(reader.read(pos, end).await?);
+        pos = end;
+        progress.store((pos - from) as u64, Ordering::Relaxed);
+
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(_) => pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => anyhow::bail!("File is not valid UTF-8 text"),
+        };
+        let line_start = text.rfind('\n').map_or(0, |i| i + 1);
+        text.push_str(std::str::from_utf8(&pending[..valid]).unwrap_or_default());
+        pending.drain(..valid);
+
+        found = regex.is_match(&text[line_start..]);
+
+        // Vorne abschneiden (an einer Zeilengrenze, wenn möglich)
+        if !found && text.len() > window {
+            let mut cut = text.len() - SEARCH_CHUNK_SIZE as usize;
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            let cut = text[..cut].rfind('\n').map_or(cut, |i| i + 1);
+            text.drain(..cut);
+            dropped += cut;
+        }
+    }
+
+    let contiguous = dropped == 0;
+    let (text, start) = if contiguous {
+        (text.split_off(carry_len), from)
+    } else {
+        let start = from - carry_len as i64 + dropped as i64;
+        (text, start)
+    };
+    Ok(SearchLoad {
+        text,
+        start,
+        end: pos - pending.len() as i64,
+        contiguous,
+        limit_reached: !found && pos < file_size,
+    })
+}
+
+async fn load_backward(
+    reader: ChunkReader,
+    regex: Regex,
+    from: i64,
+    carry: String,
+    progress: Arc<AtomicU64>,
+) -> Result<SearchLoad> {
+    let window = 2 * SEARCH_CHUNK_SIZE as usize;
+    // Chunks (Start-Offset, Text), neueste = weiter vorne in der Datei zuletzt; erst am
+    // Ende zusammengefügt (kein wiederholtes Voranstellen)
+    let mut pieces: Vec<(i64, String)> = Vec::new();
+    let mut pending: Vec<u8> = Vec::new(); // Angeschnittenes UTF-8-Zeichen am Chunk-Anfang
+    let mut pos = from;
+    let mut end = from; // Ende der behaltenen Chunks
+    let mut found = false;
+
+    while pos > 0 && from - pos < SEARCH_LOAD_LIMIT && !found {
+        let start = (pos - SEARCH_CHUNK_SIZE).max(0);
+        let mut bytes = reader.read(start, pos).await?;
+        bytes.append(&mut pending);
+        pos = start;
+        progress.store((from - pos) as u64, Ordering::Relaxed);
+
+        // Folge-Bytes (10xxxxxx) am Anfang gehören zum Zeichen davor
+        let skip = if pos > 0 {
+            bytes
+                .iter()
+                .take(3)
+                .take_while(|b| (**b & 0xC0) == 0x80)
+                .count()
+        } else {
+            0
+        };
+        pending = bytes.drain(..skip).collect();
+        let piece = String::from_utf8(bytes)
+            .map_err(|_| anyhow::anyhow!("File is not valid UTF-8 text"))?;
+
+        // Treffer im neuen Chunk oder in der Zeile, die er mit dem Text danach bildet
+        let next = pieces.last().map_or(carry.as_str(), |(_, p)| p.as_str());
+        let next_line = &next[..next.find('\n').map_or(next.len(), |i| i + 1)];
+        found = regex.is_match(&format!("{piece}{next_line}"));
+
+        pieces.push((pos + skip as i64, piece));
+
+        // Die Chunks am weitesten hinten verwerfen
+        while !found && pieces.iter().map(|(_, p)| p.len()).sum::<usize>() > window {
+            end = pieces.remove(0).0;
+        }
+    }
+
+    let text = pieces.iter().rev().map(|(_, p)| p.as_str()).collect();
+    Ok(SearchLoad {
+        text,
+        start: pos + pending.len() as i64,
+        end,
+        contiguous: end == from,
+        limit_reached: !found && pos > 0,
+    })
+}
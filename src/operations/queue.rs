@@ -1,4 +1,23 @@
-#[derive(Debug, Clone, PartialEq)]
+//! Transfer queue. Unfinished transfers are saved to `queue.json` next to the
+//! config and come back paused after a restart.
+
+use crate::models::config::write_atomic;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const QUEUE_FILE: &str = "queue.json";
+
+/// The transfer rate is averaged over this window
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+const RATE_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+/// Seconds of throughput kept for the queue details
+const HISTORY_SECONDS: usize = 120;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum OperationType {
     Upload,
@@ -8,7 +27,7 @@ pub enum OperationType {
     Rename,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileOperation {
     pub operation_type: OperationType,
     pub source: String,
@@ -21,19 +40,188 @@ pub struct FileOperation {
     pub bucket: Option<String>,  // Source bucket (Download/Upload) or Source bucket (S3→S3)
     pub dest_profile: Option<String>, // Destination profile (S3→S3 only)
     pub dest_bucket: Option<String>, // Destination bucket (S3→S3 only)
+    // Multipart upload id of a large upload, kept so it can continue after a restart
+    #[serde(default)]
+    pub upload_id: Option<String>,
+    // ETag of the object being downloaded, a resume only continues while it still matches
+    #[serde(default)]
+    pub etag: Option<String>,
+    // Automatic retries of the current attempt (shown while waiting for the next one)
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default)]
+    pub stats: TransferStats,
+    // Higher priorities run first, same priority in queue order
+    #[serde(default)]
+    pub priority: Priority,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    pub fn raised(self) -> Self {
+        match self {
+            Priority::Low => Priority::Normal,
+            _ => Priority::High,
+        }
+    }
+
+    pub fn lowered(self) -> Self {
+        match self {
+            Priority::High => Priority::Normal,
+            _ => Priority::Low,
+        }
+    }
+}
+
+/// Timing of a transfer: start/end for the queue details, live rate for speed and ETA
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferStats {
+    pub started_at: Option<DateTime<Local>>,
+    pub finished_at: Option<DateTime<Local>>,
+    #[serde(skip)]
+    pub rate: TransferRate,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Moving average of the transfer rate over the last few seconds
+#[derive(Debug, Clone, Default)]
+pub struct TransferRate {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl TransferRate {
+    /// Records the transferred byte count (called on every poll, thinned out here)
+    pub fn sample(&mut self, transferred: u64) {
+        let now = Instant::now();
+        if let Some(&(last_time, last_bytes)) = self.samples.back() {
+            if now.duration_since(last_time) < RATE_SAMPLE_INTERVAL {
+                return;
+            }
+            // Counter went back (download restarted by a retry)
+            if transferred < last_bytes {
+                self.samples.clear();
+            }
+        }
+
+        self.samples.push_back((now, transferred));
+        while self.samples.len() > 2
+            && self
+                .samples
+                .front()
+                .is_some_and(|(time, _)| now.duration_since(*time) > RATE_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Bytes per second, `None` until there is at least a second of samples
+    pub fn bytes_per_sec(&self) -> Option<f64> {
+        let (first_time, first_bytes) = self.samples.front()?;
+        let (last_time, last_bytes) = self.samples.back()?;
+        let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+        (elapsed >= 1.0).then(|| last_bytes.saturating_sub(*first_bytes) as f64 / elapsed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum OperationStatus {
     Pending,
     InProgress,
+    Paused, // Paused by hand or restored from the last session, waits to be resumed
     Completed,
     Cancelled,
     Failed(String),
 }
 
+/// Throughput of the queue, one value (bytes per second) per second of transfer
+#[derive(Debug, Clone, Default)]
+pub struct ThroughputHistory {
+    samples: VecDeque<u64>,
+    last_sample: Option<Instant>,
+}
+
+impl ThroughputHistory {
+    /// Records the current speed (called on every poll while a transfer runs)
+    pub fn record(&mut self, bytes_per_sec: Option<f64>) {
+        let now = Instant::now();
+        if self
+            .last_sample
+            .is_some_and(|last| now.duration_since(last) < Duration::from_secs(1))
+        {
+            return;
+        }
+        self.last_sample = Some(now);
+
+        self.samples.push_back(bytes_per_sec.unwrap_or(0.0) as u64);
+        if self.samples.len() > HISTORY_SECONDS {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Oldest first
+    pub fn samples(&self) -> Vec<u64> {
+        self.samples.iter().copied().collect()
+    }
+}
+
 impl FileOperation {
+    /// Puts a failed or cancelled transfer back into the queue
+    pub fn retry(&mut self) {
+        self.status = OperationStatus::Pending;
+        self.retries = 0;
+        self.stats = TransferStats::default();
+        if self.upload_id.is_none() {
+            self.transferred = 0;
+            self.etag = None;
+        }
+    }
+
+    pub fn mark_started(&mut self) {
+        self.stats.started_at = Some(Local::now());
+        self.stats.finished_at = None;
+        self.stats.rate = TransferRate::default();
+    }
+
+    pub fn mark_finished(&mut self) {
+        self.stats.finished_at = Some(Local::now());
+    }
+
+    /// Current speed of a running transfer in bytes per second
+    pub fn speed(&self) -> Option<f64> {
+        match self.status {
+            OperationStatus::InProgress => self.stats.rate.bytes_per_sec(),
+            _ => None,
+        }
+    }
+
+    /// Remaining time of a running transfer at the current speed
+    pub fn eta(&self) -> Option<Duration> {
+        let speed = self.speed().filter(|speed| *speed > 0.0)?;
+        let remaining = self.total_size.saturating_sub(self.transferred);
+        Some(Duration::from_secs_f64(remaining as f64 / speed))
+    }
+
+    /// Time from start to end (or until now while running)
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        let started = self.stats.started_at?;
+        Some(self.stats.finished_at.unwrap_or_else(Local::now) - started)
+    }
+
+    /// Not started yet (pending or paused) - only these can be reordered
+    pub fn is_waiting(&self) -> bool {
+        matches!(
+            self.status,
+            OperationStatus::Pending | OperationStatus::Paused
+        )
+    }
+
     pub fn progress_percentage(&self) -> u16 {
         if self.total_size == 0 {
             0
@@ -42,3 +230,147 @@ impl FileOperation {
         }
     }
 }
+
+/// Loads the saved queue. Transfers that were pending or running come back paused,
+/// a missing or unreadable file just means an empty queue.
+pub fn load_queue(config_dir: &Path) -> Vec<FileOperation> {
+    let mut queue: Vec<FileOperation> = std::fs::read_to_string(config_dir.join(QUEUE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    for op in &mut queue {
+        if matches!(
+            op.status,
+            OperationStatus::Pending | OperationStatus::InProgress
+        ) {
+            op.status = OperationStatus::Paused;
+            op.retries = 0;
+            // Only multipart uploads and downloads (partial file) continue where they stopped
+            if op.upload_id.is_none() && op.operation_type != OperationType::Download {
+                op.transferred = 0;
+            }
+        }
+    }
+    queue
+}
+
+/// Index of the transfer to start next: highest priority, then queue order
+pub fn next_pending(queue: &[FileOperation]) -> Option<usize> {
+    queue
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| op.status == OperationStatus::Pending)
+        .max_by_key(|(index, op)| (op.priority, std::cmp::Reverse(*index)))
+        .map(|(index, _)| index)
+}
+
+/// Saves all unfinished transfers (completed and cancelled ones are dropped)
+pub fn save_queue(config_dir: &Path, queue: &[FileOperation]) -> Result<()> {
+    let unfinished: Vec<&FileOperation> = queue
+        .iter()
+        .filter(|op| {
+            !matches!(
+                op.status,
+                OperationStatus::Completed | OperationStatus::Cancelled
+            )
+        })
+        .collect();
+
+    let path = config_dir.join(QUEUE_FILE);
+    if unfinished.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+
+    let content = serde_json::to_string_pretty(&unfinished)?;
+    write_atomic(&path, content.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(
+        operation_type: OperationType,
+        status: OperationStatus,
+        priority: Priority,
+    ) -> FileOperation {
+        FileOperation {
+            operation_type,
+            source: "src".to_string(),
+            destination: "dst".to_string(),
+            total_size: 100,
+            transferred: 40,
+            status,
+            profile: None,
+            bucket: None,
+            dest_profile: None,
+            dest_bucket: None,
+            upload_id: None,
+            etag: Some("\"abc\"".to_string()),
+            retries: 2,
+            stats: TransferStats::default(),
+            priority,
+        }
+    }
+
+    fn download(status: OperationStatus, priority: Priority) -> FileOperation {
+        op(OperationType::Download, status, priority)
+    }
+
+    #[test]
+    fn next_pending_prefers_priority_then_queue_order() {
+        let queue = vec![
+            download(OperationStatus::InProgress, Priority::High),
+            download(OperationStatus::Pending, Priority::Normal),
+            download(OperationStatus::Pending, Priority::High),
+            download(OperationStatus::Pending, Priority::High),
+            download(OperationStatus::Paused, Priority::High),
+        ];
+        assert_eq!(next_pending(&queue), Some(2));
+        assert_eq!(next_pending(&queue[..2]), Some(1));
+        assert_eq!(next_pending(&queue[..1]), None);
+    }
+
+    #[test]
+    fn retry_restarts_a_download_from_the_beginning() {
+        let mut failed = download(
+            OperationStatus::Failed("timeout".to_string()),
+            Priority::Low,
+        );
+        failed.retry();
+        assert_eq!(failed.status, OperationStatus::Pending);
+        assert_eq!((failed.transferred, failed.retries), (0, 0));
+        assert_eq!(failed.etag, None);
+        assert_eq!(failed.priority, Priority::Low);
+    }
+
+    #[test]
+    fn saved_queue_comes_back_paused() {
+        let dir = std::env::temp_dir().join(format!("s3c-test-{}-queue", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let queue = vec![
+            download(OperationStatus::InProgress, Priority::High),
+            op(
+                OperationType::Upload,
+                OperationStatus::Pending,
+                Priority::Normal,
+            ),
+            download(OperationStatus::Completed, Priority::Normal),
+        ];
+        save_queue(&dir, &queue).unwrap();
+        let loaded = load_queue(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert!(loaded.iter().all(|op| op.status == OperationStatus::Paused));
+        // Downloads continue in the partial file, simple uploads start over
+        assert_eq!(loaded[0].transferred, 40);
+        assert_eq!(loaded[0].etag.as_deref(), Some("\"abc\""));
+        assert_eq!(loaded[0].priority, Priority::High);
+        assert_eq!(loaded[1].transferred, 0);
+    }
+}
@@ -6,6 +6,10 @@ use crate::app::{self, App};
 use crate::handlers::key_to_message;
 use crate::ui;
 
+/// Delay before the first automatic retry of a transfer, doubled for each further one
+const RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+const RETRY_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Main application loop following The Elm Architecture (TEA)
 pub async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
@@ -24,6 +28,21 @@ where
             needs_render = true;
         }
 
+        // Run an external editor/pager requested with o / v
+        if process_external_program(app, terminal).await? {
+            needs_render = true;
+        }
+
+        // Open locations given on the command line
+        if process_start_locations(app).await? {
+            needs_render = true;
+        }
+
+        // Connect S3 panels restored from the last session once they are active
+        if process_pending_reconnect(app).await? {
+            needs_render = true;
+        }
+
         // Process background tasks (progress updates, completion, queue management)
         if process_background_tasks(app, terminal).await? {
             needs_render = true;
@@ -39,6 +58,21 @@ where
             needs_render = true;
         }
 
+        // Apply chunks loaded by a running preview search
+        if crate::app::handlers::check_preview_search_complete(app).await {
+            needs_render = true;
+        }
+
+        // Append new data of a followed preview file
+        if crate::app::handlers::check_preview_follow(app).await {
+            needs_render = true;
+        }
+
+        // Collect connection test results
+        if crate::app::handlers::check_connection_test_progress(app) {
+            needs_render = true;
+        }
+
         // Render only when needed and throttled
         let now = std::time::Instant::now();
         if needs_render && now.duration_since(last_render) >= render_interval {
@@ -89,6 +123,58 @@ where
     Ok(())
 }
 
+/// Opens the next command line location once no S3 list is running
+/// (there is only one background list slot)
+pub async fn process_start_locations(app: &mut App) -> Result<bool> {
+    if app.background_list_task.is_some() {
+        return Ok(false);
+    }
+    let Some((side, location)) = app.start_locations.pop_front() else {
+        return Ok(false);
+    };
+    crate::app::navigation::open_start_location(app, side, location).await?;
+    Ok(true)
+}
+
+/// Reconnects the active panel if it shows an S3 location restored from the session
+/// (restored panels have no S3Manager until they are used)
+pub async fn process_pending_reconnect(app: &mut App) -> Result<bool> {
+    if !app.start_locations.is_empty()
+        || app.background_list_task.is_some()
+        || app.script.needs_terminal
+    {
+        return Ok(false);
+    }
+
+    let panel = app.get_active_panel();
+    let crate::app::PanelType::S3Browser {
+        profile,
+        bucket,
+        prefix,
+    } = panel.panel_type.clone()
+    else {
+        return Ok(false);
+    };
+    if panel.s3_manager.is_some() {
+        return Ok(false);
+    }
+
+    crate::app::navigation::reconnect_s3_panel(app, profile, bucket, prefix).await?;
+    Ok(true)
+}
+
+/// Shell command for a profile's setup script (platform-specific shell)
+pub fn setup_script_command(script: &str) -> std::process::Command {
+    #[cfg(target_os = "windows")]
+    let (shell, flag) = ("cmd", "/C");
+    #[cfg(not(target_os = "windows"))]
+    let (shell, flag) = ("sh", "-c");
+
+    let mut command = std::process::Command::new(shell);
+    command.arg(flag).arg(script);
+    command
+}
+
 /// Process setup scripts that need terminal access
 /// Returns true if a script was executed and render is needed
 pub async fn process_setup_script<B: ratatui::backend::Backend>(
@@ -114,6 +200,7 @@ where
         app.script.pending_bucket.take(),
     ) {
         app.script.needs_terminal = false;
+        let prefix = app.script.pending_prefix.take();
 
         // Suspend TUI
         disable_raw_mode()?;
@@ -122,18 +209,7 @@ where
         // Run script interactively
         println!("Running setup script: {script}");
 
-        // Platform-specific shell execution
-        #[cfg(target_os = "windows")]
-        let status = std::process::Command::new("cmd")
-            .arg("/C")
-            .arg(&script)
-            .status();
-
-        #[cfg(not(target_os = "windows"))]
-        let status = std::process::Command::new("sh")
-            .arg("-c")
-            .arg(&script)
-            .status();
+        let status = setup_script_command(&script).status();
 
         // Resume TUI
         enable_raw_mode()?;
@@ -145,7 +221,7 @@ where
             Ok(exit_status) if exit_status.success() => {
                 if let Some(Some(bucket)) = bucket_opt {
                     // Continue loading bucket without script
-                    crate::app::navigation::load_s3_bucket_no_script(app, profile, bucket).await?;
+                    crate::app::navigation::load_s3_bucket_at(app, profile, bucket, prefix).await?;
                 } else {
                     // Just show bucket list for profile
                     let buckets = app.config_manager.get_buckets_for_profile(&profile);
@@ -170,6 +246,40 @@ where
     Ok(false)
 }
 
+/// Run the external editor or pager for a file opened with o / v, suspending
+/// the TUI like `process_setup_script`. Returns true if a program was run.
+pub async fn process_external_program<B: ratatui::backend::Backend>(
+    app: &mut App,
+    terminal: &mut ratatui::Terminal<B>,
+) -> Result<bool>
+where
+    B::Error: Send + Sync + 'static,
+{
+    use crossterm::{
+        execute,
+        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    };
+    use std::io;
+
+    let Some(file) = app.external_edit.pending.take() else {
+        return Ok(false);
+    };
+
+    // Suspend TUI
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+
+    let status = crate::operations::external::external_command(file.edit, &file.path).status();
+
+    // Resume TUI
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    crate::app::handlers::finish_external_program(app, file, status).await;
+    Ok(true)
+}
+
 /// Process background transfer tasks and update progress
 /// This handles all queue processing logic in one place
 pub async fn process_background_tasks<B: ratatui::backend::Backend>(
@@ -193,6 +303,15 @@ where
                     op.transferred = current;
                     needs_render = true;
                 }
+                op.stats.rate.sample(current);
+                app.throughput.record(op.speed());
+                // Retry counter (the task only holds the lock briefly)
+                if let Ok(running) = task.operation.try_lock() {
+                    if op.retries != running.retries {
+                        op.retries = running.retries;
+                        needs_render = true;
+                    }
+                }
             }
         }
 
@@ -205,6 +324,7 @@ where
             if operation.status == crate::operations::OperationStatus::Completed {
                 operation.transferred = operation.total_size;
             }
+            operation.mark_finished();
 
             // Update queue IMMEDIATELY with final status
             if let Some(index) = app.current_transfer_index {
@@ -245,6 +365,9 @@ where
 
             // Clean up and start next transfer
             app.current_transfer_index = None;
+            if let Err(e) = app.save_queue() {
+                app.show_error(&format!("Failed to save transfer queue: {e}"));
+            }
             start_next_queued_transfer(app).await?;
             needs_render = true;
         }
@@ -291,24 +414,52 @@ where
 
             // Get result
             match task.task_handle.await {
-                Ok(Ok(objects)) => {
+                Ok(Ok((objects, switched_region))) => {
                     app.clear_info();
 
+                    if let Some(region) = switched_region {
+                        let saved = app.config_manager.set_bucket_region(
+                            &task.profile,
+                            &task.bucket,
+                            &region,
+                        );
+                        match saved {
+                            Ok(true) => app.show_info(&format!(
+                                "Bucket '{}' is in {region} - region updated in config",
+                                task.bucket
+                            )),
+                            Ok(false) => app.show_info(&format!(
+                                "Bucket '{}' is in {region} - using it for this session",
+                                task.bucket
+                            )),
+                            Err(e) => app.show_error(&format!(
+                                "Bucket '{}' is in {region}, but saving the config failed: {e}",
+                                task.bucket
+                            )),
+                        }
+                    }
+
+                    app.remember_cursor(&task.target_panel);
+
                     // Update the panel that started the task (not necessarily the active one)
                     let panel = match task.target_panel {
                         crate::app::ActivePanel::Left => &mut app.left_panel,
                         crate::app::ActivePanel::Right => &mut app.right_panel,
                     };
-
                     panel.panel_type = crate::app::PanelType::S3Browser {
                         profile: task.profile,
                         bucket: task.bucket,
                         prefix: task.prefix,
                     };
+                    panel.archive = None;
                     panel
                         .list_model
                         .set_items(crate::app::converters::s3_objects_to_items(objects));
                     panel.selected_index = 0;
+                    match &task.select {
+                        Some(name) => panel.select_by_name(name),
+                        None => app.restore_cursor(&task.target_panel),
+                    }
 
                     needs_render = true;
                 }
@@ -334,19 +485,21 @@ pub async fn start_next_queued_transfer(app: &mut App) -> Result<()> {
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
-    // Find next pending operation in queue
-    let next_pending_index = app
-        .file_operation_queue
-        .iter()
-        .position(|op| op.status == crate::operations::OperationStatus::Pending);
+    // Find next pending operation in queue (highest priority first)
+    let next_pending_index = crate::operations::queue::next_pending(&app.file_operation_queue);
 
     if let Some(index) = next_pending_index {
+        app.file_operation_queue[index].mark_started();
+
         // Get operation details
         let op = app.file_operation_queue[index].clone();
 
         // Mark as in progress
         app.file_operation_queue[index].status = crate::operations::OperationStatus::InProgress;
         app.current_transfer_index = Some(index);
+        if let Err(e) = app.save_queue() {
+            app.show_error(&format!("Failed to save transfer queue: {e}"));
+        }
 
         // Create Arc<Mutex<FileOperation>> for task
         let operation = Arc::new(Mutex::new(op.clone()));
@@ -365,21 +518,19 @@ pub async fn start_next_queued_transfer(app: &mut App) -> Result<()> {
 
                     if let Some(config) = bucket_config {
                         // Create S3Manager with stored credentials
-                        match crate::operations::s3::S3Manager::new(
-                            profile,
-                            bucket.clone(),
-                            config.role_chain.clone(),
-                            &config.region,
-                            config.endpoint_url.as_deref(),
-                            config.path_style,
-                        )
-                        .await
+                        match crate::operations::s3::S3Manager::from_bucket_config(profile, &config)
+                            .await
                         {
                             Ok(s3_manager) => {
+                                let throttles = app.bandwidth.for_bucket(
+                                    profile,
+                                    bucket,
+                                    config.bandwidth_limit,
+                                );
                                 start_download_task(
                                     app,
                                     operation,
-                                    s3_manager,
+                                    s3_manager.with_throttles(throttles),
                                     op.source.clone(),
                                     op.destination.clone(),
                                 )
@@ -412,21 +563,19 @@ pub async fn start_next_queued_transfer(app: &mut App) -> Result<()> {
                         .find(|b| &b.name == bucket);
 
                     if let Some(config) = bucket_config {
-                        match crate::operations::s3::S3Manager::new(
-                            profile,
-                            bucket.clone(),
-                            config.role_chain.clone(),
-                            &config.region,
-                            config.endpoint_url.as_deref(),
-                            config.path_style,
-                        )
-                        .await
+                        match crate::operations::s3::S3Manager::from_bucket_config(profile, &config)
+                            .await
                         {
                             Ok(s3_manager) => {
+                                let throttles = app.bandwidth.for_bucket(
+                                    profile,
+                                    bucket,
+                                    config.bandwidth_limit,
+                                );
                                 start_upload_task(
                                     app,
                                     operation,
-                                    s3_manager,
+                                    s3_manager.with_throttles(throttles),
                                     op.source.clone(),
                                     op.destination.clone(),
                                 )
@@ -480,28 +629,34 @@ pub async fn start_next_queued_transfer(app: &mut App) -> Result<()> {
                         (src_bucket_config, dest_bucket_config)
                     {
                         // Create both S3 managers
-                        let src_manager_result = crate::operations::s3::S3Manager::new(
-                            src_profile,
-                            src_bucket.clone(),
-                            src_config.role_chain.clone(),
-                            &src_config.region,
-                            src_config.endpoint_url.as_deref(),
-                            src_config.path_style,
-                        )
-                        .await;
-
-                        let dest_manager_result = crate::operations::s3::S3Manager::new(
-                            dest_profile,
-                            dest_bucket.clone(),
-                            dest_config.role_chain.clone(),
-                            &dest_config.region,
-                            dest_config.endpoint_url.as_deref(),
-                            dest_config.path_style,
-                        )
-                        .await;
+                        let src_manager_result =
+                            crate::operations::s3::S3Manager::from_bucket_config(
+                                src_profile,
+                                &src_config,
+                            )
+                            .await;
+
+                        let dest_manager_result =
+                            crate::operations::s3::S3Manager::from_bucket_config(
+                                dest_profile,
+                                &dest_config,
+                            )
+                            .await;
 
                         match (src_manager_result, dest_manager_result) {
                             (Ok(src_manager), Ok(dest_manager)) => {
+                                let src_manager =
+                                    src_manager.with_throttles(app.bandwidth.for_bucket(
+                                        src_profile,
+                                        src_bucket,
+                                        src_config.bandwidth_limit,
+                                    ));
+                                let dest_manager =
+                                    dest_manager.with_throttles(app.bandwidth.for_bucket(
+                                        dest_profile,
+                                        dest_bucket,
+                                        dest_config.bandwidth_limit,
+                                    ));
                                 // Extract keys from s3:// URLs
                                 let source_key = op
                                     .source
@@ -569,8 +724,11 @@ async fn start_download_task(
     use std::path::PathBuf;
     use std::sync::Arc;
 
-    let transferred_counter = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    // A paused download continues after the bytes already downloaded
+    let resume_from = operation.lock().await.transferred;
+    let transferred_counter = Arc::new(std::sync::atomic::AtomicU64::new(resume_from));
     let transferred_clone = transferred_counter.clone();
+    let resume_counter = transferred_counter.clone();
 
     let progress_callback: crate::operations::s3::ProgressCallback =
         Arc::new(tokio::sync::Mutex::new(move |transferred: u64| {
@@ -579,10 +737,33 @@ async fn start_download_task(
 
     let operation_clone = operation.clone();
     let local_path_buf = PathBuf::from(local_path);
+    let max_retries = app.config_manager.app_config.settings.transfer_retries;
     let task_handle = tokio::spawn(async move {
-        let result = s3_manager
-            .download_file_with_progress(&s3_key, &local_path_buf, Some(progress_callback))
-            .await;
+        // Retries continue where the failed attempt stopped as well
+        let result = with_retries(max_retries, &operation_clone, || async {
+            // Remember which object version the partial file belongs to before the
+            // first byte arrives, so a resume after a restart can check it
+            let mut etag = operation_clone.lock().await.etag.clone();
+            let mut offset = resume_counter.load(std::sync::atomic::Ordering::Relaxed);
+            if etag.is_none() {
+                // A partial file of unknown origin can't be trusted
+                offset = 0;
+                etag = s3_manager.object_etag(&s3_key).await?;
+                operation_clone.lock().await.etag = etag.clone();
+            }
+            let downloaded = s3_manager
+                .download_file_resumable(
+                    &s3_key,
+                    &local_path_buf,
+                    offset,
+                    etag.as_deref(),
+                    Some(progress_callback.clone()),
+                )
+                .await?;
+            operation_clone.lock().await.etag = downloaded;
+            Ok(())
+        })
+        .await;
 
         match result {
             Ok(_) => {
@@ -614,7 +795,9 @@ async fn start_upload_task(
     use std::path::PathBuf;
     use std::sync::Arc;
 
-    let transferred_counter = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    // Keep showing the progress of a paused multipart upload until its parts are listed
+    let resume_from = operation.lock().await.transferred;
+    let transferred_counter = Arc::new(std::sync::atomic::AtomicU64::new(resume_from));
     let transferred_clone = transferred_counter.clone();
 
     let progress_callback: crate::operations::s3::ProgressCallback =
@@ -624,11 +807,20 @@ async fn start_upload_task(
 
     let operation_clone = operation.clone();
     let path = PathBuf::from(local_path);
+    let max_retries = app.config_manager.app_config.settings.transfer_retries;
 
     let task_handle = tokio::spawn(async move {
-        let result = s3_manager
-            .upload_file_with_progress(&path, &s3_key, Some(progress_callback))
-            .await;
+        // A retried multipart upload continues with the parts still missing
+        let result = with_retries(max_retries, &operation_clone, || {
+            upload_resumable(
+                &s3_manager,
+                &path,
+                &s3_key,
+                &operation_clone,
+                progress_callback.clone(),
+            )
+        })
+        .await;
 
         match result {
             Ok(_) => {
@@ -650,6 +842,134 @@ async fn start_upload_task(
     });
 }
 
+/// Runs a transfer and repeats it with exponential backoff while it fails with a
+/// retryable error (throttling, 5xx, network) and retries are left
+async fn with_retries<F, Fut>(
+    max_retries: u32,
+    operation: &tokio::sync::Mutex<crate::operations::FileOperation>,
+    mut attempt: F,
+) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt().await {
+            Err(e) if retries < max_retries && crate::operations::s3::is_retryable(&e) => {
+                let delay = (RETRY_BASE_DELAY * 2u32.pow(retries)).min(RETRY_MAX_DELAY);
+                retries += 1;
+                operation.lock().await.retries = retries;
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Stops the running transfer and parks it as paused. Multipart uploads keep their
+/// upload id (uploaded parts stay), downloads their partial file; copies start over.
+pub async fn pause_running_transfer(app: &mut App) {
+    let (Some(task), Some(index)) = (
+        app.background_transfer_task.take(),
+        app.current_transfer_index,
+    ) else {
+        return;
+    };
+    task.task_handle.abort();
+
+    let mut operation = task.operation.lock().await.clone();
+    operation.status = crate::operations::OperationStatus::Paused;
+    operation.retries = 0;
+    operation.transferred = match operation.operation_type {
+        crate::operations::OperationType::Download => task
+            .progress_counter
+            .load(std::sync::atomic::Ordering::Relaxed),
+        crate::operations::OperationType::Upload if operation.upload_id.is_some() => task
+            .progress_counter
+            .load(std::sync::atomic::Ordering::Relaxed),
+        _ => 0,
+    };
+
+    if let Some(op) = app.file_operation_queue.get_mut(index) {
+        *op = operation;
+    }
+    app.current_transfer_index = None;
+    if let Err(e) = app.save_queue() {
+        app.show_error(&format!("Failed to save transfer queue: {e}"));
+    }
+}
+
+/// Aborts the unfinished multipart upload of a cancelled or removed upload in the
+/// background, so its parts don't stay (and cost) in the bucket
+pub fn discard_multipart_upload(app: &App, op: &crate::operations::FileOperation) {
+    let (Some(upload_id), Some(profile), Some(bucket)) = (&op.upload_id, &op.profile, &op.bucket)
+    else {
+        return;
+    };
+    let Some(config) = app.config_manager.get_bucket_config(profile, bucket) else {
+        return;
+    };
+
+    let profile = profile.clone();
+    let key = op.destination.clone();
+    let upload_id = upload_id.clone();
+    tokio::spawn(async move {
+        if let Ok(s3_manager) =
+            crate::operations::s3::S3Manager::from_bucket_config(&profile, &config).await
+        {
+            let _ = s3_manager.abort_multipart_upload(&key, &upload_id).await;
+        }
+    });
+}
+
+/// Large uploads keep their multipart upload id in the operation, so an upload
+/// interrupted by quitting continues with the missing parts instead of starting over
+async fn upload_resumable(
+    s3_manager: &crate::operations::s3::S3Manager,
+    path: &std::path::Path,
+    s3_key: &str,
+    operation: &tokio::sync::Mutex<crate::operations::FileOperation>,
+    progress_callback: crate::operations::s3::ProgressCallback,
+) -> Result<()> {
+    let file_size = tokio::fs::metadata(path).await?.len();
+    if file_size <= crate::operations::s3::MULTIPART_THRESHOLD {
+        return s3_manager
+            .upload_file_with_progress(path, s3_key, Some(progress_callback))
+            .await;
+    }
+
+    // Upload of an earlier run - only usable if it still exists on the server
+    let previous = operation.lock().await.upload_id.clone();
+    let resumed = match previous {
+        Some(upload_id) => match s3_manager.list_uploaded_parts(s3_key, &upload_id).await {
+            Ok(parts) => Some((upload_id, parts)),
+            // Network trouble: retry later with the same upload
+            Err(e) if crate::operations::s3::is_retryable(&e) => return Err(e),
+            Err(_) => {
+                // Upload gone or unusable - don't leave its parts behind
+                let _ = s3_manager.abort_multipart_upload(s3_key, &upload_id).await;
+                None
+            }
+        },
+        None => None,
+    };
+    let (upload_id, parts) = match resumed {
+        Some(resumed) => resumed,
+        None => (
+            s3_manager.create_multipart_upload(s3_key).await?,
+            Vec::new(),
+        ),
+    };
+    operation.lock().await.upload_id = Some(upload_id.clone());
+
+    s3_manager
+        .resume_multipart_upload(path, s3_key, &upload_id, parts, Some(progress_callback))
+        .await?;
+    operation.lock().await.upload_id = None;
+    Ok(())
+}
+
 async fn start_copy_task(
     app: &mut App,
     operation: std::sync::Arc<tokio::sync::Mutex<crate::operations::FileOperation>>,
@@ -733,72 +1053,47 @@ async fn start_s3_copy_task(
         }));
 
     let operation_clone = operation.clone();
+    let max_retries = app.config_manager.app_config.settings.transfer_retries;
 
     let task_handle = tokio::spawn(async move {
-        // For cross-profile, use stream-based copy directly (different credentials)
-        if is_cross_profile {
-            let stream_result = dest_manager
-                .stream_copy_from_with_progress(
-                    &src_manager,
-                    &source_key,
-                    &dest_key,
-                    Some(progress_callback),
-                )
-                .await;
-
-            match stream_result {
-                Ok(_) => {
-                    operation_clone.lock().await.status =
-                        crate::operations::OperationStatus::Completed;
-                    Ok(())
-                }
-                Err(e) => {
-                    operation_clone.lock().await.status =
-                        crate::operations::OperationStatus::Failed(format!("{e}"));
-                    Err(anyhow::anyhow!("S3 copy failed: {e}"))
+        let result = with_retries(max_retries, &operation_clone, || async {
+            // For cross-profile, use stream-based copy directly (different credentials)
+            if !is_cross_profile {
+                // Same profile: Try server-side copy first (faster, no data transfer)
+                let server_side = dest_manager
+                    .copy_from_bucket_with_progress(
+                        &src_bucket,
+                        &source_key,
+                        &dest_key,
+                        Some(progress_callback.clone()),
+                    )
+                    .await;
+                if server_side.is_ok() {
+                    return Ok(());
                 }
+                // Fallback to stream-based copy if server-side fails
             }
-        } else {
-            // Same profile: Try server-side copy first (faster, no data transfer)
-            let result = dest_manager
-                .copy_from_bucket_with_progress(
-                    &src_bucket,
+
+            dest_manager
+                .stream_copy_from_with_progress(
+                    &src_manager,
                     &source_key,
                     &dest_key,
                     Some(progress_callback.clone()),
                 )
-                .await;
+                .await
+        })
+        .await;
 
-            match result {
-                Ok(_) => {
-                    operation_clone.lock().await.status =
-                        crate::operations::OperationStatus::Completed;
-                    Ok(())
-                }
-                Err(_) => {
-                    // Fallback to stream-based copy if server-side fails
-                    let stream_result = dest_manager
-                        .stream_copy_from_with_progress(
-                            &src_manager,
-                            &source_key,
-                            &dest_key,
-                            Some(progress_callback),
-                        )
-                        .await;
-
-                    match stream_result {
-                        Ok(_) => {
-                            operation_clone.lock().await.status =
-                                crate::operations::OperationStatus::Completed;
-                            Ok(())
-                        }
-                        Err(e) => {
-                            operation_clone.lock().await.status =
-                                crate::operations::OperationStatus::Failed(format!("{e}"));
-                            Err(anyhow::anyhow!("S3 copy failed: {e}"))
-                        }
-                    }
-                }
+        match result {
+            Ok(_) => {
+                operation_clone.lock().await.status = crate::operations::OperationStatus::Completed;
+                Ok(())
+            }
+            Err(e) => {
+                operation_clone.lock().await.status =
+                    crate::operations::OperationStatus::Failed(format!("{e}"));
+                Err(anyhow::anyhow!("S3 copy failed: {e}"))
             }
         }
     });
@@ -1,16 +1,26 @@
-use anyhow::{Context, Result};
-use aws_config::BehaviorVersion;
-use aws_sdk_s3::primitives::ByteStream;
+use anyhow::{anyhow, Context, Result};
+use aws_config::{BehaviorVersion, SdkConfig};
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::primitives::{ByteStream, ByteStreamError, SdkBody};
 use aws_sdk_s3::Client;
+use aws_sdk_sts::operation::assume_role::AssumeRoleError;
 use chrono::{DateTime, Utc};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 
+use super::throttle::{throttled_body, Throttle};
+
 pub type ProgressCallback = Arc<Mutex<dyn FnMut(u64) + Send>>;
 
+/// Uploads above this size use multipart upload
+pub const MULTIPART_THRESHOLD: u64 = 5 * 1024 * 1024; // 5MB
+/// Part size: 5MB minimum, use 10MB for better progress granularity
+const MULTIPART_PART_SIZE: usize = 10 * 1024 * 1024; // 10MB
+
 #[derive(Debug, Clone)]
 pub struct S3Object {
     pub key: String,
@@ -21,8 +31,20 @@ pub struct S3Object {
 
 #[derive(Clone)]
 pub struct S3Manager {
-    pub client: Client,
     pub bucket: String,
+    // Shared by all clones, so a detected region is used by every request after it
+    connection: Arc<RwLock<Connection>>,
+    config: SdkConfig,
+    endpoint_url: Option<String>,
+    path_style: Option<bool>,
+    // Bandwidth limits for transfers (global, bucket)
+    throttles: Vec<Arc<Throttle>>,
+}
+
+/// Client for the region the requests currently go to
+struct Connection {
+    client: Client,
+    region: String,
 }
 
 impl S3Manager {
@@ -34,61 +56,307 @@ impl S3Manager {
         endpoint_url: Option<&str>,
         path_style: Option<bool>,
     ) -> Result<Self> {
-        let region_str = region.to_string();
-
         // Load initial config from profile
-        let mut config = aws_config::defaults(BehaviorVersion::latest())
-            .profile_name(profile_name)
-            .region(aws_config::Region::new(region_str.clone()))
-            .load()
-            .await;
+        let mut config = Self::load_profile_config(profile_name, region).await;
 
         // Chain through multiple roles if provided
         for (index, role) in role_chain.iter().enumerate() {
-            let sts_client = aws_sdk_sts::Client::new(&config);
-
-            let assumed_role = sts_client
-                .assume_role()
-                .role_arn(role)
-                .role_session_name(format!(
-                    "s3c-chain-{}-{}",
-                    index,
-                    chrono::Utc::now().timestamp()
-                ))
-                .send()
+            config = Self::assume_role(&config, role, index, region)
                 .await
                 .context(format!(
                     "Failed to assume role (step {} of {}): {role}",
                     index + 1,
                     role_chain.len()
                 ))?;
+        }
 
-            if let Some(creds) = assumed_role.credentials() {
-                // Create credentials provider from assumed role credentials
-                use aws_credential_types::Credentials;
-                use std::time::SystemTime;
+        let client = Self::build_client(&config, endpoint_url, path_style);
 
-                let expiration = SystemTime::try_from(*creds.expiration()).ok();
+        Ok(Self {
+            bucket,
+            connection: Connection::shared(client, region),
+            config,
+            endpoint_url: endpoint_url.map(str::to_string),
+            path_style,
+            throttles: Vec::new(),
+        })
+    }
 
-                let credentials = Credentials::new(
-                    creds.access_key_id(),
-                    creds.secret_access_key(),
-                    Some(creds.session_token().to_string()),
-                    expiration,
-                    "AssumedRole",
-                );
+    pub fn client(&self) -> Client {
+        match self.connection.read() {
+            Ok(connection) => connection.client.clone(),
+            Err(poisoned) => poisoned.into_inner().client.clone(),
+        }
+    }
 
-                // Build new config with these credentials
-                config = aws_config::defaults(BehaviorVersion::latest())
-                    .credentials_provider(credentials)
-                    .region(aws_config::Region::new(region_str.clone()))
-                    .load()
-                    .await;
+    pub fn region(&self) -> String {
+        match self.connection.read() {
+            Ok(connection) => connection.region.clone(),
+            Err(poisoned) => poisoned.into_inner().region.clone(),
+        }
+    }
+
+    /// Sends a request built by `request`. If the bucket turns out to be in another
+    /// region (redirect), this manager and its clones switch to that region for good
+    /// and the request is sent once more.
+    async fn send<T, E, F, Fut>(
+        &self,
+        request: F,
+    ) -> std::result::Result<T, SdkError<E, HttpResponse>>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<T, SdkError<E, HttpResponse>>>,
+        E: ProvideErrorMetadata,
+    {
+        match request(self.client()).await {
+            Err(e) => match self.redirect_region(&e).await {
+                Some(region) => {
+                    self.switch_region(&region);
+                    request(self.client()).await
+                }
+                None => Err(e),
+            },
+            result => result,
+        }
+    }
+
+    fn switch_region(&self, region: &str) {
+        let corrected = self.with_region(region);
+        let connection = Connection {
+            client: corrected.client(),
+            region: region.to_string(),
+        };
+        match self.connection.write() {
+            Ok(mut current) => *current = connection,
+            Err(poisoned) => *poisoned.into_inner() = connection,
+        }
+    }
+
+    /// Transfers of this manager respect these bandwidth limits
+    pub fn with_throttles(mut self, throttles: Vec<Arc<Throttle>>) -> Self {
+        self.throttles = throttles;
+        self
+    }
+
+    async fn throttle(&self, bytes: u64) {
+        for throttle in &self.throttles {
+            throttle.consume(bytes).await;
+        }
+    }
+
+    /// Upload body that is sent at the pace of the bandwidth limits
+    /// (a fresh copy of `body` for every attempt)
+    fn upload_body(&self, body: &SdkBody) -> ByteStream {
+        let body = body.try_clone().unwrap_or_else(SdkBody::taken);
+        ByteStream::new(throttled_body(body, self.throttles.clone()))
+    }
+
+    /// Returns a manager for the same bucket and credentials, using another region
+    pub fn with_region(&self, region: &str) -> Self {
+        let config = self
+            .config
+            .to_builder()
+            .region(aws_config::Region::new(region.to_string()))
+            .build();
+        let client = Self::build_client(&config, self.endpoint_url.as_deref(), self.path_style);
+
+        Self {
+            bucket: self.bucket.clone(),
+            connection: Connection::shared(client, region),
+            config,
+            endpoint_url: self.endpoint_url.clone(),
+            path_style: self.path_style,
+            throttles: self.throttles.clone(),
+        }
+    }
+
+    /// Finds the real region of the bucket: `x-amz-bucket-region` of a HeadBucket,
+    /// falling back to GetBucketLocation via the global endpoint
+    pub async fn detect_region(&self) -> Result<String> {
+        let us_east_1 = self.with_region("us-east-1");
+
+        match us_east_1
+            .client()
+            .head_bucket()
+            .bucket(&self.bucket)
+            .send()
+            .await
+        {
+            Ok(output) => {
+                if let Some(region) = output.bucket_region() {
+                    return Ok(region.to_string());
+                }
+            }
+            Err(e) => {
+                if let Some(region) = bucket_region_header(&e) {
+                    return Ok(region);
+                }
             }
         }
 
-        // Build S3 client with optional custom endpoint and path style
-        let mut s3_config_builder = aws_sdk_s3::config::Builder::from(&config);
+        let location = us_east_1
+            .client()
+            .get_bucket_location()
+            .bucket(&self.bucket)
+            .send()
+            .await
+            .context("Failed to get bucket location")?;
+
+        // Empty location means us-east-1, "EU" is the legacy name of eu-west-1
+        Ok(match location.location_constraint().map(|c| c.as_str()) {
+            None | Some("") => "us-east-1".to_string(),
+            Some("EU") => "eu-west-1".to_string(),
+            Some(region) => region.to_string(),
+        })
+    }
+
+    /// The real region of the bucket if `err` is a redirect to another region
+    async fn redirect_region<E>(&self, err: &SdkError<E, HttpResponse>) -> Option<String>
+    where
+        E: ProvideErrorMetadata,
+    {
+        // Custom endpoints have no regional redirects
+        if self.endpoint_url.is_some() {
+            return None;
+        }
+
+        let status = err.raw_response().map(|r| r.status().as_u16());
+        let is_redirect = status == Some(301)
+            || matches!(
+                err.code(),
+                Some("PermanentRedirect")
+                    | Some("AuthorizationHeaderMalformed")
+                    | Some("IllegalLocationConstraintException")
+            );
+        if !is_redirect {
+            return None;
+        }
+
+        let actual = match bucket_region_header(err) {
+            Some(region) => region,
+            None => self.detect_region().await.ok()?,
+        };
+        (actual != self.region()).then_some(actual)
+    }
+
+    /// Creates a manager from a bucket config entry (anonymous or via profile + role chain)
+    pub async fn from_bucket_config(
+        profile_name: &str,
+        bucket_config: &crate::models::config::BucketConfig,
+    ) -> Result<Self> {
+        if bucket_config.is_anonymous() {
+            return Ok(Self::anonymous(
+                bucket_config.name.clone(),
+                &bucket_config.region,
+                bucket_config.endpoint_url.as_deref(),
+                bucket_config.path_style,
+            )
+            .await);
+        }
+
+        Self::new(
+            profile_name,
+            bucket_config.name.clone(),
+            bucket_config.role_chain.clone(),
+            &bucket_config.region,
+            bucket_config.endpoint_url.as_deref(),
+            bucket_config.path_style,
+        )
+        .await
+    }
+
+    /// Manager for public buckets: no credentials, requests are sent unsigned
+    pub async fn anonymous(
+        bucket: String,
+        region: &str,
+        endpoint_url: Option<&str>,
+        path_style: Option<bool>,
+    ) -> Self {
+        let config = Self::load_anonymous_config(region).await;
+        let client = Self::build_client(&config, endpoint_url, path_style);
+
+        Self {
+            bucket,
+            connection: Connection::shared(client, region),
+            config,
+            endpoint_url: endpoint_url.map(str::to_string),
+            path_style,
+            throttles: Vec::new(),
+        }
+    }
+
+    /// SDK config without credentials provider (requests are not signed)
+    pub async fn load_anonymous_config(region: &str) -> SdkConfig {
+        aws_config::defaults(BehaviorVersion::latest())
+            .no_credentials()
+            .region(aws_config::Region::new(region.to_string()))
+            .load()
+            .await
+    }
+
+    /// Loads the SDK config (credentials chain) for an AWS profile
+    pub async fn load_profile_config(profile_name: &str, region: &str) -> SdkConfig {
+        aws_config::defaults(BehaviorVersion::latest())
+            .profile_name(profile_name)
+            .region(aws_config::Region::new(region.to_string()))
+            .load()
+            .await
+    }
+
+    /// Assumes `role` using the credentials of `config` (step `index` of a role chain)
+    /// and returns a config for the assumed role session
+    pub async fn assume_role(
+        config: &SdkConfig,
+        role: &str,
+        index: usize,
+        region: &str,
+    ) -> std::result::Result<SdkConfig, SdkError<AssumeRoleError>> {
+        let sts_client = aws_sdk_sts::Client::new(config);
+
+        let assumed_role = sts_client
+            .assume_role()
+            .role_arn(role)
+            .role_session_name(format!(
+                "s3c-chain-{}-{}",
+                index,
+                chrono::Utc::now().timestamp()
+            ))
+            .send()
+            .await?;
+
+        let Some(creds) = assumed_role.credentials() else {
+            return Ok(config.clone());
+        };
+
+        // Create credentials provider from assumed role credentials
+        use aws_credential_types::Credentials;
+        use std::time::SystemTime;
+
+        let expiration = SystemTime::try_from(*creds.expiration()).ok();
+
+        let credentials = Credentials::new(
+            creds.access_key_id(),
+            creds.secret_access_key(),
+            Some(creds.session_token().to_string()),
+            expiration,
+            "AssumedRole",
+        );
+
+        // Build new config with these credentials
+        Ok(aws_config::defaults(BehaviorVersion::latest())
+            .credentials_provider(credentials)
+            .region(aws_config::Region::new(region.to_string()))
+            .load()
+            .await)
+    }
+
+    /// Builds the S3 client with optional custom endpoint and path style
+    pub fn build_client(
+        config: &SdkConfig,
+        endpoint_url: Option<&str>,
+        path_style: Option<bool>,
+    ) -> Client {
+        let mut s3_config_builder = aws_sdk_s3::config::Builder::from(config);
 
         // Set custom endpoint for S3-compatible services (Hetzner, Minio, DigitalOcean, etc.)
         if let Some(endpoint) = endpoint_url {
@@ -100,9 +368,7 @@ impl S3Manager {
             s3_config_builder = s3_config_builder.force_path_style(true);
         }
 
-        let client = Client::from_conf(s3_config_builder.build());
-
-        Ok(Self { client, bucket })
+        Client::from_conf(s3_config_builder.build())
     }
 
     pub async fn list_objects(&self, prefix: &str) -> Result<Vec<S3Object>> {
@@ -111,21 +377,21 @@ impl S3Manager {
         let mut continuation_token: Option<String> = None;
 
         loop {
-            let mut request = self
-                .client
-                .list_objects_v2()
-                .bucket(&self.bucket)
-                .prefix(prefix)
-                .delimiter("/");
-
-            if let Some(token) = continuation_token {
-                request = request.continuation_token(token);
-            }
-
-            let resp = request.send().await.map_err(|e| {
-                let bucket = &self.bucket;
-                anyhow::anyhow!("Failed to list objects in bucket '{bucket}': {e:?}")
-            })?;
+            let resp = self
+                .send(|client| {
+                    client
+                        .list_objects_v2()
+                        .bucket(&self.bucket)
+                        .prefix(prefix)
+                        .delimiter("/")
+                        .set_continuation_token(continuation_token.clone())
+                        .send()
+                })
+                .await
+                .map_err(|e| {
+                    let bucket = &self.bucket;
+                    anyhow!("Failed to list objects in bucket '{bucket}': {e:?}")
+                })?;
 
             for cp in resp.common_prefixes() {
                 if let Some(prefix_str) = cp.prefix() {
@@ -173,7 +439,90 @@ impl S3Manager {
         Ok(objects)
     }
 
-    #[allow(dead_code)]
+    /// Lists all objects below `prefix` (no delimiter, i.e. including all "subdirectories")
+    pub async fn list_objects_recursive(&self, prefix: &str) -> Result<Vec<S3Object>> {
+        let mut objects = Vec::new();
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let resp = self
+                .send(|client| {
+                    client
+                        .list_objects_v2()
+                        .bucket(&self.bucket)
+                        .prefix(prefix)
+                        .set_continuation_token(continuation_token.clone())
+                        .send()
+                })
+                .await
+                .map_err(|e| {
+                    let bucket = &self.bucket;
+                    anyhow!("Failed to list objects in bucket '{bucket}': {e:?}")
+                })?;
+
+            for obj in resp.contents() {
+                if let Some(key) = obj.key() {
+                    objects.push(S3Object {
+                        key: key.to_string(),
+                        size: obj.size().unwrap_or(0),
+                        last_modified: obj
+                            .last_modified()
+                            .map(|t| DateTime::from_timestamp(t.secs(), 0).unwrap_or_default()),
+                        is_prefix: false,
+                    });
+                }
+            }
+
+            if resp.is_truncated().unwrap_or(false) {
+                continuation_token = resp.next_continuation_token().map(|s| s.to_string());
+            } else {
+                break;
+            }
+        }
+
+        Ok(objects)
+    }
+
+    /// Streams an object into `writer` (e.g. stdout) without buffering it completely
+    pub async fn write_object_to<W>(&self, key: &str, writer: &mut W) -> Result<u64>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        let resp = self
+            .send(|client| client.get_object().bucket(&self.bucket).key(key).send())
+            .await
+            .map_err(|e| sdk_error("Failed to get object", e))?;
+
+        let mut stream = resp.body;
+        let mut total = 0u64;
+        while let Some(bytes) = stream.try_next().await? {
+            self.throttle(bytes.len() as u64).await;
+            writer.write_all(&bytes).await?;
+            total += bytes.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(total)
+    }
+
+    /// Creates a presigned GET URL valid for `expires_in`
+    pub async fn presign_get_object(
+        &self,
+        key: &str,
+        expires_in: std::time::Duration,
+    ) -> Result<String> {
+        let presigning = aws_sdk_s3::presigning::PresigningConfig::expires_in(expires_in)
+            .context("Invalid expiry for presigned URL")?;
+        let request = self
+            .client()
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(presigning)
+            .await
+            .context("Failed to presign request")?;
+        Ok(request.uri().to_string())
+    }
+
     pub async fn download_file(&self, key: &str, local_path: &Path) -> Result<()> {
         self.download_file_with_progress(key, local_path, None)
             .await
@@ -185,38 +534,111 @@ impl S3Manager {
         local_path: &Path,
         progress_callback: Option<ProgressCallback>,
     ) -> Result<()> {
-        let resp = self
-            .client
-            .get_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .send()
-            .await
-            .context("Failed to get object")?;
+        self.download_file_resumable(key, local_path, 0, None, progress_callback)
+            .await?;
+        Ok(())
+    }
+
+    /// Continues a download whose first `offset` bytes are already in `local_path`
+    /// (Range request). Only bytes actually on disk count, so a shorter partial
+    /// file continues from its end and a missing one starts over.
+    ///
+    /// `if_match` is the ETag the partial file was downloaded from. Without it, or
+    /// if the object has changed since (412, or a full 200 reply to the Range
+    /// request), the download starts over. Returns the ETag of the downloaded object.
+    pub async fn download_file_resumable(
+        &self,
+        key: &str,
+        local_path: &Path,
+        offset: u64,
+        if_match: Option<&str>,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<Option<String>> {
+        let mut offset = match tokio::fs::metadata(local_path).await {
+            Ok(metadata) if if_match.is_some() => offset.min(metadata.len()),
+            _ => 0,
+        };
+        let mut if_match = if_match;
+
+        let resp = loop {
+            let range = (offset > 0).then(|| format!("bytes={offset}-"));
+            let request = self.send(|client| {
+                client
+                    .get_object()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .set_range(range.clone())
+                    .set_if_match(if_match.map(str::to_string))
+                    .send()
+            });
+            match request.await {
+                // The server ignored the Range and sends the whole object
+                Ok(resp) if offset > 0 && resp.content_range().is_none() => {
+                    offset = 0;
+                    break resp;
+                }
+                Ok(resp) => break resp,
+                Err(e) => {
+                    let status = e.raw_response().map(|response| response.status().as_u16());
+                    match status {
+                        // Object replaced since the download started
+                        Some(412) if if_match.is_some() => {
+                            if_match = None;
+                            offset = 0;
+                        }
+                        // Nothing left after the offset (a checksum error at the very
+                        // end) - start over
+                        Some(416) if offset > 0 => offset = 0,
+                        _ => return Err(sdk_error("Failed to get object", e)),
+                    }
+                }
+            }
+        };
+        let etag = resp.e_tag().map(str::to_string);
 
         if let Some(parent) = local_path.parent() {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let mut file = File::create(local_path).await?;
+        let mut file = if offset > 0 {
+            // Drop whatever came after the resume point
+            let file = tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(local_path)
+                .await?;
+            file.set_len(offset).await?;
+            file
+        } else {
+            File::create(local_path).await?
+        };
         let mut stream = resp.body;
-        let mut total_transferred = 0u64;
+        let mut total_transferred = offset;
 
-        while let Some(bytes) = stream.try_next().await? {
-            file.write_all(&bytes).await?;
-            total_transferred += bytes.len() as u64;
+        if let Some(ref callback) = progress_callback {
+            let mut cb = callback.lock().await;
+            cb(total_transferred);
+        }
 
-            if let Some(ref callback) = progress_callback {
-                let mut cb = callback.lock().await;
-                cb(total_transferred);
+        let result: Result<()> = async {
+            while let Some(bytes) = stream.try_next().await? {
+                self.throttle(bytes.len() as u64).await;
+                file.write_all(&bytes).await?;
+                total_transferred += bytes.len() as u64;
+
+                if let Some(ref callback) = progress_callback {
+                    let mut cb = callback.lock().await;
+                    cb(total_transferred);
+                }
             }
+            Ok(())
         }
+        .await;
 
+        // Also on errors: a retry continues after the bytes written so far
         file.flush().await?;
-        Ok(())
+        result.map(|_| etag)
     }
 
-    #[allow(dead_code)]
     pub async fn upload_file(&self, local_path: &Path, key: &str) -> Result<()> {
         self.upload_file_with_progress(local_path, key, None).await
     }
@@ -238,8 +660,6 @@ impl S3Manager {
         }
 
         // Use multipart upload for files larger than 5MB
-        const MULTIPART_THRESHOLD: u64 = 5 * 1024 * 1024; // 5MB
-
         if file_size > MULTIPART_THRESHOLD {
             self.upload_file_multipart(local_path, key, file_size, progress_callback)
                 .await
@@ -269,16 +689,18 @@ impl S3Manager {
             cb(file_size / 2);
         }
 
-        let body = ByteStream::from(buffer);
+        let body = SdkBody::from(buffer);
 
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .body(body)
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to upload object: {e}"))?;
+        self.send(|client| {
+            client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(key)
+                .body(self.upload_body(&body))
+                .send()
+        })
+        .await
+        .map_err(|e| sdk_error("Failed to upload object", e))?;
 
         // Report 100% completion
         if let Some(ref callback) = progress_callback {
@@ -296,114 +718,221 @@ impl S3Manager {
         file_size: u64,
         progress_callback: Option<ProgressCallback>,
     ) -> Result<()> {
-        use tokio::io::AsyncReadExt;
+        let upload_id = self.create_multipart_upload(key).await?;
+
+        let result = self
+            .upload_parts(
+                local_path,
+                key,
+                &upload_id,
+                file_size,
+                Vec::new(),
+                progress_callback,
+            )
+            .await;
 
-        // Part size: 5MB minimum, use 10MB for better progress granularity
-        const PART_SIZE: usize = 10 * 1024 * 1024; // 10MB
+        if result.is_err() {
+            // Abort multipart upload on error
+            let _ = self.abort_multipart_upload(key, &upload_id).await;
+        }
+        result
+    }
 
-        // Step 1: Create multipart upload
+    pub async fn create_multipart_upload(&self, key: &str) -> Result<String> {
         let multipart_upload = self
-            .client
-            .create_multipart_upload()
-            .bucket(&self.bucket)
-            .key(key)
-            .send()
+            .send(|client| {
+                client
+                    .create_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .send()
+            })
             .await
-            .context("Failed to create multipart upload")?;
+            .map_err(|e| sdk_error("Failed to create multipart upload", e))?;
 
-        let upload_id = multipart_upload
+        Ok(multipart_upload
             .upload_id()
             .context("Missing upload ID")?
-            .to_string();
+            .to_string())
+    }
 
-        let mut file = File::open(local_path).await?;
-        let mut part_number = 1;
-        let mut uploaded_parts = Vec::new();
-        let mut total_uploaded = 0u64;
+    pub async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<()> {
+        self.send(|client| {
+            client
+                .abort_multipart_upload()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .send()
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to abort multipart upload: {e}"))?;
+        Ok(())
+    }
+
+    /// Parts already stored for a multipart upload (fails if the upload no longer exists)
+    pub async fn list_uploaded_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+    ) -> Result<Vec<(i32, i64, String)>> {
+        let mut parts = Vec::new();
+        let mut marker: Option<String> = None;
 
-        // Step 2: Upload parts
         loop {
-            let mut buffer = vec![0u8; PART_SIZE];
-            let mut bytes_read_total = 0;
-
-            // Read up to PART_SIZE bytes
-            while bytes_read_total < PART_SIZE {
-                let bytes_read = file.read(&mut buffer[bytes_read_total..]).await?;
-                if bytes_read == 0 {
-                    break; // EOF
+            let output = self
+                .send(|client| {
+                    client
+                        .list_parts()
+                        .bucket(&self.bucket)
+                        .key(key)
+                        .upload_id(upload_id)
+                        .set_part_number_marker(marker.clone())
+                        .send()
+                })
+                .await
+                .map_err(|e| sdk_error("Failed to list parts", e))?;
+
+            for part in output.parts() {
+                if let (Some(number), Some(e_tag)) = (part.part_number(), part.e_tag()) {
+                    parts.push((number, part.size().unwrap_or(0), e_tag.to_string()));
                 }
-                bytes_read_total += bytes_read;
             }
 
-            if bytes_read_total == 0 {
-                break; // No more data
+            match output.next_part_number_marker() {
+                Some(next) if output.is_truncated().unwrap_or(false) => {
+                    marker = Some(next.to_string())
+                }
+                _ => break,
             }
+        }
 
-            // Trim buffer to actual size read
-            buffer.truncate(bytes_read_total);
+        Ok(parts)
+    }
 
-            // Upload this part
-            let upload_part_result = self
-                .client
-                .upload_part()
-                .bucket(&self.bucket)
-                .key(key)
-                .upload_id(&upload_id)
-                .part_number(part_number)
-                .body(ByteStream::from(buffer))
-                .send()
-                .await;
+    /// Continues a multipart upload started earlier: `existing` parts (from
+    /// `list_uploaded_parts`) are skipped, the rest is uploaded and the upload
+    /// completed. Nothing is aborted on errors, so it can be resumed again later.
+    pub async fn resume_multipart_upload(
+        &self,
+        local_path: &Path,
+        key: &str,
+        upload_id: &str,
+        existing: Vec<(i32, i64, String)>,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<()> {
+        let file_size = tokio::fs::metadata(local_path)
+            .await
+            .context("Failed to get file metadata")?
+            .len();
 
-            match upload_part_result {
-                Ok(output) => {
-                    // Store completed part info
-                    uploaded_parts.push(
-                        aws_sdk_s3::types::CompletedPart::builder()
-                            .part_number(part_number)
-                            .e_tag(output.e_tag().unwrap_or(""))
-                            .build(),
-                    );
+        self.upload_parts(
+            local_path,
+            key,
+            upload_id,
+            file_size,
+            existing,
+            progress_callback,
+        )
+        .await
+    }
 
-                    total_uploaded += bytes_read_total as u64;
+    async fn upload_parts(
+        &self,
+        local_path: &Path,
+        key: &str,
+        upload_id: &str,
+        file_size: u64,
+        existing: Vec<(i32, i64, String)>,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<()> {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
 
-                    // Report progress
-                    if let Some(ref callback) = progress_callback {
-                        let mut cb = callback.lock().await;
-                        cb(total_uploaded);
-                    }
+        let part_size = MULTIPART_PART_SIZE as u64;
+        let num_parts = file_size.div_ceil(part_size).max(1) as i32;
 
-                    part_number += 1;
+        let mut file = File::open(local_path).await?;
+        let mut uploaded_parts = Vec::new();
+        let mut total_uploaded = 0u64;
+
+        for part_number in 1..=num_parts {
+            let offset = (part_number as u64 - 1) * part_size;
+            let expected_size = part_size.min(file_size - offset);
+
+            // Part from an earlier run with the right size: keep it
+            if let Some((_, _, e_tag)) = existing
+                .iter()
+                .find(|(n, size, _)| *n == part_number && *size as u64 == expected_size)
+            {
+                uploaded_parts.push(
+                    aws_sdk_s3::types::CompletedPart::builder()
+                        .part_number(part_number)
+                        .e_tag(e_tag)
+                        .build(),
+                );
+                total_uploaded += expected_size;
+                if let Some(ref callback) = progress_callback {
+                    let mut cb = callback.lock().await;
+                    cb(total_uploaded);
                 }
-                Err(e) => {
-                    // Abort multipart upload on error
-                    let _ = self
-                        .client
-                        .abort_multipart_upload()
+                continue;
+            }
+
+            let mut buffer = vec![0u8; expected_size as usize];
+            file.seek(std::io::SeekFrom::Start(offset)).await?;
+            file.read_exact(&mut buffer)
+                .await
+                .context("File changed during upload")?;
+
+            // Upload this part
+            let body = SdkBody::from(buffer);
+            let output = self
+                .send(|client| {
+                    client
+                        .upload_part()
                         .bucket(&self.bucket)
                         .key(key)
-                        .upload_id(&upload_id)
+                        .upload_id(upload_id)
+                        .part_number(part_number)
+                        .body(self.upload_body(&body))
                         .send()
-                        .await;
+                })
+                .await
+                .map_err(|e| sdk_error(format!("Failed to upload part {part_number}"), e))?;
 
-                    return Err(anyhow::anyhow!("Failed to upload part {part_number}: {e}"));
-                }
+            // Store completed part info
+            uploaded_parts.push(
+                aws_sdk_s3::types::CompletedPart::builder()
+                    .part_number(part_number)
+                    .e_tag(output.e_tag().unwrap_or(""))
+                    .build(),
+            );
+
+            total_uploaded += expected_size;
+
+            // Report progress
+            if let Some(ref callback) = progress_callback {
+                let mut cb = callback.lock().await;
+                cb(total_uploaded);
             }
         }
 
-        // Step 3: Complete multipart upload
+        // Complete multipart upload
         let completed_multipart_upload = aws_sdk_s3::types::CompletedMultipartUpload::builder()
             .set_parts(Some(uploaded_parts))
             .build();
 
-        self.client
-            .complete_multipart_upload()
-            .bucket(&self.bucket)
-            .key(key)
-            .upload_id(&upload_id)
-            .multipart_upload(completed_multipart_upload)
-            .send()
-            .await
-            .context("Failed to complete multipart upload")?;
+        self.send(|client| {
+            client
+                .complete_multipart_upload()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .multipart_upload(completed_multipart_upload.clone())
+                .send()
+        })
+        .await
+        .map_err(|e| sdk_error("Failed to complete multipart upload", e))?;
 
         // Report 100% completion
         if let Some(ref callback) = progress_callback {
@@ -414,18 +943,89 @@ impl S3Manager {
         Ok(())
     }
 
+    /// Downloads an object completely and returns its ETag (to detect later changes)
+    pub async fn download_file_with_etag(
+        &self,
+        key: &str,
+        local_path: &Path,
+    ) -> Result<Option<String>> {
+        let resp = self
+            .send(|client| client.get_object().bucket(&self.bucket).key(key).send())
+            .await
+            .map_err(|e| sdk_error("Failed to get object", e))?;
+        let etag = resp.e_tag().map(str::to_string);
+
+        let mut file = File::create(local_path).await?;
+        let mut stream = resp.body;
+        while let Some(bytes) = stream.try_next().await? {
+            self.throttle(bytes.len() as u64).await;
+            file.write_all(&bytes).await?;
+        }
+        file.flush().await?;
+        Ok(etag)
+    }
+
+    /// Current ETag of an object, `None` if it doesn't exist
+    pub async fn object_etag(&self, key: &str) -> Result<Option<String>> {
+        match self
+            .send(|client| client.head_object().bucket(&self.bucket).key(key).send())
+            .await
+        {
+            Ok(resp) => Ok(resp.e_tag().map(str::to_string)),
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(None),
+            Err(e) => Err(sdk_error("Failed to get object metadata", e)),
+        }
+    }
+
+    /// Uploads a file in one request. With `if_match` the object is only replaced
+    /// while it still has that ETag; returns false if it was modified in between.
+    pub async fn put_file_if_match(
+        &self,
+        local_path: &Path,
+        key: &str,
+        if_match: Option<&str>,
+    ) -> Result<bool> {
+        let body = ByteStream::from_path(local_path)
+            .await
+            .context("Failed to read file")?;
+        let body = body.into_inner();
+
+        match self
+            .send(|client| {
+                client
+                    .put_object()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .body(self.upload_body(&body))
+                    .set_if_match(if_match.map(str::to_string))
+                    .send()
+            })
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e)
+                if e.raw_response()
+                    .is_some_and(|response| response.status().as_u16() == 412) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(sdk_error("Failed to upload object", e)),
+        }
+    }
+
     pub async fn upload_empty_folder(&self, key: &str) -> Result<()> {
         // Create empty object with trailing slash to represent folder
-        let body = ByteStream::from_static(b"");
 
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .body(body)
-            .send()
-            .await
-            .context("Failed to create folder")?;
+        self.send(|client| {
+            client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(key)
+                .body(ByteStream::from_static(b""))
+                .send()
+        })
+        .await
+        .context("Failed to create folder")?;
 
         Ok(())
     }
@@ -473,14 +1073,16 @@ impl S3Manager {
         let bucket = &self.bucket;
         let copy_source = format!("{bucket}/{source_key}");
 
-        self.client
-            .copy_object()
-            .bucket(&self.bucket)
-            .copy_source(&copy_source)
-            .key(dest_key)
-            .send()
-            .await
-            .context("Failed to copy object")?;
+        self.send(|client| {
+            client
+                .copy_object()
+                .bucket(&self.bucket)
+                .copy_source(&copy_source)
+                .key(dest_key)
+                .send()
+        })
+        .await
+        .map_err(|e| sdk_error("Failed to copy object", e))?;
 
         // Report 100% completion
         if let Some(ref callback) = progress_callback {
@@ -506,13 +1108,15 @@ impl S3Manager {
 
         // Step 1: Create multipart upload
         let multipart_upload = self
-            .client
-            .create_multipart_upload()
-            .bucket(&self.bucket)
-            .key(dest_key)
-            .send()
+            .send(|client| {
+                client
+                    .create_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(dest_key)
+                    .send()
+            })
             .await
-            .context("Failed to create multipart upload")?;
+            .map_err(|e| sdk_error("Failed to create multipart upload", e))?;
 
         let upload_id = multipart_upload
             .upload_id()
@@ -532,15 +1136,17 @@ impl S3Manager {
             let copy_source_range = format!("bytes={start_byte}-{end_byte}");
 
             let upload_part_result = self
-                .client
-                .upload_part_copy()
-                .bucket(&self.bucket)
-                .key(dest_key)
-                .upload_id(&upload_id)
-                .copy_source(&copy_source)
-                .copy_source_range(&copy_source_range)
-                .part_number(part_number)
-                .send()
+                .send(|client| {
+                    client
+                        .upload_part_copy()
+                        .bucket(&self.bucket)
+                        .key(dest_key)
+                        .upload_id(&upload_id)
+                        .copy_source(&copy_source)
+                        .copy_source_range(&copy_source_range)
+                        .part_number(part_number)
+                        .send()
+                })
                 .await;
 
             match upload_part_result {
@@ -571,15 +1177,17 @@ impl S3Manager {
                 Err(e) => {
                     // Abort multipart upload on error
                     let _ = self
-                        .client
-                        .abort_multipart_upload()
-                        .bucket(&self.bucket)
-                        .key(dest_key)
-                        .upload_id(&upload_id)
-                        .send()
+                        .send(|client| {
+                            client
+                                .abort_multipart_upload()
+                                .bucket(&self.bucket)
+                                .key(dest_key)
+                                .upload_id(&upload_id)
+                                .send()
+                        })
                         .await;
 
-                    return Err(anyhow::anyhow!("Failed to copy part {part_number}: {e}"));
+                    return Err(sdk_error(format!("Failed to copy part {part_number}"), e));
                 }
             }
         }
@@ -589,15 +1197,17 @@ impl S3Manager {
             .set_parts(Some(uploaded_parts))
             .build();
 
-        self.client
-            .complete_multipart_upload()
-            .bucket(&self.bucket)
-            .key(dest_key)
-            .upload_id(&upload_id)
-            .multipart_upload(completed_multipart_upload)
-            .send()
-            .await
-            .context("Failed to complete multipart copy")?;
+        self.send(|client| {
+            client
+                .complete_multipart_upload()
+                .bucket(&self.bucket)
+                .key(dest_key)
+                .upload_id(&upload_id)
+                .multipart_upload(completed_multipart_upload.clone())
+                .send()
+        })
+        .await
+        .map_err(|e| sdk_error("Failed to complete multipart copy", e))?;
 
         // Report 100% completion AFTER complete_multipart_upload finishes
         if let Some(ref callback) = progress_callback {
@@ -635,7 +1245,7 @@ impl S3Manager {
 
         // Try to get object size from source bucket
         let object_size_result = self
-            .client
+            .client()
             .head_object()
             .bucket(source_bucket)
             .key(source_key)
@@ -681,14 +1291,16 @@ impl S3Manager {
     ) -> Result<()> {
         let copy_source = format!("{source_bucket}/{source_key}");
 
-        self.client
-            .copy_object()
-            .bucket(&self.bucket)
-            .copy_source(&copy_source)
-            .key(dest_key)
-            .send()
-            .await
-            .context("Failed to copy from another bucket")?;
+        self.send(|client| {
+            client
+                .copy_object()
+                .bucket(&self.bucket)
+                .copy_source(&copy_source)
+                .key(dest_key)
+                .send()
+        })
+        .await
+        .map_err(|e| sdk_error("Failed to copy from another bucket", e))?;
 
         // Report 100% completion
         if let Some(ref callback) = progress_callback {
@@ -718,13 +1330,15 @@ impl S3Manager {
 
         // Step 1: Create multipart upload
         let multipart_upload = self
-            .client
-            .create_multipart_upload()
-            .bucket(&self.bucket)
-            .key(dest_key)
-            .send()
+            .send(|client| {
+                client
+                    .create_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(dest_key)
+                    .send()
+            })
             .await
-            .context("Failed to create multipart upload")?;
+            .map_err(|e| sdk_error("Failed to create multipart upload", e))?;
 
         let upload_id = multipart_upload
             .upload_id()
@@ -744,15 +1358,17 @@ impl S3Manager {
             let copy_source_range = format!("bytes={start_byte}-{end_byte}");
 
             let upload_part_result = self
-                .client
-                .upload_part_copy()
-                .bucket(&self.bucket)
-                .key(dest_key)
-                .upload_id(&upload_id)
-                .copy_source(&copy_source)
-                .copy_source_range(&copy_source_range)
-                .part_number(part_number)
-                .send()
+                .send(|client| {
+                    client
+                        .upload_part_copy()
+                        .bucket(&self.bucket)
+                        .key(dest_key)
+                        .upload_id(&upload_id)
+                        .copy_source(&copy_source)
+                        .copy_source_range(&copy_source_range)
+                        .part_number(part_number)
+                        .send()
+                })
                 .await;
 
             match upload_part_result {
@@ -783,15 +1399,17 @@ impl S3Manager {
                 Err(e) => {
                     // Abort multipart upload on error
                     let _ = self
-                        .client
-                        .abort_multipart_upload()
-                        .bucket(&self.bucket)
-                        .key(dest_key)
-                        .upload_id(&upload_id)
-                        .send()
+                        .send(|client| {
+                            client
+                                .abort_multipart_upload()
+                                .bucket(&self.bucket)
+                                .key(dest_key)
+                                .upload_id(&upload_id)
+                                .send()
+                        })
                         .await;
 
-                    return Err(anyhow::anyhow!("Failed to copy part {part_number}: {e}"));
+                    return Err(sdk_error(format!("Failed to copy part {part_number}"), e));
                 }
             }
         }
@@ -801,15 +1419,17 @@ impl S3Manager {
             .set_parts(Some(uploaded_parts))
             .build();
 
-        self.client
-            .complete_multipart_upload()
-            .bucket(&self.bucket)
-            .key(dest_key)
-            .upload_id(&upload_id)
-            .multipart_upload(completed_multipart_upload)
-            .send()
-            .await
-            .context("Failed to complete multipart copy")?;
+        self.send(|client| {
+            client
+                .complete_multipart_upload()
+                .bucket(&self.bucket)
+                .key(dest_key)
+                .upload_id(&upload_id)
+                .multipart_upload(completed_multipart_upload.clone())
+                .send()
+        })
+        .await
+        .map_err(|e| sdk_error("Failed to complete multipart copy", e))?;
 
         // Report 100% completion AFTER complete_multipart_upload finishes
         if let Some(ref callback) = progress_callback {
@@ -849,13 +1469,15 @@ impl S3Manager {
 
         // Get object from source
         let resp = source_manager
-            .client
-            .get_object()
-            .bucket(&source_manager.bucket)
-            .key(source_key)
-            .send()
+            .send(|client| {
+                client
+                    .get_object()
+                    .bucket(&source_manager.bucket)
+                    .key(source_key)
+                    .send()
+            })
             .await
-            .context("Failed to get source object")?;
+            .map_err(|e| sdk_error("Failed to get source object", e))?;
 
         let object_size = resp.content_length().unwrap_or(0);
 
@@ -871,6 +1493,15 @@ impl S3Manager {
                 break;
             }
 
+            // Limits of both sides (shared throttles only count once)
+            for throttle in source_manager.throttles.iter().chain(
+                self.throttles
+                    .iter()
+                    .filter(|t| !source_manager.throttles.iter().any(|s| Arc::ptr_eq(s, t))),
+            ) {
+                throttle.consume(bytes_read as u64).await;
+            }
+
             buffer.extend_from_slice(&chunk[..bytes_read]);
             total_read += bytes_read as u64;
 
@@ -881,16 +1512,18 @@ impl S3Manager {
         }
 
         // Upload to destination
-        let body = ByteStream::from(buffer);
+        let body = bytes::Bytes::from(buffer);
 
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(dest_key)
-            .body(body)
-            .send()
-            .await
-            .context("Failed to upload to destination")?;
+        self.send(|client| {
+            client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(dest_key)
+                .body(ByteStream::from(body.clone()))
+                .send()
+        })
+        .await
+        .map_err(|e| sdk_error("Failed to upload to destination", e))?;
 
         // Report 100% completion
         if let Some(ref callback) = progress_callback {
@@ -947,11 +1580,7 @@ impl S3Manager {
     }
 
     pub async fn delete_object(&self, key: &str) -> Result<()> {
-        self.client
-            .delete_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .send()
+        self.send(|client| client.delete_object().bucket(&self.bucket).key(key).send())
             .await
             .context("Failed to delete object")?;
 
@@ -974,13 +1603,28 @@ impl S3Manager {
             .await
     }
 
+    /// Metadata of an object, `None` if it doesn't exist
+    pub async fn stat_object(&self, key: &str) -> Result<Option<S3Object>> {
+        match self
+            .send(|client| client.head_object().bucket(&self.bucket).key(key).send())
+            .await
+        {
+            Ok(resp) => Ok(Some(S3Object {
+                key: key.to_string(),
+                size: resp.content_length().unwrap_or(0),
+                last_modified: resp
+                    .last_modified()
+                    .map(|t| DateTime::from_timestamp(t.secs(), 0).unwrap_or_default()),
+                is_prefix: false,
+            })),
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(None),
+            Err(e) => Err(sdk_error("Failed to get object metadata", e)),
+        }
+    }
+
     pub async fn get_object_size(&self, key: &str) -> Result<i64> {
         let resp = self
-            .client
-            .head_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .send()
+            .send(|client| client.head_object().bucket(&self.bucket).key(key).send())
             .await
             .context("Failed to get object metadata")?;
 
@@ -991,12 +1635,14 @@ impl S3Manager {
         let range = format!("bytes={start}-{end}");
 
         let resp = self
-            .client
-            .get_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .range(range)
-            .send()
+            .send(|client| {
+                client
+                    .get_object()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .range(&range)
+                    .send()
+            })
             .await
             .context("Failed to get object range")?;
 
@@ -1007,13 +1653,9 @@ impl S3Manager {
     #[allow(dead_code)]
     pub async fn get_object_content(&self, key: &str, max_size: usize) -> Result<String> {
         let resp = self
-            .client
-            .get_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .send()
+            .send(|client| client.get_object().bucket(&self.bucket).key(key).send())
             .await
-            .context("Failed to get object")?;
+            .map_err(|e| sdk_error("Failed to get object", e))?;
 
         let bytes = resp.body.collect().await?.into_bytes();
 
@@ -1028,3 +1670,103 @@ impl S3Manager {
         Ok(String::from_utf8_lossy(&bytes).to_string())
     }
 }
+
+/// Throttling, server (5xx) or network error - worth another attempt
+#[derive(Debug)]
+pub struct TransientError(String);
+
+impl std::fmt::Display for TransientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TransientError {}
+
+/// Error message with the S3 error code; retryable errors become a `TransientError`
+fn sdk_error<E>(context: impl std::fmt::Display, err: SdkError<E, HttpResponse>) -> anyhow::Error
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+{
+    let status = err.raw_response().map(|r| r.status().as_u16());
+    let transient = matches!(
+        err,
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_)
+    ) || status.is_some_and(|s| s == 429 || s >= 500)
+        || matches!(
+            err.code(),
+            Some(
+                "SlowDown"
+                    | "Throttling"
+                    | "ThrottlingException"
+                    | "RequestLimitExceeded"
+                    | "RequestTimeout"
+                    | "InternalError"
+                    | "ServiceUnavailable"
+            )
+        );
+
+    let detail = match (err.code(), err.message()) {
+        (Some(code), Some(message)) => format!("{code}: {message}"),
+        (Some(code), None) => code.to_string(),
+        _ => match std::error::Error::source(&err) {
+            Some(source) => format!("{err}: {source}"),
+            None => err.to_string(),
+        },
+    };
+
+    let message = format!("{context}: {detail}");
+    if transient {
+        TransientError(message).into()
+    } else {
+        anyhow::anyhow!(message)
+    }
+}
+
+/// True for errors a transfer should be retried on (see `TransientError`),
+/// including connection problems while streaming a body
+pub fn is_retryable(err: &anyhow::Error) -> bool {
+    use std::io::ErrorKind;
+
+    err.chain().any(|cause| {
+        if cause.is::<TransientError>() || cause.is::<ByteStreamError>() {
+            return true;
+        }
+        cause.downcast_ref::<std::io::Error>().is_some_and(|io| {
+            matches!(
+                io.kind(),
+                ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::TimedOut
+                    | ErrorKind::UnexpectedEof
+            ) || io
+                .get_ref()
+                .is_some_and(|inner| inner.is::<ByteStreamError>())
+        })
+    })
+}
+
+fn bucket_region_header<E>(err: &SdkError<E, HttpResponse>) -> Option<String> {
+    err.raw_response()
+        .and_then(|r| r.headers().get("x-amz-bucket-region"))
+        .map(str::to_string)
+}
+
+/// Splits `s3://bucket/prefix` (the scheme is optional) into bucket and key/prefix
+pub fn parse_s3_url(url: &str) -> Option<(String, String)> {
+    let path = url.trim().strip_prefix("s3://").unwrap_or(url.trim());
+    let (bucket, key) = path.split_once('/').unwrap_or((path, ""));
+    if bucket.is_empty() {
+        return None;
+    }
+    Some((bucket.to_string(), key.to_string()))
+}
+
+impl Connection {
+    fn shared(client: Client, region: &str) -> Arc<RwLock<Connection>> {
+        Arc::new(RwLock::new(Connection {
+            client,
+            region: region.to_string(),
+        }))
+    }
+}
@@ -0,0 +1,287 @@
+//! Bandwidth limits for transfers (bytes per second).
+//! A global limit shared by all transfers plus optional limits per bucket;
+//! the streaming loops in `S3Manager` wait on them before moving data.
+
+use aws_sdk_s3::primitives::SdkBody;
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Request bodies are throttled in chunks of this size
+const BODY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Token bucket: the budget refills with `limit` bytes per second,
+/// at most one second of unused bandwidth is saved up
+#[derive(Debug)]
+pub struct Throttle {
+    // 0 = unlimited
+    limit: AtomicU64,
+    state: Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    available: f64,
+    last_refill: Instant,
+}
+
+impl Throttle {
+    pub fn new(limit: Option<u64>) -> Self {
+        Self {
+            limit: AtomicU64::new(limit.unwrap_or(0)),
+            state: Mutex::new(TokenBucket {
+                available: 0.0,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn limit(&self) -> Option<u64> {
+        match self.limit.load(Ordering::Relaxed) {
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
+    /// Takes effect for running transfers with their next chunk
+    pub fn set_limit(&self, limit: Option<u64>) {
+        self.limit.store(limit.unwrap_or(0), Ordering::Relaxed);
+        if let Ok(mut state) = self.state.lock() {
+            state.available = 0.0;
+            state.last_refill = Instant::now();
+        }
+    }
+
+    /// Waits until `bytes` may be transferred
+    pub async fn consume(&self, bytes: u64) {
+        let Some(limit) = self.limit() else {
+            return;
+        };
+        let limit = limit as f64;
+
+        let delay = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            let now = Instant::now();
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.last_refill = now;
+            state.available = (state.available + elapsed * limit).min(limit);
+            // May go negative: the debt is paid off by waiting
+            state.available -= bytes as f64;
+            if state.available < 0.0 {
+                Duration::from_secs_f64(-state.available / limit)
+            } else {
+                Duration::ZERO
+            }
+        };
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Request body (upload) that waits on the throttles before each chunk, so an
+/// upload is paced like a download instead of being charged once up front
+pub fn throttled_body(body: SdkBody, throttles: Vec<Arc<Throttle>>) -> SdkBody {
+    if throttles.is_empty() {
+        return body;
+    }
+    // Keep the body retryable: every attempt pays for its bytes again
+    SdkBody::retryable(move || {
+        let inner = body.try_clone().unwrap_or_else(SdkBody::taken);
+        SdkBody::from_body_1_x(ThrottledBody {
+            inner,
+            throttles: throttles.clone(),
+            remaining: Bytes::new(),
+            waiting: None,
+        })
+    })
+}
+
+type ThrottleWait = Pin<Box<dyn Future<Output = ()> + Send + Sync>>;
+
+struct ThrottledBody {
+    inner: SdkBody,
+    throttles: Vec<Arc<Throttle>>,
+    // Data of the current inner frame not passed on yet
+    remaining: Bytes,
+    // Chunk released once the throttles allow it
+    waiting: Option<(ThrottleWait, Bytes)>,
+}
+
+impl Body for ThrottledBody {
+    type Data = Bytes;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Self::Error>>> {
+        loop {
+            if let Some((wait, _)) = self.waiting.as_mut() {
+                ready!(wait.as_mut().poll(cx));
+                let (_, chunk) = self.waiting.take().expect("waiting chunk");
+                return Poll::Ready(Some(Ok(Frame::data(chunk))));
+            }
+
+            if !self.remaining.is_empty() {
+                let len = self.remaining.len().min(BODY_CHUNK_SIZE);
+                let chunk = self.remaining.split_to(len);
+                let throttles = self.throttles.clone();
+                let wait: ThrottleWait = Box::pin(async move {
+                    for throttle in &throttles {
+                        throttle.consume(len as u64).await;
+                    }
+                });
+                self.waiting = Some((wait, chunk));
+                continue;
+            }
+
+            match ready!(Pin::new(&mut self.inner).poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => self.remaining = data,
+                    Err(frame) => return Poll::Ready(Some(Ok(frame))),
+                },
+                other => return Poll::Ready(other),
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.waiting.is_none() && self.remaining.is_empty() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let buffered = (self.remaining.len()
+            + self.waiting.as_ref().map_or(0, |(_, chunk)| chunk.len()))
+            as u64;
+        let inner = Body::size_hint(&self.inner);
+        let mut hint = SizeHint::new();
+        hint.set_lower(inner.lower() + buffered);
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + buffered);
+        }
+        hint
+    }
+}
+
+/// Global limit plus one throttle per bucket with a configured limit
+#[derive(Debug)]
+pub struct BandwidthLimits {
+    global: Arc<Throttle>,
+    buckets: HashMap<(String, String), Arc<Throttle>>,
+}
+
+impl BandwidthLimits {
+    pub fn new(global_limit: Option<u64>) -> Self {
+        Self {
+            global: Arc::new(Throttle::new(global_limit)),
+            buckets: HashMap::new(),
+        }
+    }
+
+    pub fn global(&self) -> &Arc<Throttle> {
+        &self.global
+    }
+
+    /// Throttles a transfer on this bucket has to respect. Transfers on the same
+    /// bucket share its limit.
+    pub fn for_bucket(
+        &mut self,
+        profile: &str,
+        bucket: &str,
+        bucket_limit: Option<u64>,
+    ) -> Vec<Arc<Throttle>> {
+        let mut throttles = vec![self.global.clone()];
+        if let Some(limit) = bucket_limit {
+            let throttle = self
+                .buckets
+                .entry((profile.to_string(), bucket.to_string()))
+                .or_insert_with(|| Arc::new(Throttle::new(Some(limit))));
+            if throttle.limit() != Some(limit) {
+                throttle.set_limit(Some(limit));
+            }
+            throttles.push(throttle.clone());
+        }
+        throttles
+    }
+}
+
+/// Parses a rate like `500k`, `10M`, `1.5MB/s` or a plain byte count.
+/// Empty, `0` and `off` mean unlimited (`Some(None)`), garbage gives `None`.
+pub fn parse_rate(input: &str) -> Option<Option<u64>> {
+    let input = input.trim().to_lowercase();
+    let input = input.strip_suffix("/s").unwrap_or(&input).trim();
+    if input.is_empty() || input == "off" || input == "0" {
+        return Some(None);
+    }
+
+    let number_end = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(number_end);
+    let number: f64 = number.parse().ok()?;
+    let factor = match unit.trim().trim_end_matches(['b', 'i']) {
+        "" => 1.0,
+        "k" => 1024.0,
+        "m" => 1024.0 * 1024.0,
+        "g" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+
+    let bytes = (number * factor) as u64;
+    Some((bytes > 0).then_some(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_with_units() {
+        assert_eq!(parse_rate("500k"), Some(Some(500 * 1024)));
+        assert_eq!(parse_rate("10M"), Some(Some(10 * 1024 * 1024)));
+        assert_eq!(parse_rate("1.5MB/s"), Some(Some(1536 * 1024)));
+        assert_eq!(parse_rate(" 2 GiB "), Some(Some(2 * 1024 * 1024 * 1024)));
+        assert_eq!(parse_rate("4096"), Some(Some(4096)));
+    }
+
+    #[test]
+    fn unlimited_and_invalid_rates() {
+        for unlimited in ["", "0", "off", "OFF", "0k"] {
+            assert_eq!(parse_rate(unlimited), Some(None), "{unlimited}");
+        }
+        for invalid in ["fast", "10x", "1.2.3k", "-5k"] {
+            assert_eq!(parse_rate(invalid), None, "{invalid}");
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_body_is_split_into_chunks() {
+        let data = vec![7u8; 3 * BODY_CHUNK_SIZE + 100];
+        let throttle = Arc::new(Throttle::new(None));
+        let mut body = throttled_body(SdkBody::from(data.clone()), vec![throttle]);
+
+        let mut sizes = Vec::new();
+        let mut received = Vec::new();
+        while let Some(frame) = std::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await
+        {
+            let chunk = frame.unwrap().into_data().unwrap();
+            sizes.push(chunk.len());
+            received.extend_from_slice(&chunk);
+        }
+        assert_eq!(
+            sizes,
+            [BODY_CHUNK_SIZE, BODY_CHUNK_SIZE, BODY_CHUNK_SIZE, 100]
+        );
+        assert_eq!(received, data);
+    }
+}
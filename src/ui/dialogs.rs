@@ -1,12 +1,14 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
-use super::helpers::centered_rect;
-use crate::app::App;
+use super::helpers::{centered_rect, format_size};
+use crate::app::{App, ConfigFormState};
+use crate::operations::conflict::{ConflictResolution, FileStat};
 
 pub fn draw_delete_confirmation(f: &mut Frame, app: &App) {
     let area = centered_rect(70, 30, f.area());
@@ -80,6 +82,159 @@ pub fn draw_delete_confirmation(f: &mut Frame, app: &App) {
     f.render_widget(help, chunks[3]);
 }
 
+pub fn draw_external_upload(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 40, f.area());
+    let state = &app.external_edit;
+
+    let block = Block::default()
+        .title("Upload Changes")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let Some(file) = &state.upload else {
+        return;
+    };
+    let target = file
+        .s3
+        .as_ref()
+        .map(|origin| format!("s3://{}/{}", origin.bucket, origin.key))
+        .unwrap_or_default();
+
+    let question = Paragraph::new(format!(
+        "'{}' was changed in the editor.\nUpload it back to the same key?",
+        file.name
+    ))
+    .style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(question, chunks[0]);
+
+    let target_text = Paragraph::new(target)
+        .style(Style::default().fg(Color::Cyan))
+        .alignment(Alignment::Center);
+    f.render_widget(target_text, chunks[1]);
+
+    if state.modified_remotely {
+        let warning = Paragraph::new(
+            "The object was modified or deleted on S3 since it was opened (ETag changed). \
+             Uploading overwrites those changes.",
+        )
+        .style(Style::default().fg(Color::Red))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+        f.render_widget(warning, chunks[2]);
+    }
+
+    let upload_label = if state.modified_remotely {
+        "Overwrite"
+    } else {
+        "Upload"
+    };
+    let buttons = if state.button == 0 {
+        Paragraph::new(format!("[ {} ]  Discard", upload_label.to_uppercase()))
+            .style(
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center)
+    } else {
+        Paragraph::new(format!("{upload_label}  [ DISCARD ]"))
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+    };
+    f.render_widget(buttons, chunks[3]);
+
+    let help = Paragraph::new("←/→ or Tab: Select | Enter: Confirm | Esc: Keep local copy")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[4]);
+}
+
+pub fn draw_transfer_conflict(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 60, f.area());
+    let state = &app.transfer_conflict;
+
+    let block = Block::default()
+        .title("Destination Exists")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let options = ConflictResolution::ALL;
+    let mut constraints = vec![Constraint::Length(4)];
+    constraints.extend(options.iter().map(|_| Constraint::Length(1)));
+    constraints.extend([Constraint::Min(0), Constraint::Length(1)]);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(constraints)
+        .split(inner);
+
+    let describe = |stat: &FileStat| {
+        let modified = stat
+            .modified
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "unknown date".to_string());
+        format!("{}, {modified}", format_size(stat.size))
+    };
+    let destination = state
+        .operation
+        .as_ref()
+        .map(|op| op.destination.clone())
+        .unwrap_or_default();
+    let info = Paragraph::new(vec![
+        Line::from(Span::styled(destination, Style::default().fg(Color::Cyan))),
+        Line::from(format!("Existing: {}", describe(&state.destination))),
+        Line::from(format!("New:      {}", describe(&state.source))),
+    ])
+    .wrap(Wrap { trim: false });
+    f.render_widget(info, chunks[0]);
+
+    for (i, option) in options.iter().enumerate() {
+        let is_selected = i == state.selected;
+        let prefix = if is_selected { "● " } else { "○ " };
+        let style = if is_selected {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let text = Paragraph::new(format!("{prefix}{}", option.label())).style(style);
+        f.render_widget(text, chunks[i + 1]);
+    }
+
+    let help = Paragraph::new("↑/↓: Select | Enter: Confirm | Esc: Skip")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[options.len() + 2]);
+}
+
 pub fn draw_sort_dialog(f: &mut Frame, app: &App) {
     let area = centered_rect(60, 50, f.area());
 
@@ -270,7 +425,7 @@ pub fn draw_config_form(f: &mut Frame, app: &App) {
             Constraint::Length(3), // Description
             Constraint::Length(3), // Region
             Constraint::Length(3), // Endpoint URL
-            Constraint::Length(3), // Path Style (checkbox)
+            Constraint::Length(3), // Path Style + Anonymous (checkboxes)
             Constraint::Min(3),    // Roles
             Constraint::Length(3), // Buttons
             Constraint::Length(3), // Help
@@ -395,7 +550,32 @@ pub fn draw_config_form(f: &mut Frame, app: &App) {
     let path_style_para = Paragraph::new(path_style_text)
         .style(path_style_style)
         .block(Block::default().borders(Borders::ALL));
-    f.render_widget(path_style_para, form_chunks[5]);
+
+    let checkbox_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(form_chunks[5]);
+    f.render_widget(path_style_para, checkbox_chunks[0]);
+
+    // Anonymous checkbox
+    let anonymous_indicator = if app.config_form.anonymous {
+        "[✓]"
+    } else {
+        "[ ]"
+    };
+    let anonymous_style = if app.config_form.field == 6 {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let anonymous_para = Paragraph::new(format!(
+        "{anonymous_indicator} Anonymous (public bucket, no credentials)"
+    ))
+    .style(anonymous_style)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(anonymous_para, checkbox_chunks[1]);
 
     let roles_area = form_chunks[6];
     let role_block = Block::default().borders(Borders::ALL).title("Role ARNs");
@@ -414,7 +594,7 @@ pub fn draw_config_form(f: &mut Frame, app: &App) {
         .split(inner_area);
 
     for (i, role) in app.config_form.roles.iter().enumerate() {
-        let role_style = if app.config_form.field == i + 6 {
+        let role_style = if app.config_form.field == i + ConfigFormState::FIRST_ROLE_FIELD {
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD)
@@ -426,7 +606,7 @@ pub fn draw_config_form(f: &mut Frame, app: &App) {
         if i < role_chunks.len() {
             f.render_widget(role_para, role_chunks[i]);
 
-            if app.config_form.field == i + 6 {
+            if app.config_form.field == i + ConfigFormState::FIRST_ROLE_FIELD {
                 let cursor_x = role_chunks[i].x
                     + format!("[{}] ", i + 1).len() as u16
                     + app.config_form.cursor as u16;
@@ -442,7 +622,7 @@ pub fn draw_config_form(f: &mut Frame, app: &App) {
         f.render_widget(help_text, role_chunks[app.config_form.roles.len()]);
     }
 
-    let button_field = app.config_form.roles.len() + 6; // Changed from 4 to 6 (added 2 fields)
+    let button_field = app.config_form.button_field();
     let save_style = if app.config_form.field == button_field {
         Style::default()
             .fg(Color::Green)
@@ -450,26 +630,119 @@ pub fn draw_config_form(f: &mut Frame, app: &App) {
     } else {
         Style::default().fg(Color::Green)
     };
-    let cancel_style = if app.config_form.field == button_field + 1 {
+    let test_style = if app.config_form.field == button_field + 1 {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
+    let cancel_style = if app.config_form.field == button_field + 2 {
         Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(Color::Red)
     };
 
     let buttons = if app.config_form.field == button_field {
-        Paragraph::new("[ SAVE ]  Cancel")
+        Paragraph::new("[ SAVE ]  Test  Cancel")
             .style(save_style)
             .alignment(Alignment::Center)
     } else if app.config_form.field == button_field + 1 {
-        Paragraph::new("Save  [ CANCEL ]")
+        Paragraph::new("Save  [ TEST ]  Cancel")
+            .style(test_style)
+            .alignment(Alignment::Center)
+    } else if app.config_form.field == button_field + 2 {
+        Paragraph::new("Save  Test  [ CANCEL ]")
             .style(cancel_style)
             .alignment(Alignment::Center)
     } else {
-        Paragraph::new("Save  Cancel").alignment(Alignment::Center)
+        Paragraph::new("Save  Test  Cancel").alignment(Alignment::Center)
     };
     f.render_widget(buttons, form_chunks[7]);
 
-    let help = Paragraph::new("↑/↓: Navigate | Type: Edit | Space: Toggle Path Style | +: Add role | -: Remove role | Enter: Save/Cancel | Esc: Cancel")
+    let help = Paragraph::new("↑/↓: Navigate | Type: Edit | Space: Toggle checkbox | F5: Test connection | F7: Add role | F8: Remove role | Enter: Save/Cancel | Esc: Cancel")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help, chunks[2]);
+}
+
+pub fn draw_connection_test(f: &mut Frame, app: &App) {
+    use crate::operations::diagnostics::StepStatus;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(f.area());
+
+    let title = Paragraph::new(app.connection_test.title.as_str())
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for step in &app.connection_test.steps {
+        let (icon, color) = match &step.status {
+            StepStatus::Running => ("…", Color::Yellow),
+            StepStatus::Passed(_) => ("✓", Color::Green),
+            StepStatus::Failed { .. } => ("✗", Color::Red),
+            StepStatus::Skipped(_) => ("-", Color::DarkGray),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {icon} "), Style::default().fg(color)),
+            Span::styled(
+                step.name.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]));
+
+        match &step.status {
+            StepStatus::Running => {}
+            StepStatus::Passed(detail) | StepStatus::Skipped(detail) => {
+                lines.push(Line::from(Span::styled(
+                    format!("     {detail}"),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+            StepStatus::Failed {
+                code,
+                message,
+                hint,
+            } => {
+                lines.push(Line::from(Span::styled(
+                    format!("     [{code}] {message}"),
+                    Style::default().fg(Color::Red),
+                )));
+                if let Some(hint) = hint {
+                    lines.push(Line::from(Span::styled(
+                        format!("     → {hint}"),
+                        Style::default().fg(Color::Yellow),
+                    )));
+                }
+            }
+        }
+    }
+
+    let status = if app.connection_test.is_running() {
+        "Running..."
+    } else {
+        "Finished"
+    };
+    let steps = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(status))
+        .wrap(Wrap { trim: false });
+    f.render_widget(steps, chunks[1]);
+
+    let help = Paragraph::new("r/F5: Run again | Esc/Enter: Back to form")
         .style(Style::default().fg(Color::Gray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
@@ -482,6 +755,8 @@ pub fn draw_input_dialog(f: &mut Frame, app: &App) {
     let cursor_x = area.x + 1 + app.input.cursor_position as u16;
     let cursor_y = area.y + 1;
 
+    f.render_widget(ratatui::widgets::Clear, area);
+
     let input = Paragraph::new(app.input.buffer.as_str())
         .style(Style::default().fg(Color::Yellow))
         .block(
@@ -1,10 +1,11 @@
 use super::dialogs::{
-    draw_config_form, draw_delete_confirmation, draw_error_overlay, draw_info_overlay,
-    draw_input_dialog, draw_profile_config_form, draw_sort_dialog, draw_success_overlay,
+    draw_config_form, draw_connection_test, draw_delete_confirmation, draw_error_overlay,
+    draw_external_upload, draw_info_overlay, draw_input_dialog, draw_profile_config_form,
+    draw_sort_dialog, draw_success_overlay, draw_transfer_conflict,
 };
 use super::panels::draw_panel;
-use super::preview::{draw_file_content_preview, draw_image_preview};
-use super::widgets::draw_file_operation_queue;
+use super::preview::{draw_column_picker, draw_file_content_preview, draw_image_preview};
+use super::widgets::{draw_file_operation_queue, draw_queue_details};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
@@ -21,13 +22,27 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     match app.screen {
         Screen::DualPanel => draw_dual_panel(f, app),
         Screen::ConfigForm => draw_config_form(f, app),
+        Screen::ConnectionTest => draw_connection_test(f, app),
         Screen::ProfileConfigForm => draw_profile_config_form(f, app),
         Screen::SortDialog => draw_sort_dialog(f, app),
         Screen::DeleteConfirmation => draw_delete_confirmation(f, app),
         Screen::FileContentPreview => draw_file_content_preview(f, app),
         Screen::ImagePreview => draw_image_preview(f, app),
-        Screen::Input => draw_input_dialog(f, app),
+        Screen::Input => {
+            // Suchprompt über der Vorschau anzeigen
+            if app.prev_screen == Some(Screen::FileContentPreview) {
+                draw_file_content_preview(f, app);
+            }
+            draw_input_dialog(f, app)
+        }
         Screen::Help => draw_help(f, app),
+        Screen::QueueDetails => draw_queue_details(f, app),
+        Screen::TransferConflict => draw_transfer_conflict(f, app),
+        Screen::ExternalEditUpload => draw_external_upload(f, app),
+        Screen::ColumnPicker => {
+            draw_file_content_preview(f, app);
+            draw_column_picker(f, app);
+        }
     }
 
     // Render error/success/info overlays on top of any screen
@@ -195,7 +210,7 @@ fn draw_help(f: &mut Frame, _app: &App) {
         "Navigation:",
         "  ↑/↓         - Navigate in active panel",
         "  Tab         - Switch between left/right panel",
-        "  Enter       - Open selected item (profile/folder/bucket)",
+        "  Enter       - Open selected item (profile/folder/bucket/.zip/.tar/.tar.gz)",
         "  Backspace   - Go to parent directory",
         "",
         "Function Keys:",
@@ -203,13 +218,50 @@ fn draw_help(f: &mut Frame, _app: &App) {
         "  F2          - Sort (Name, Size, Date)",
         "  F3          - Edit (Profile/Bucket) / View file (S3/Filesystem)",
         "  F4          - Filter items",
-        "  F5          - Copy from active to inactive panel",
+        "  F5          - Copy from active to inactive panel (asks if target exists)",
+        "                (inside an archive: extract file/folder to the other panel)",
         "  F6          - Rename file/folder (S3/Filesystem)",
         "  F7          - Create bucket config (BucketList) / Create folder (S3/Filesystem)",
         "  F8          - Delete selected item",
         "  F9          - Toggle Advanced Mode",
         "  F10         - Quit application",
         "  F12         - Toggle active panel between AWS-S3-Mode or local Filesystem",
+        "  o           - Edit file in $EDITOR (S3: upload changes)",
+        "  v           - View file in $PAGER",
+        "",
+        "Transfer Queue (when visible):",
+        "  q           - Focus queue (↑/↓ select, ESC leave)",
+        "  x           - Cancel running transfer",
+        "  p           - Pause/resume running (or selected) transfer",
+        "  Shift+↑/↓   - Move selected waiting transfer (queue focused)",
+        "  t           - Start selected transfer next (queue focused)",
+        "  + / -       - Raise/lower priority (queue focused)",
+        "  d           - Delete selected transfer (queue focused)",
+        "  c           - Clear finished transfers",
+        "  r / R       - Resume paused / retry selected failed / retry all failed",
+        "  b           - Change bandwidth limit",
+        "  e           - Queue details (times, speed, errors)",
+        "",
+        "File Preview (F3):",
+        "  / or ?      - Search forward/backward (regex, loads further chunks)",
+        "  n / N       - Next match / match in opposite direction",
+        "  F4 / h      - Toggle text / hex view (binary files open in hex)",
+        "  g           - Go to byte offset (decimal or 0x hex)",
+        "  p           - Pretty-print JSON (toggle)",
+        "  t           - CSV/TSV: toggle table / text view",
+        "  ← / →       - Table: scroll columns",
+        "  c           - Table: column picker (show/hide, go to column)",
+        "  f           - Follow mode: append new data at the end (scrolling up pauses, End resumes)",
+        "  .gz .zst .bz2 .xz are decompressed while scrolling (forward only)",
+        "  .parquet .avro .jsonl show schema and the first 1000 records (c: schema)",
+        "  Esc         - Cancel running search / close preview",
+        "",
+        "Image Preview (F3):",
+        "  + / - / 0   - Zoom in / out / fit",
+        "  ← ↑ ↓ →     - Pan the zoomed image",
+        "  n / p       - Next / previous image in the folder (also PgDn/PgUp)",
+        "  i           - Toggle format, color depth and EXIF info",
+        "  Enter       - Load an image above image_max_size_mb",
         "",
         "General:",
         "  q/Esc       - Quit application / Close dialog",
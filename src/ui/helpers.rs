@@ -27,6 +27,21 @@ pub fn format_size(size: u64) -> String {
     }
 }
 
+/// Short duration like `42s`, `3m05s` or `1h02m`
+pub fn format_duration(secs: u64) -> String {
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+pub fn format_speed(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_size(bytes_per_sec as u64))
+}
+
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
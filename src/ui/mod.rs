@@ -1,8 +1,8 @@
 mod dialogs;
 mod draw;
-mod helpers;
+pub(crate) mod helpers;
 mod panels;
-mod preview;
+pub(crate) mod preview;
 mod widgets;
 
 pub use draw::draw;
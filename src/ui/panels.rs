@@ -126,7 +126,7 @@ pub fn draw_panel(
                         ItemType::ParentDir => "📁 ..".to_string(),
                         _ => {
                             if let ItemData::Bucket(bucket_config) = &item.data {
-                                match (
+                                let display = match (
                                     &bucket_config.description,
                                     bucket_config.role_chain.is_empty(),
                                 ) {
@@ -145,6 +145,15 @@ pub fn draw_panel(
                                         bucket_config.name,
                                         bucket_config.role_chain.len()
                                     ),
+                                };
+                                // With a project config active, show where each entry comes from
+                                match bucket_config.source_label() {
+                                    Some(source)
+                                        if config_manager.project_config_path().is_some() =>
+                                    {
+                                        format!("{display} ‹{source}›")
+                                    }
+                                    _ => display,
                                 }
                             } else {
                                 item.name.clone()
@@ -227,62 +236,23 @@ pub fn draw_panel(
                 };
                 ListItem::new(display).style(style)
             }));
+
+            // Restored from the last session, connects when the panel becomes active
+            if panel.s3_manager.is_none() && items.is_empty() {
+                items.push(
+                    ListItem::new("  (not connected - activate panel to reconnect)")
+                        .style(Style::default().fg(Color::DarkGray)),
+                );
+            }
             (title, items)
         }
         PanelType::LocalFilesystem { path } => {
             let title = format!("Local: {}", path.display());
-            let mut items: Vec<ListItem> = Vec::new();
-
-            // Calculate dynamic filename width based on available space
-            // Reserve: 2 (borders) + 10 (size) + 2 (spacing) + 16 (date) + 1 (spacing) = 31
-            let available_width = area.width.saturating_sub(4) as usize; // 4 for borders + padding
-            let reserved_width = 29; // size (10) + spacing (2) + date (16) + spacing (1)
-            let name_width = available_width.saturating_sub(reserved_width).max(20);
-
-            items.extend(panel.list_model.iter().enumerate().map(|(i, item)| {
-                use crate::models::list::ItemType;
-
-                let (icon_name, size_str, modified_str) = match &item.item_type {
-                    ItemType::ParentDir => ("📁 ..".to_string(), "".to_string(), "".to_string()),
-                    ItemType::Directory => (
-                        format!("📁 {}", item.name),
-                        "<DIR>".to_string(),
-                        "".to_string(),
-                    ),
-                    ItemType::File => {
-                        let size_str = item.size.map(format_size).unwrap_or_default();
-                        let modified_str = item
-                            .modified
-                            .map(|m| {
-                                m.with_timezone(&chrono::Local)
-                                    .format("%Y-%m-%d %H:%M")
-                                    .to_string()
-                            })
-                            .unwrap_or_default();
-                        (format!("📄 {}", item.name), size_str, modified_str)
-                    }
-                };
-
-                let display = format!(
-                    "{:<width$} {:>10}  {}",
-                    truncate_string(&icon_name, name_width),
-                    size_str,
-                    modified_str,
-                    width = name_width
-                );
-
-                let style = if i == panel.selected_index && is_active {
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
-                } else if matches!(item.item_type, ItemType::Directory | ItemType::ParentDir) {
-                    Style::default().fg(Color::LightBlue)
-                } else {
-                    Style::default()
-                };
-                ListItem::new(display).style(style)
-            }));
-            (title, items)
+            (title, file_list_items(panel, area, is_active))
+        }
+        PanelType::Archive { location, prefix } => {
+            let title = format!("Archive: {}/{prefix}", location.name());
+            (title, file_list_items(panel, area, is_active))
         }
     };
 
@@ -324,3 +294,60 @@ pub fn draw_panel(
 
     f.render_widget(list, area);
 }
+
+/// Einträge mit Größe und Datum (lokale Verzeichnisse und Archive)
+fn file_list_items(panel: &Panel, area: Rect, is_active: bool) -> Vec<ListItem<'static>> {
+    let mut items: Vec<ListItem> = Vec::new();
+
+    // Calculate dynamic filename width based on available space
+    // Reserve: 2 (borders) + 10 (size) + 2 (spacing) + 16 (date) + 1 (spacing) = 31
+    let available_width = area.width.saturating_sub(4) as usize; // 4 for borders + padding
+    let reserved_width = 29; // size (10) + spacing (2) + date (16) + spacing (1)
+    let name_width = available_width.saturating_sub(reserved_width).max(20);
+
+    items.extend(panel.list_model.iter().enumerate().map(|(i, item)| {
+        use crate::models::list::ItemType;
+
+        let (icon_name, size_str, modified_str) = match &item.item_type {
+            ItemType::ParentDir => ("📁 ..".to_string(), "".to_string(), "".to_string()),
+            ItemType::Directory => (
+                format!("📁 {}", item.name),
+                "<DIR>".to_string(),
+                "".to_string(),
+            ),
+            ItemType::File => {
+                let size_str = item.size.map(format_size).unwrap_or_default();
+                let modified_str = item
+                    .modified
+                    .map(|m| {
+                        m.with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                (format!("📄 {}", item.name), size_str, modified_str)
+            }
+        };
+
+        let display = format!(
+            "{:<width$} {:>10}  {}",
+            truncate_string(&icon_name, name_width),
+            size_str,
+            modified_str,
+            width = name_width
+        );
+
+        let style = if i == panel.selected_index && is_active {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else if matches!(item.item_type, ItemType::Directory | ItemType::ParentDir) {
+            Style::default().fg(Color::LightBlue)
+        } else {
+            Style::default()
+        };
+        ListItem::new(display).style(style)
+    }));
+
+    items
+}
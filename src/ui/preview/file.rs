@@ -1,13 +1,17 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::Line,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
 use crate::app::App;
+use crate::models::preview::{FileContentPreview, ViewMode, HEX_BYTES_PER_ROW};
 use crate::ui::helpers::format_size;
+use crate::ui::preview::highlight::{Highlights, Syntax};
+use crate::ui::preview::table::draw_table_view;
+use std::ops::Range;
 
 /// Rendert Text-Datei-Vorschau
 pub fn draw_file_content_preview(f: &mut Frame, app: &mut App) {
@@ -32,19 +36,49 @@ pub fn draw_file_content_preview(f: &mut Frame, app: &mut App) {
             .constraints([Constraint::Min(0), Constraint::Length(1)])
             .split(inner);
 
+        if preview.view_mode == ViewMode::Hex {
+            draw_hex_view(f, preview, chunks[0], chunks[1]);
+            return;
+        }
+
         // Update viewport width for visual line calculation
         preview.viewport_width = chunks[0].width;
+        preview.viewport_height = chunks[0].height;
+
+        let search_info = match (&app.preview_search_task, &preview.search) {
+            (Some(task), _) => format!(
+                "Searching {} {} / {} (Esc cancel) | ",
+                if task.backward { "↑" } else { "↓" },
+                format_size(task.progress.load(std::sync::atomic::Ordering::Relaxed)),
+                format_size(task.total)
+            ),
+            (None, Some(search)) => format!(
+                "{}{} | ",
+                if search.backward { "?" } else { "/" },
+                search.pattern
+            ),
+            (None, None) => String::new(),
+        };
+        let search_info = match &preview.follow {
+            Some(follow) if follow.paused => format!("FOLLOW paused | {search_info}"),
+            Some(_) => format!(
+                "FOLLOW {}s | {search_info}",
+                app.config_manager
+                    .app_config
+                    .settings
+                    .follow_interval_secs
+                    .max(1)
+            ),
+            None => search_info,
+        };
 
-        // Get visual lines (pre-wrapped based on viewport width)
-        let visual_lines = preview.get_visual_lines();
+        if preview.view_mode == ViewMode::Table {
+            draw_table_view(f, preview, &search_info, chunks[0], chunks[1]);
+            return;
+        }
 
-        // Render content with scroll (using visual lines, no need for Wrap)
-        let visible_lines: Vec<_> = visual_lines
-            .iter()
-            .skip(preview.scroll_offset)
-            .take(chunks[0].height as usize)
-            .map(|s| Line::from(s.as_str()))
-            .collect();
+        // Render content with scroll (pre-wrapped visual lines, no need for Wrap)
+        let visible_lines = visible_lines(preview, chunks[0].height as usize);
 
         let paragraph = Paragraph::new(visible_lines);
 
@@ -57,12 +91,12 @@ pub fn draw_file_content_preview(f: &mut Frame, app: &mut App) {
                 format!(
                     "Line {}/{}",
                     preview.scroll_offset + 1,
-                    preview.content.lines().count()
+                    preview.text().lines().count()
                 )
             }
             crate::models::preview::PreviewMode::Backward => {
                 // Backward mode: Line -X / LAST
-                let total_lines = preview.content.lines().count();
+                let total_lines = preview.text().lines().count();
                 let lines_from_end = total_lines.saturating_sub(preview.scroll_offset);
                 format!("Line -{lines_from_end} / LAST")
             }
@@ -79,13 +113,40 @@ pub fn draw_file_content_preview(f: &mut Frame, app: &mut App) {
             preview.content_start_offset == 0 && preview.byte_offset >= preview.file_size;
         let chunk_status = if is_fully_loaded { "FULL" } else { "CHUNK" };
 
+        let syntax = preview_syntax(preview);
+        let syntax_info = match (preview.pretty.is_some(), syntax.label()) {
+            (true, _) => " | JSON PRETTY".to_string(),
+            (false, Some(label)) => format!(" | {label}"),
+            (false, None) => String::new(),
+        };
+        let pretty_hint = if syntax == Syntax::Json {
+            " | p Pretty"
+        } else if preview.table.is_some() {
+            " | t Table"
+        } else {
+            ""
+        };
+
+        // Komprimierte Dateien lassen sich nur vorwärts lesen, Datensätze sind keine Datei-Bytes
+        let jump_hint = if preview.compressed.is_some() {
+            "Home/Esc"
+        } else if preview.records.is_some() {
+            "Home/End/Esc"
+        } else {
+            "F4 Hex | g Goto | Home/End/Esc"
+        };
+
         let info = format!(
-            " {} | {} | {} | Chunks: {} | Size: {} | ↑↓ Scroll | Home/End/Esc ",
+            " {}{} | {} | {}{} | Chunks: {} | Size: {} | ↑↓ Scroll | / ? n N Search{} | {} ",
+            search_info,
             line_info,
             mode_str,
             chunk_status,
+            syntax_info,
             preview.chunk_load_count,
-            format_size(preview.file_size as u64)
+            size_info(preview),
+            pretty_hint,
+            jump_hint
         );
 
         let info_paragraph = Paragraph::new(info)
@@ -95,6 +156,227 @@ pub fn draw_file_content_preview(f: &mut Frame, app: &mut App) {
     }
 }
 
+/// Hex-Dump: Offset | 16 Bytes hex | ASCII
+fn draw_hex_view(f: &mut Frame, preview: &FileContentPreview, content: Rect, info_area: Rect) {
+    let hex = &preview.hex;
+    // Offset-Spalte breit genug für die Dateigröße, mindestens 8 Stellen
+    let offset_width = format!("{:x}", preview.file_size).len().max(8);
+    let offset_style = Style::default().fg(Color::DarkGray);
+    let zero_style = Style::default().fg(Color::DarkGray);
+    let ascii_style = Style::default().fg(Color::Green);
+
+    let lines: Vec<Line> = hex
+        .bytes
+        .chunks(HEX_BYTES_PER_ROW)
+        .enumerate()
+        .skip(hex.scroll_row)
+        .take(content.height as usize)
+        .map(|(row, bytes)| {
+            let offset = hex.start_offset as usize + row * HEX_BYTES_PER_ROW;
+            let mut spans = vec![Span::styled(
+                format!("{offset:0offset_width$x}  "),
+                offset_style,
+            )];
+            for i in 0..HEX_BYTES_PER_ROW {
+                let gap = if i == HEX_BYTES_PER_ROW / 2 { " " } else { "" };
+                match bytes.get(i) {
+                    Some(0) => spans.push(Span::styled(format!("{gap}00 "), zero_style)),
+                    Some(b) => spans.push(Span::raw(format!("{gap}{b:02x} "))),
+                    None => spans.push(Span::raw(format!("{gap}   "))),
+                }
+            }
+            let ascii: String = bytes
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            spans.push(Span::styled(format!(" {ascii}"), ascii_style));
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), content);
+
+    let top = hex.top_offset().min(preview.file_size);
+    let percent = if preview.file_size > 0 {
+        top * 100 / preview.file_size
+    } else {
+        100
+    };
+    let jump_hint = if preview.compressed.is_some() {
+        "Home/End/Esc"
+    } else {
+        "F4 Text | g Goto | Home/End/Esc"
+    };
+    let info = format!(
+        " HEX | Offset 0x{top:x} ({percent}%) | Chunks: {} | Size: {} | ↑↓ Scroll | {jump_hint} ",
+        preview.chunk_load_count,
+        size_info(preview)
+    );
+    let info_paragraph = Paragraph::new(info)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(info_paragraph, info_area);
+}
+
+/// Dateigröße für die Info-Leiste; bei komprimierten Dateien komprimiert → dekomprimiert
+/// (≥ solange noch nicht alles dekomprimiert ist)
+pub(super) fn size_info(preview: &FileContentPreview) -> String {
+    match &preview.compressed {
+        Some(stream) => format!(
+            "{} {} → {}{}",
+            format_size(stream.compressed_size as u64),
+            stream.compression.label(),
+            if stream.finished { "" } else { "≥" },
+            format_size(preview.byte_offset.max(preview.hex.end_offset()) as u64)
+        ),
+        // Strukturierte Vorschau: Größe der Datei, nicht der Datensätze als CSV
+        None => format_size(
+            preview
+                .records
+                .as_ref()
+                .map_or(preview.file_size, |r| r.file_size) as u64,
+        ),
+    }
+}
+
+/// Syntax der Vorschau nach Dateiendung (formatiertes JSON immer als JSON)
+fn preview_syntax(preview: &FileContentPreview) -> Syntax {
+    if preview.pretty.is_some() {
+        Syntax::Json
+    } else {
+        Syntax::from_filename(preview.content_name())
+    }
+}
+
+/// Sichtbare visuelle Zeilen ab scroll_offset, mit Syntax-Hervorhebung und Suchtreffern
+fn visible_lines(preview: &FileContentPreview, height: usize) -> Vec<Line<'static>> {
+    let width = if preview.viewport_width == 0 {
+        usize::MAX
+    } else {
+        preview.viewport_width as usize
+    };
+    let match_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let current_style = match_style.bg(Color::LightRed).add_modifier(Modifier::BOLD);
+
+    let syntax = preview_syntax(preview);
+
+    let mut lines = Vec::with_capacity(height);
+    let mut visual = 0;
+    for (offset, line) in preview.lines_with_offsets() {
+        let line_height = preview.wrapped_height(line);
+        if visual + line_height <= preview.scroll_offset {
+            visual += line_height;
+            continue;
+        }
+
+        // Treffer dieser Zeile als (Byte-Bereich, Stil)
+        let matches: Vec<_> = preview
+            .search
+            .iter()
+            .flat_map(|search| {
+                search.regex.find_iter(line).map(move |m| {
+                    let is_current = search.current.as_ref().is_some_and(|c| {
+                        c.start == offset + m.start() && c.end == offset + m.end()
+                    });
+                    (
+                        m.range(),
+                        if is_current {
+                            current_style
+                        } else {
+                            match_style
+                        },
+                    )
+                })
+            })
+            .filter(|(range, _)| !range.is_empty())
+            .collect();
+        let highlights = syntax.highlight(line);
+
+        // In Stücke der Viewport-Breite aufteilen (an Zeichengrenzen)
+        let mut boundaries: Vec<usize> =
+            line.char_indices().map(|(i, _)| i).step_by(width).collect();
+        boundaries.push(line.len());
+        if boundaries.len() == 1 {
+            boundaries.insert(0, 0); // Leere Zeile
+        }
+
+        for piece in boundaries.windows(2) {
+            if visual >= preview.scroll_offset {
+                lines.push(styled_piece(
+                    line,
+                    piece[0]..piece[1],
+                    &highlights,
+                    &matches,
+                ));
+                if lines.len() >= height {
+                    return lines;
+                }
+            }
+            visual += 1;
+        }
+    }
+    lines
+}
+
+/// Teil `range` einer Zeile als Spans; Suchtreffer haben Vorrang vor dem Syntax-Stil
+fn styled_piece(
+    line: &str,
+    range: Range<usize>,
+    syntax: &Highlights,
+    matches: &Highlights,
+) -> Line<'static> {
+    // Nur die Bereiche, die dieses Stück berühren (beide Listen sind aufsteigend sortiert)
+    let overlapping = |list: &'_ Highlights| -> Vec<(Range<usize>, Style)> {
+        let first = list.partition_point(|(r, _)| r.end <= range.start);
+        list[first..]
+            .iter()
+            .take_while(|(r, _)| r.start < range.end)
+            .cloned()
+            .collect()
+    };
+    let syntax = overlapping(syntax);
+    let matches = overlapping(matches);
+
+    let mut cuts = vec![range.start, range.end];
+    for (r, _) in syntax.iter().chain(&matches) {
+        cuts.extend([r.start, r.end].into_iter().filter(|c| range.contains(c)));
+    }
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let style_at = |pos: usize| {
+        let find = |list: &[(Range<usize>, Style)]| {
+            list.iter()
+                .find(|(r, _)| r.contains(&pos))
+                .map(|(_, style)| *style)
+        };
+        find(&matches).or_else(|| find(&syntax)).unwrap_or_default()
+    };
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut current: Option<(usize, Style)> = None;
+    for segment in cuts.windows(2) {
+        let style = style_at(segment[0]);
+        match current {
+            Some((_, s)) if s == style => {}
+            Some((start, s)) => {
+                spans.push(Span::styled(line[start..segment[0]].to_string(), s));
+                current = Some((segment[0], style));
+            }
+            None => current = Some((segment[0], style)),
+        }
+    }
+    if let Some((start, style)) = current {
+        spans.push(Span::styled(line[start..range.end].to_string(), style));
+    }
+    Line::from(spans)
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
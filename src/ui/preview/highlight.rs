@@ -0,0 +1,310 @@
+//! Syntax-Highlighting für die Text-Vorschau. Arbeitet zeilenweise ohne Zustand, damit es
+//! mit Zeilenumbruch und nachgeladenen Chunks (auch rückwärts) funktioniert.
+
+use ratatui::style::{Color, Modifier, Style};
+use regex::Regex;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Syntax {
+    Plain,
+    Json,
+    Yaml,
+    Csv(char),
+    Log,
+    Config,
+}
+
+/// Hervorgehobene Bereiche einer Zeile (Byte-Bereiche, aufsteigend, ohne Überlappung)
+pub type Highlights = Vec<(Range<usize>, Style)>;
+
+const CSV_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::LightBlue,
+    Color::LightRed,
+];
+
+fn punct() -> Style {
+    Style::default().fg(Color::DarkGray)
+}
+fn key() -> Style {
+    Style::default().fg(Color::Cyan)
+}
+fn string() -> Style {
+    Style::default().fg(Color::Green)
+}
+fn number() -> Style {
+    Style::default().fg(Color::Magenta)
+}
+fn keyword() -> Style {
+    Style::default().fg(Color::Yellow)
+}
+fn comment() -> Style {
+    Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC)
+}
+
+impl Syntax {
+    pub fn from_filename(filename: &str) -> Self {
+        let lower = filename.to_lowercase();
+        let extension = lower.rsplit_once('.').map_or("", |(_, ext)| ext);
+        match extension {
+            "json" | "jsonl" | "ndjson" | "geojson" | "har" => Syntax::Json,
+            "yaml" | "yml" => Syntax::Yaml,
+            "csv" => Syntax::Csv(','),
+            "tsv" | "tab" => Syntax::Csv('\t'),
+            "log" | "out" | "err" => Syntax::Log,
+            "toml" | "ini" | "cfg" | "conf" | "properties" | "env" => Syntax::Config,
+            // Rotierte Logs: app.log.1
+            _ if lower.contains(".log.") => Syntax::Log,
+            _ => Syntax::Plain,
+        }
+    }
+
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            Syntax::Plain => None,
+            Syntax::Json => Some("JSON"),
+            Syntax::Yaml => Some("YAML"),
+            Syntax::Csv(',') => Some("CSV"),
+            Syntax::Csv(_) => Some("TSV"),
+            Syntax::Log => Some("LOG"),
+            Syntax::Config => Some("CONFIG"),
+        }
+    }
+
+    pub fn highlight(self, line: &str) -> Highlights {
+        match self {
+            Syntax::Plain => Vec::new(),
+            Syntax::Json => json(line),
+            Syntax::Yaml => yaml(line),
+            Syntax::Csv(delimiter) => csv(line, delimiter),
+            Syntax::Log => log(line),
+            Syntax::Config => config(line),
+        }
+    }
+}
+
+/// Ende eines Strings, der bei `start` mit `quote` beginnt (exklusiv, notfalls Zeilenende)
+fn string_end(line: &str, start: usize, quote: u8) -> usize {
+    let bytes = line.as_bytes();
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn json(line: &str) -> Highlights {
+    let bytes = line.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(line, i, b'"');
+                // Ein String vor `:` ist ein Schlüssel
+                let is_key = line[end..].trim_start().starts_with(':');
+                out.push((i..end, if is_key { key() } else { string() }));
+                i = end;
+            }
+            b'{' | b'}' | b'[' | b']' | b',' | b':' => {
+                out.push((i..i + 1, punct()));
+                i += 1;
+            }
+            b'-' | b'0'..=b'9' => {
+                let end = i + bytes[i..]
+                    .iter()
+                    .position(|b| !matches!(b, b'0'..=b'9' | b'.' | b'e' | b'E' | b'+' | b'-'))
+                    .unwrap_or(bytes.len() - i);
+                out.push((i..end, number()));
+                i = end;
+            }
+            b'a'..=b'z' => {
+                let end = i + bytes[i..]
+                    .iter()
+                    .position(|b| !b.is_ascii_alphabetic())
+                    .unwrap_or(bytes.len() - i);
+                if matches!(&line[i..end], "true" | "false" | "null") {
+                    out.push((i..end, keyword()));
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    out
+}
+
+/// Stil eines einzelnen Werts (YAML-Skalar, Config-Wert)
+fn scalar(value: &str) -> Option<Style> {
+    static NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^[-+]?(\d[\d_]*(\.\d+)?([eE][-+]?\d+)?|0x[0-9a-fA-F]+|\.inf|\.nan)$").unwrap()
+    });
+    let value = value.trim();
+    if value.starts_with(['"', '\'']) {
+        Some(string())
+    } else if matches!(
+        value.to_lowercase().as_str(),
+        "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+    ) {
+        Some(keyword())
+    } else if NUMBER.is_match(value) {
+        Some(number())
+    } else {
+        None
+    }
+}
+
+/// Kommentar (`#`, am Anfang oder nach Leerzeichen, nicht in Anführungszeichen)
+fn comment_start(line: &str, from: usize) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            q @ (b'"' | b'\'') => i = string_end(line, i, q),
+            b'#' if i == 0 || bytes[i - 1].is_ascii_whitespace() => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Wert ab `start` hervorheben, ggf. mit Kommentar dahinter
+fn value_with_comment(out: &mut Highlights, line: &str, start: usize) {
+    let end = comment_start(line, start).unwrap_or(line.len());
+    let value = &line[start..end];
+    if let Some(style) = scalar(value) {
+        let trimmed_start = start + (value.len() - value.trim_start().len());
+        out.push((trimmed_start..start + value.trim_end().len(), style));
+    }
+    if end < line.len() {
+        out.push((end..line.len(), comment()));
+    }
+}
+
+fn yaml(line: &str) -> Highlights {
+    static KEY: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"^(\s*(?:-\s+)*)("[^"]*"|'[^']*'|[^\s#'"{\[][^#]*?)\s*:(\s|$)"#).unwrap()
+    });
+    let mut out = Vec::new();
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+
+    if trimmed.starts_with('#') {
+        out.push((indent..line.len(), comment()));
+        return out;
+    }
+    if trimmed.starts_with("---") || trimmed.starts_with("...") {
+        out.push((indent..line.len(), Style::default().fg(Color::Magenta)));
+        return out;
+    }
+
+    let mut value_start = indent;
+    if let Some(caps) = KEY.captures(line) {
+        let prefix = caps.get(1).unwrap();
+        let key_match = caps.get(2).unwrap();
+        if let Some(dash) = line[prefix.range()].find('-') {
+            out.push((dash..dash + 1, keyword()));
+        }
+        out.push((key_match.range(), key()));
+        let colon = key_match.end() + line[key_match.end()..].find(':').unwrap_or(0);
+        out.push((colon..colon + 1, punct()));
+        value_start = colon + 1;
+    } else if let Some(rest) = trimmed.strip_prefix("- ") {
+        out.push((indent..indent + 1, keyword()));
+        value_start = line.len() - rest.len();
+    }
+    value_with_comment(&mut out, line, value_start);
+    out
+}
+
+fn csv(line: &str, delimiter: char) -> Highlights {
+    let mut out = Vec::new();
+    let mut column = 0;
+    let mut field_start = 0;
+    let mut in_quotes = false;
+    for (i, c) in line.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == delimiter && !in_quotes {
+            let color = CSV_COLORS[column % CSV_COLORS.len()];
+            out.push((field_start..i, Style::default().fg(color)));
+            out.push((i..i + c.len_utf8(), punct()));
+            column += 1;
+            field_start = i + c.len_utf8();
+        }
+    }
+    let color = CSV_COLORS[column % CSV_COLORS.len()];
+    out.push((field_start..line.len(), Style::default().fg(color)));
+    out
+}
+
+fn log(line: &str) -> Highlights {
+    static TIMESTAMP: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"^\[?(\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}([.,]\d+)?(Z|[+-]\d{2}:?\d{2})?|[A-Z][a-z]{2} +\d{1,2} \d{2}:\d{2}:\d{2})\]?",
+        )
+        .unwrap()
+    });
+    static LEVEL: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"\b(FATAL|CRITICAL|ERROR|ERR|WARNING|WARN|INFO|DEBUG|TRACE)\b").unwrap()
+    });
+    let mut out = Vec::new();
+    let mut from = 0;
+    if let Some(m) = TIMESTAMP.find(line) {
+        out.push((m.range(), Style::default().fg(Color::Blue)));
+        from = m.end();
+    }
+    // Nur das erste Level-Wort (der Rest der Nachricht bleibt unverändert)
+    if let Some(m) = LEVEL.find_at(line, from) {
+        let style = match m.as_str() {
+            "FATAL" | "CRITICAL" | "ERROR" | "ERR" => {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            }
+            "WARNING" | "WARN" => Style::default().fg(Color::Yellow),
+            "INFO" => Style::default().fg(Color::Green),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        out.push((m.range(), style));
+    }
+    out
+}
+
+fn config(line: &str) -> Highlights {
+    static KEY_VALUE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^(\s*)([^=:\s#;\[][^=:]*?)\s*([=:])").unwrap());
+    let mut out = Vec::new();
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+
+    if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        out.push((indent..line.len(), comment()));
+    } else if trimmed.starts_with('[') {
+        let end = line.rfind(']').map_or(line.len(), |i| i + 1);
+        out.push((
+            indent..end,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        if let Some(start) = comment_start(line, end) {
+            out.push((start..line.len(), comment()));
+        }
+    } else if let Some(caps) = KEY_VALUE.captures(line) {
+        out.push((caps.get(2).unwrap().range(), key()));
+        let separator = caps.get(3).unwrap();
+        out.push((separator.range(), punct()));
+        value_with_comment(&mut out, line, separator.end());
+    }
+    out
+}
@@ -1,12 +1,15 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    widgets::{Block, Borders, Clear, Paragraph},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 use ratatui_image::{picker::Picker, StatefulImage};
 
 use crate::app::App;
+use crate::models::preview::ImagePreview;
+use crate::ui::helpers::format_size;
 
 /// Rendert Bild-Vorschau
 pub fn draw_image_preview(f: &mut Frame, app: &App) {
@@ -15,17 +18,19 @@ pub fn draw_image_preview(f: &mut Frame, app: &App) {
     let title = if app.image_preview_loading {
         " Loading Image... ".to_string()
     } else if let Some(ref preview) = app.image_preview {
-        let dimensions_str = if let Some((w, h)) = preview.dimensions {
-            format!(" [{w}x{h}] ")
+        let (w, h) = preview.dimensions;
+        let zoom = if preview.zoom_level > 0 {
+            format!(" {:.0}%", preview.zoom() * 100.0)
         } else {
             String::new()
         };
         format!(
-            " {}{} ({}) ",
+            " {} [{w}x{h}]{zoom} ({}) ",
             preview.filename,
-            dimensions_str,
             preview.source_display()
         )
+    } else if let Some(ref pending) = app.image_preview_pending {
+        format!(" {} ", pending.filename)
     } else {
         " Image Preview ".to_string()
     };
@@ -44,7 +49,7 @@ pub fn draw_image_preview(f: &mut Frame, app: &App) {
         .constraints([Constraint::Min(0), Constraint::Length(1)])
         .split(inner);
 
-    // Show loading or image
+    // Show loading, size confirmation or image
     if app.image_preview_loading {
         // Loading indicator
         let msg =
@@ -53,34 +58,112 @@ pub fn draw_image_preview(f: &mut Frame, app: &App) {
             .alignment(Alignment::Center)
             .style(Style::default().fg(Color::Yellow));
         f.render_widget(paragraph, chunks[0]);
+    } else if let Some(ref pending) = app.image_preview_pending {
+        let msg = format!(
+            "This image is {} - larger than the limit of {} (image_max_size_mb).\n\n\
+             Loading downloads and decodes the whole file.\n\n\
+             Enter - Load anyway    Esc - Cancel    n / p - Next / previous image",
+            format_size(pending.size),
+            format_size(pending.limit)
+        );
+        let paragraph = Paragraph::new(msg)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: false });
+        f.render_widget(paragraph, chunks[0]);
     } else if let Some(ref preview) = app.image_preview {
-        // Try to render image with ratatui-image
-        if let Ok(dyn_img) = image::load_from_memory(&preview.image_data) {
-            use ratatui_image::picker::ProtocolType;
-
-            // Create picker with assumed font size (width, height) - works cross-platform
-            // FontSize is just (u16, u16) representing character cell dimensions in pixels
-            let mut picker = Picker::new((8, 16));
-            picker.protocol_type = ProtocolType::Halfblocks;
-
-            let mut image_state = picker.new_resize_protocol(dyn_img);
-            let image_widget = StatefulImage::new(None);
-            f.render_stateful_widget(image_widget, chunks[0], &mut image_state);
+        let image_area = if preview.show_info && chunks[0].width >= 60 {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(36)])
+                .split(chunks[0]);
+            draw_image_info(f, preview, columns[1]);
+            columns[0]
         } else {
-            let msg = "Failed to load image.\n\nPress Esc to close.";
-            let paragraph = Paragraph::new(msg).alignment(Alignment::Center);
-            f.render_widget(paragraph, chunks[0]);
-        }
+            chunks[0]
+        };
+
+        use ratatui_image::picker::ProtocolType;
+
+        // Create picker with assumed font size (width, height) - works cross-platform
+        // FontSize is just (u16, u16) representing character cell dimensions in pixels
+        let mut picker = Picker::new((8, 16));
+        picker.protocol_type = ProtocolType::Halfblocks;
+
+        // Beim Zoomen nur den sichtbaren Ausschnitt übergeben
+        let (x, y, w, h) = preview.visible_region();
+        let region = if preview.zoom_level > 0 {
+            preview.image.crop_imm(x, y, w, h)
+        } else {
+            preview.image.clone()
+        };
+        let mut image_state = picker.new_resize_protocol(region);
+        let image_widget = StatefulImage::new(None);
+        f.render_stateful_widget(image_widget, image_area, &mut image_state);
     }
 
     // Info bar
-    let info = " Image Preview | Esc Close ";
+    let info = if app.image_preview_pending.is_some() {
+        " Enter Load | n/p Next/Prev | Esc Close "
+    } else {
+        " +/- Zoom | 0 Fit | ←↑↓→ Pan | n/p Next/Prev | i Info | Esc Close "
+    };
     let info_paragraph = Paragraph::new(info)
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
     f.render_widget(info_paragraph, chunks[1]);
 }
 
+/// Seitenleiste mit Format, Farbtiefe und EXIF-Angaben
+fn draw_image_info(f: &mut Frame, preview: &ImagePreview, area: Rect) {
+    let label = Style::default().fg(Color::Yellow);
+    let row = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{name:<13}"), label),
+            Span::raw(value),
+        ])
+    };
+    let info = &preview.info;
+    let (w, h) = preview.dimensions;
+
+    let mut lines = vec![
+        row("Format", info.format.clone()),
+        row("File size", format_size(info.file_size)),
+        row("Dimensions", format!("{w} x {h}")),
+        row(
+            "Color",
+            format!("{} ({} bit/pixel)", info.color, info.bits_per_pixel),
+        ),
+        row("Zoom", format!("{:.0}%", preview.zoom() * 100.0)),
+        Line::from(""),
+    ];
+    if info.exif.is_empty() {
+        lines.push(Line::styled(
+            "No EXIF data",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        lines.push(Line::styled(
+            "EXIF",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+        for (name, value) in &info.exif {
+            lines.push(row(name, value.clone()));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::LEFT)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -1,5 +1,8 @@
 pub mod file;
+pub mod highlight;
 pub mod image;
+pub mod table;
 
 pub use file::draw_file_content_preview;
 pub use image::draw_image_preview;
+pub use table::draw_column_picker;
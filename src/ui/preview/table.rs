@@ -0,0 +1,288 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::file::size_info;
+use crate::app::App;
+use crate::models::preview::{FileContentPreview, PreviewMode, TableData, MAX_COLUMN_WIDTH};
+use crate::ui::helpers::centered_rect;
+
+const SEPARATOR: &str = " │ ";
+const SEPARATOR_WIDTH: usize = 3;
+
+/// CSV/TSV als Tabelle: Kopfzeile fest oben, Spalten auf die geladene Breite ausgerichtet
+pub fn draw_table_view(
+    f: &mut Frame,
+    preview: &mut FileContentPreview,
+    search_info: &str,
+    content: Rect,
+    info_area: Rect,
+) {
+    // Strukturierte Vorschau: Kennzahlen der Datei über der Tabelle
+    let content = match &preview.records {
+        Some(records) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(content);
+            f.render_widget(
+                Paragraph::new(format!("{}: {}", records.format, records.summary))
+                    .style(Style::default().fg(Color::Yellow)),
+                chunks[0],
+            );
+            chunks[1]
+        }
+        None => content,
+    };
+    let Some(table) = preview.table.as_mut() else {
+        return;
+    };
+    let page = (content.height as usize).saturating_sub(2);
+    // Nicht hinter die letzte volle Seite scrollen (z.B. nach End)
+    table.scroll_row = table.scroll_row.min(table.rows.len().saturating_sub(page));
+    let table = &*table;
+
+    let columns = visible_columns(table, content.width as usize);
+    let header_style = if table.header.is_some() {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let names: Vec<String> = columns.iter().map(|(c, _)| table.column_name(*c)).collect();
+    let mut lines = vec![
+        cells_line(&columns, &names, header_style, None, None),
+        Line::styled(
+            columns
+                .iter()
+                .map(|(_, w)| "─".repeat(*w))
+                .collect::<Vec<_>>()
+                .join("─┼─"),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+
+    let current = preview
+        .search
+        .as_ref()
+        .and_then(|s| s.current.as_ref())
+        .map(|m| table.row_at_byte(m.start));
+    let regex = preview.search.as_ref().map(|s| &s.regex);
+    for row in table.scroll_row..(table.scroll_row + page).min(table.rows.len()) {
+        let fields = table.fields(&preview.content, row);
+        let values: Vec<String> = columns
+            .iter()
+            .map(|(c, _)| fields.get(*c).cloned().unwrap_or_default())
+            .collect();
+        lines.push(cells_line(
+            &columns,
+            &values,
+            Style::default(),
+            regex,
+            Some(current == Some(row)),
+        ));
+    }
+    if table.rows.is_empty() {
+        lines.push(Line::styled(
+            "(no complete rows loaded)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    f.render_widget(Paragraph::new(lines), content);
+
+    let row_info = match preview.preview_mode {
+        PreviewMode::Forward => format!("Row {}/{}", table.scroll_row + 1, table.rows.len()),
+        PreviewMode::Backward => format!(
+            "Row -{} / LAST",
+            table.rows.len().saturating_sub(table.scroll_row)
+        ),
+    };
+    let hidden = table.hidden.iter().filter(|h| **h).count();
+    let column_info = if hidden > 0 {
+        format!(
+            "Col {}/{} ({hidden} hidden)",
+            table.first_column + 1,
+            table.column_count()
+        )
+    } else {
+        format!("Col {}/{}", table.first_column + 1, table.column_count())
+    };
+    if let Some(records) = &preview.records {
+        let total = records
+            .total_rows
+            .map_or(String::new(), |total| format!(" of {total}"));
+        let info = format!(
+            " {search_info}{} | Row {}/{}{total} | {column_info} | Size: {} | ↑↓←→ Scroll | c Schema | t Text | / ? n N Search | Esc ",
+            records.format,
+            table.scroll_row + 1,
+            table.rows.len(),
+            size_info(preview)
+        );
+        let info_paragraph = Paragraph::new(info)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(info_paragraph, info_area);
+        return;
+    }
+    let is_fully_loaded =
+        preview.content_start_offset == 0 && preview.byte_offset >= preview.file_size;
+    let hex_hint = if preview.compressed.is_some() {
+        ""
+    } else {
+        "F4 Hex | "
+    };
+    let info = format!(
+        " {search_info}TABLE | {row_info} | {column_info} | {} | {} | Chunks: {} | Size: {} | ↑↓←→ Scroll | c Columns | t Text | / ? n N Search | {hex_hint}Esc ",
+        if preview.preview_mode == PreviewMode::Forward { "FWD" } else { "BWD" },
+        if is_fully_loaded { "FULL" } else { "CHUNK" },
+        preview.chunk_load_count,
+        size_info(preview)
+    );
+    let info_paragraph = Paragraph::new(info)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(info_paragraph, info_area);
+}
+
+/// Sichtbare Spalten ab first_column als (Spalte, Breite); die letzte wird ggf. gekürzt
+fn visible_columns(table: &TableData, width: usize) -> Vec<(usize, usize)> {
+    let mut columns = Vec::new();
+    let mut used = 0;
+    for column in (table.first_column..table.column_count()).filter(|c| !table.hidden[*c]) {
+        let name_width = table.column_name(column).chars().count();
+        let column_width = table.widths[column].max(name_width.min(MAX_COLUMN_WIDTH));
+        let gap = if columns.is_empty() {
+            0
+        } else {
+            SEPARATOR_WIDTH
+        };
+        if used + gap >= width {
+            break;
+        }
+        let column_width = column_width.min(width - used - gap);
+        columns.push((column, column_width));
+        used += gap + column_width;
+    }
+    columns
+}
+
+/// Eine Tabellenzeile; Zahlen rechtsbündig, Suchtreffer hervorgehoben
+/// (`current_row`: Zeile des aktuellen Treffers)
+fn cells_line(
+    columns: &[(usize, usize)],
+    values: &[String],
+    style: Style,
+    regex: Option<&regex::Regex>,
+    current_row: Option<bool>,
+) -> Line<'static> {
+    let match_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let match_style = if current_row == Some(true) {
+        match_style.bg(Color::LightRed).add_modifier(Modifier::BOLD)
+    } else {
+        match_style
+    };
+
+    let mut spans = Vec::new();
+    for (i, ((_, width), value)) in columns.iter().zip(values).enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(
+                SEPARATOR,
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        let value = value.trim();
+        let text: String = if value.chars().count() > *width {
+            let mut cut: String = value.chars().take(width.saturating_sub(1)).collect();
+            cut.push('…');
+            cut
+        } else {
+            value.to_string()
+        };
+        let padding = " ".repeat(width - text.chars().count());
+        let is_number = current_row.is_some() && value.parse::<f64>().is_ok();
+        if is_number {
+            spans.push(Span::raw(padding.clone()));
+        }
+
+        let mut pos = 0;
+        for m in regex.into_iter().flat_map(|r| r.find_iter(&text)) {
+            if m.is_empty() {
+                continue;
+            }
+            spans.push(Span::styled(text[pos..m.start()].to_string(), style));
+            spans.push(Span::styled(m.as_str().to_string(), match_style));
+            pos = m.end();
+        }
+        spans.push(Span::styled(text[pos..].to_string(), style));
+        if !is_number {
+            spans.push(Span::raw(padding));
+        }
+    }
+    Line::from(spans)
+}
+
+/// Spaltenwähler über der Tabelle
+pub fn draw_column_picker(f: &mut Frame, app: &App) {
+    let Some(preview) = app.file_content_preview.as_ref() else {
+        return;
+    };
+    let Some(table) = preview.table.as_ref() else {
+        return;
+    };
+    let area = centered_rect(50, 60, f.area());
+    f.render_widget(Clear, area);
+
+    // Strukturierte Vorschau: Spalten mit ihrem Typ laut Schema
+    let types = preview.records.as_ref();
+    let block = Block::default()
+        .title(if types.is_some() {
+            " Schema "
+        } else {
+            " Columns "
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let height = chunks[0].height as usize;
+    let skip = (table.picker_selected + 1).saturating_sub(height);
+    let lines: Vec<Line> = (0..table.column_count())
+        .skip(skip)
+        .take(height)
+        .map(|column| {
+            let checkbox = if table.hidden[column] { "[ ]" } else { "[x]" };
+            let name = table.column_name(column);
+            let text = match types.and_then(|r| r.field_type(&name)) {
+                Some(field_type) => format!("{checkbox} {:>3}  {name}  ({field_type})", column + 1),
+                None => format!("{checkbox} {:>3}  {name}", column + 1),
+            };
+            if column == table.picker_selected {
+                Line::styled(
+                    text,
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Line::raw(text)
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new("↑/↓: Select | Space: Show/Hide | Enter: Go to column | Esc: Close")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[1]);
+}
@@ -1,12 +1,13 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Row, Sparkline, Table, Wrap},
     Frame,
 };
 
-use super::helpers::format_size;
+use super::helpers::{format_duration, format_size, format_speed};
 use crate::app::App;
+use crate::operations::queue::Priority;
 
 pub fn draw_file_operation_queue(f: &mut Frame, app: &App, area: Rect) {
     if app.file_operation_queue.is_empty() {
@@ -38,14 +39,67 @@ pub fn draw_file_operation_queue(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let focus_indicator = if app.queue_focused { " [FOCUSED]" } else { "" };
+    let limit_info = match app.bandwidth.global().limit() {
+        Some(limit) => format!(" [limit {}/s]", format_size(limit)),
+        None => String::new(),
+    };
+    let paused_count = app
+        .file_operation_queue
+        .iter()
+        .filter(|op| op.status == crate::operations::OperationStatus::Paused)
+        .count();
+    let paused_info = if paused_count > 0 {
+        format!(", {paused_count} paused - 'r' resume")
+    } else {
+        String::new()
+    };
+    let failed_count = app
+        .file_operation_queue
+        .iter()
+        .filter(|op| matches!(op.status, crate::operations::OperationStatus::Failed(_)))
+        .count();
+    let failed_info = if failed_count > 0 {
+        format!(", {failed_count} failed - 'r' retry | 'R' retry all")
+    } else {
+        String::new()
+    };
+
+    // Speed of the running transfer and time left for everything still queued
+    let speed = app
+        .file_operation_queue
+        .iter()
+        .find_map(|op| op.speed())
+        .filter(|speed| *speed > 0.0);
+    let speed_info = match speed {
+        Some(speed) => {
+            let remaining: u64 = app
+                .file_operation_queue
+                .iter()
+                .filter(|op| {
+                    matches!(
+                        op.status,
+                        crate::operations::OperationStatus::Pending
+                            | crate::operations::OperationStatus::InProgress
+                    )
+                })
+                .map(|op| op.total_size.saturating_sub(op.transferred))
+                .sum();
+            format!(
+                ", {} ETA {}",
+                format_speed(speed),
+                format_duration((remaining as f64 / speed) as u64)
+            )
+        }
+        None => String::new(),
+    };
 
     let title = if running_count > 0 {
         format!(
-            "File Operations ({running_count} running, {queued_count} queued){scroll_info}{focus_indicator} - 'q' focus | '↑↓' scroll | 'x' cancel | 'd' delete | 'c' clear"
+            "File Operations ({running_count} running, {queued_count} queued{speed_info}{paused_info}{failed_info}){scroll_info}{limit_info}{focus_indicator} - 'q' focus | '↑↓' scroll | 'x' cancel | 'p' pause | 'd' delete | 'c' clear | 'b' limit | 'e' details"
         )
     } else {
         format!(
-            "File Operations ({queued_count} queued){scroll_info}{focus_indicator} - 'q' focus | '↑↓' scroll | 'd' delete | 'c' clear"
+            "File Operations ({queued_count} queued{paused_info}{failed_info}){scroll_info}{limit_info}{focus_indicator} - 'q' focus | '↑↓' scroll | 'd' delete | 'c' clear | 'b' limit | 'e' details"
         )
     };
 
@@ -110,29 +164,9 @@ pub fn draw_file_operation_queue(f: &mut Frame, app: &App, area: Rect) {
         let is_selected = (total_count - 1 - actual_rev_index) == selected;
 
         // Operation info line
-        let op_type = match operation.operation_type {
-            crate::operations::OperationType::Upload => "↑ Upload",
-            crate::operations::OperationType::Download => "↓ Download",
-            crate::operations::OperationType::Copy => "→ Copy",
-            crate::operations::OperationType::S3Copy => "⇄ S3 Copy",
-            crate::operations::OperationType::Rename => "✎ Rename",
-        };
-
-        let status_icon = match &operation.status {
-            crate::operations::OperationStatus::Pending => "⏸",
-            crate::operations::OperationStatus::InProgress => "⟳",
-            crate::operations::OperationStatus::Completed => "✓",
-            crate::operations::OperationStatus::Cancelled => "⊗",
-            crate::operations::OperationStatus::Failed(_) => "✗",
-        };
-
-        let status_color = match &operation.status {
-            crate::operations::OperationStatus::Pending => Color::Yellow,
-            crate::operations::OperationStatus::InProgress => Color::Cyan,
-            crate::operations::OperationStatus::Completed => Color::Green,
-            crate::operations::OperationStatus::Cancelled => Color::Yellow,
-            crate::operations::OperationStatus::Failed(_) => Color::Red,
-        };
+        let op_type = op_type_label(&operation.operation_type);
+        let status_icon = status_icon(&operation.status);
+        let status_color = status_color(&operation.status);
 
         // Format file sizes
         let transferred_str = format_size(operation.transferred);
@@ -142,13 +176,49 @@ pub fn draw_file_operation_queue(f: &mut Frame, app: &App, area: Rect) {
         // Add selection indicator if this is the selected item
         let selection_mark = if is_selected { "►" } else { " " };
 
+        // Speed and ETA while running, duration once done
+        let timing_info = match (&operation.status, operation.speed(), operation.eta()) {
+            (crate::operations::OperationStatus::InProgress, Some(speed), Some(eta)) => {
+                format!(
+                    " │ {} ETA {}",
+                    format_speed(speed),
+                    format_duration(eta.as_secs())
+                )
+            }
+            (crate::operations::OperationStatus::Completed, _, _) => operation
+                .duration()
+                .map(|d| format!(" │ {}", format_duration(d.num_seconds().max(0) as u64)))
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        let max_retries = app.config_manager.app_config.settings.transfer_retries;
+        let retry_info = match &operation.status {
+            crate::operations::OperationStatus::InProgress if operation.retries > 0 => {
+                format!(" │ retry {}/{max_retries}", operation.retries)
+            }
+            crate::operations::OperationStatus::Failed(_) if operation.retries > 0 => {
+                format!(" │ after {} retries", operation.retries)
+            }
+            _ => String::new(),
+        };
+        let priority_info = match operation.priority {
+            Priority::High => " │ ▲ high",
+            Priority::Low => " │ ▼ low",
+            Priority::Normal => "",
+        };
+        let extra_info = format!("{timing_info}{retry_info}{priority_info}");
+
         // Calculate dynamic path width based on available space
         // Fixed parts: selection_mark (2) + status_icon (2) + op_type (12) + separators (8) + size (20) + percentage (7) = 51
-        let available_width = chunks[chunk_idx].width.saturating_sub(51) as usize;
+        let available_width = chunks[chunk_idx]
+            .width
+            .saturating_sub(51 + extra_info.chars().count() as u16)
+            as usize;
         let path_width = (available_width / 2).max(15); // At least 15 chars per path
 
         let info_text = format!(
-            "{} {} {} │ {} → {} │ {} / {} ({:3}%)",
+            "{} {} {} │ {} → {} │ {} / {} ({:3}%){}",
             selection_mark,
             status_icon,
             op_type,
@@ -156,7 +226,8 @@ pub fn draw_file_operation_queue(f: &mut Frame, app: &App, area: Rect) {
             truncate_filename(&operation.destination, path_width),
             transferred_str,
             total_str,
-            percentage
+            percentage,
+            extra_info
         );
 
         let info_style = if is_selected {
@@ -180,6 +251,190 @@ pub fn draw_file_operation_queue(f: &mut Frame, app: &App, area: Rect) {
     }
 }
 
+fn op_type_label(operation_type: &crate::operations::OperationType) -> &'static str {
+    match operation_type {
+        crate::operations::OperationType::Upload => "↑ Upload",
+        crate::operations::OperationType::Download => "↓ Download",
+        crate::operations::OperationType::Copy => "→ Copy",
+        crate::operations::OperationType::S3Copy => "⇄ S3 Copy",
+        crate::operations::OperationType::Rename => "✎ Rename",
+    }
+}
+
+fn status_icon(status: &crate::operations::OperationStatus) -> &'static str {
+    match status {
+        crate::operations::OperationStatus::Pending => "⏸",
+        crate::operations::OperationStatus::InProgress => "⟳",
+        crate::operations::OperationStatus::Paused => "‖",
+        crate::operations::OperationStatus::Completed => "✓",
+        crate::operations::OperationStatus::Cancelled => "⊗",
+        crate::operations::OperationStatus::Failed(_) => "✗",
+    }
+}
+
+fn status_color(status: &crate::operations::OperationStatus) -> Color {
+    match status {
+        crate::operations::OperationStatus::Pending => Color::Yellow,
+        crate::operations::OperationStatus::InProgress => Color::Cyan,
+        crate::operations::OperationStatus::Paused => Color::Gray,
+        crate::operations::OperationStatus::Completed => Color::Green,
+        crate::operations::OperationStatus::Cancelled => Color::Yellow,
+        crate::operations::OperationStatus::Failed(_) => Color::Red,
+    }
+}
+
+/// Full-screen queue view: throughput history, all transfers with start/end times
+/// and durations, and the details (error message) of the selected one
+pub fn draw_queue_details(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6),
+            Constraint::Min(5),
+            Constraint::Length(8),
+        ])
+        .split(f.area());
+
+    // Throughput history
+    let history = app.throughput.samples();
+    let peak = history.iter().copied().max().unwrap_or(0);
+    let current = app
+        .file_operation_queue
+        .iter()
+        .find_map(|op| op.speed())
+        .map(format_speed)
+        .unwrap_or_else(|| "idle".to_string());
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Throughput (last {}s) - now {current}, peak {}",
+            history.len(),
+            format_speed(peak as f64)
+        )))
+        .data(&history)
+        .style(Style::default().fg(Color::Cyan));
+    f.render_widget(sparkline, chunks[0]);
+
+    // Transfer list (newest first, like the queue widget)
+    let header = Row::new(vec![
+        "",
+        "Type",
+        "Source → Destination",
+        "Size",
+        "Started",
+        "Finished",
+        "Duration",
+        "Avg speed",
+    ])
+    .style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let total = app.file_operation_queue.len();
+    let selected = app.selected_queue_index.min(total.saturating_sub(1));
+    let visible = chunks[1].height.saturating_sub(3) as usize;
+    // Row of the selected item in the reversed list, kept in view
+    let selected_row = total.saturating_sub(1) - selected.min(total.saturating_sub(1));
+    let skip = selected_row.saturating_sub(visible.saturating_sub(1));
+
+    let time = |t: Option<chrono::DateTime<chrono::Local>>| {
+        t.map(|t| t.format("%H:%M:%S").to_string())
+            .unwrap_or_default()
+    };
+
+    let rows: Vec<Row> = app
+        .file_operation_queue
+        .iter()
+        .enumerate()
+        .rev()
+        .skip(skip)
+        .take(visible)
+        .map(|(index, op)| {
+            let duration = op.duration();
+            let avg_speed = match (&op.status, duration) {
+                (crate::operations::OperationStatus::Completed, Some(d)) => {
+                    let secs = d.num_milliseconds().max(1) as f64 / 1000.0;
+                    format_speed(op.total_size as f64 / secs)
+                }
+                _ => op.speed().map(format_speed).unwrap_or_default(),
+            };
+            let duration = duration.map(|d| d.num_seconds().max(0) as u64);
+            let style = if index == selected {
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Black)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(status_color(&op.status))
+            };
+
+            Row::new(vec![
+                status_icon(&op.status).to_string(),
+                op_type_label(&op.operation_type).to_string(),
+                format!("{} → {}", op.source, op.destination),
+                format_size(op.total_size),
+                time(op.stats.started_at),
+                time(op.stats.finished_at),
+                duration.map(format_duration).unwrap_or_default(),
+                avg_speed,
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Length(11),
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Transfers ({total}) - '↑↓' select | 'r' retry | 'R' retry failed | ESC back"
+    )));
+    f.render_widget(table, chunks[1]);
+
+    // Details of the selected transfer
+    let details = match app.file_operation_queue.get(selected) {
+        Some(op) => {
+            let status = match &op.status {
+                crate::operations::OperationStatus::Failed(error) => format!("Failed: {error}"),
+                other => format!("{other:?}"),
+            };
+            let mut lines = vec![
+                format!("Source:      {}", op.source),
+                format!("Destination: {}", op.destination),
+                format!(
+                    "Transferred: {} / {}",
+                    format_size(op.transferred),
+                    format_size(op.total_size)
+                ),
+                format!("Status:      {status}"),
+            ];
+            if op.retries > 0 {
+                lines.push(format!("Retries:     {}", op.retries));
+            }
+            if op.is_waiting() {
+                lines.push(format!("Priority:    {:?}", op.priority));
+            }
+            lines.join("\n")
+        }
+        None => String::new(),
+    };
+    let details = Paragraph::new(details)
+        .block(Block::default().borders(Borders::ALL).title("Details"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(details, chunks[2]);
+}
+
 fn draw_progress_bar(percentage: u16, width: usize) -> String {
     let filled_width = ((width as f64 * percentage as f64) / 100.0) as usize;
     let empty_width = width.saturating_sub(filled_width);